@@ -4,9 +4,11 @@ pub mod services;
 mod unit_tests {
 
     use serde_json::Value;
+    use serverless_workflow_core::models::call::HttpOutputFormat;
     use serverless_workflow_core::models::duration::*;
     use serverless_workflow_core::models::error::OneOfErrorDefinitionOrReference;
     use crate::services::workflow::WorkflowBuilder;
+    use serverless_workflow_core::models::resource::*;
     use serverless_workflow_core::models::task::*;
     use serverless_workflow_core::models::timeout::*;
     use std::collections::HashMap;
@@ -497,4 +499,138 @@ mod unit_tests {
             wait_duration);
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn build_grpc_call_should_work() {
+        //arrange
+        let task_name = "grpc-task";
+        let proto_uri = "https://fake.com/greet.proto";
+        let service_name = "GreeterApi.Greeter";
+        let service_host = "localhost";
+        let service_port = 5011;
+        let method = "SayHello";
+
+        //act
+        let workflow = WorkflowBuilder::new()
+            .use_dsl("1.0.0")
+            .with_namespace("namespace")
+            .with_name("fake-name")
+            .with_version("1.0.0")
+            .do_(task_name, |task| {
+                task.call_grpc(|grpc| {
+                    grpc.with_proto(|p| { p.with_endpoint_uri(proto_uri); })
+                        .with_service(|s| {
+                            s.with_name(service_name)
+                                .with_host(service_host)
+                                .with_port(service_port);
+                        })
+                        .with_method(method)
+                        .with_argument("name", json!("${ .user.preferredDisplayName }"));
+                }).unwrap();
+            })
+            .build();
+
+        //assert
+        let task = workflow.do_.entries[0].get(task_name).unwrap();
+        if let TaskDefinition::Call(call_task) = task {
+            let arguments = call_task.as_grpc().unwrap();
+            assert_eq!(call_task.call, "grpc");
+            assert_eq!(arguments.proto.endpoint, OneOfEndpointDefinitionOrUri::Uri(proto_uri.to_string()));
+            assert_eq!(arguments.service.name, service_name);
+            assert_eq!(arguments.service.host, service_host);
+            assert_eq!(arguments.service.port, Some(service_port));
+            assert_eq!(arguments.method, method);
+            assert!(arguments.arguments.unwrap().contains_key("name"));
+        }
+        else {
+            panic!("Expected a CallTaskDefinition");
+        }
+    }
+
+    #[test]
+    fn build_openapi_call_should_work() {
+        //arrange
+        let task_name = "openapi-task";
+        let document_uri = "https://petstore.swagger.io/v2/swagger.json";
+        let operation_id = "findPetsByStatus";
+
+        //act
+        let workflow = WorkflowBuilder::new()
+            .use_dsl("1.0.0")
+            .with_namespace("namespace")
+            .with_name("fake-name")
+            .with_version("1.0.0")
+            .do_(task_name, |task| {
+                task.call_openapi(|openapi| {
+                    openapi.with_document(|d| { d.with_endpoint_uri(document_uri); })
+                        .with_operation_id(operation_id)
+                        .with_parameter("status", json!("available"))
+                        .with_output_format(HttpOutputFormat::Response);
+                }).unwrap();
+            })
+            .build();
+
+        //assert
+        let task = workflow.do_.entries[0].get(task_name).unwrap();
+        if let TaskDefinition::Call(call_task) = task {
+            let arguments = call_task.as_openapi().unwrap();
+            assert_eq!(call_task.call, "openapi");
+            assert_eq!(arguments.document.endpoint, OneOfEndpointDefinitionOrUri::Uri(document_uri.to_string()));
+            assert_eq!(arguments.operation_id, operation_id);
+            assert_eq!(arguments.parameters.unwrap().get("status"), Some(&json!("available")));
+            assert_eq!(arguments.output, Some(HttpOutputFormat::Response));
+        }
+        else {
+            panic!("Expected a CallTaskDefinition");
+        }
+    }
+
+    #[test]
+    fn build_asyncapi_call_should_work() {
+        //arrange
+        let task_name = "asyncapi-task";
+        let document_uri = "https://fake.com/asyncapi.json";
+        let operation = "findPetsByStatus";
+        let server = "staging";
+        let payload = json!({ "petId": "${ .pet.id }" });
+
+        //act
+        let workflow = WorkflowBuilder::new()
+            .use_dsl("1.0.0")
+            .with_namespace("namespace")
+            .with_name("fake-name")
+            .with_version("1.0.0")
+            .do_(task_name, |task| {
+                task.call_asyncapi(|asyncapi| {
+                    asyncapi.with_document(|d| { d.with_endpoint_uri(document_uri); })
+                        .with_operation(operation)
+                        .with_server(server)
+                        .with_message(|m| { m.with_payload(payload.clone()); })
+                        .subscribe(|s| {
+                            s.with_filter("${ .correlationId == $context.correlationId }")
+                                .consume_amount(5)
+                                .consume_for(OneOfDurationOrIso8601Expression::Duration(Duration::from_minutes(1)))
+                                .foreach(|f| { f.with_item("message"); });
+                        });
+                }).unwrap();
+            })
+            .build();
+
+        //assert
+        let task = workflow.do_.entries[0].get(task_name).unwrap();
+        if let TaskDefinition::Call(call_task) = task {
+            let arguments = call_task.as_asyncapi().unwrap();
+            assert_eq!(call_task.call, "asyncapi");
+            assert_eq!(arguments.operation, operation);
+            assert_eq!(arguments.server.unwrap().name, server);
+            assert_eq!(arguments.message.unwrap().payload, Some(payload));
+            let subscription = arguments.subscription.unwrap();
+            assert_eq!(subscription.consume.amount, Some(5));
+            assert_eq!(subscription.consume.for_, Some(OneOfDurationOrIso8601Expression::Duration(Duration::from_minutes(1))));
+            assert_eq!(subscription.foreach.unwrap().item, Some("message".to_string()));
+        }
+        else {
+            panic!("Expected a CallTaskDefinition");
+        }
+    }
+
+}
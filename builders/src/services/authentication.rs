@@ -287,8 +287,8 @@ impl OAuth2AuthenticationSchemeDefinitionBuilder{
     }
 
     /// Sets the grant type to use
-    pub fn with_grant_type(&mut self, grant: &str) -> &mut Self{
-        self.scheme.grant = Some(grant.to_string());
+    pub fn with_grant_type(&mut self, grant: OAuth2GrantType) -> &mut Self{
+        self.scheme.grant = Some(grant);
         self
     }
 
@@ -386,8 +386,8 @@ impl OpenIDConnectSchemeDefinitionBuilder{
     }
 
     /// Sets the grant type to use
-    pub fn with_grant_type(&mut self, grant: &str) -> &mut Self{
-        self.scheme.grant = Some(grant.to_string());
+    pub fn with_grant_type(&mut self, grant: OAuth2GrantType) -> &mut Self{
+        self.scheme.grant = Some(grant);
         self
     }
 
@@ -492,8 +492,8 @@ impl OAuth2AuthenticationClientDefinitionBuilder {
     }
 
     /// Sets the OAUTH2 client's authentication method
-    pub fn with_authentication_method(&mut self, method: &str) -> &mut Self{
-        self.client.authentication = Some(method.to_string());
+    pub fn with_authentication_method(&mut self, method: OAuth2ClientAuthenticationMethod) -> &mut Self{
+        self.client.authentication = Some(method);
         self
     }
 
@@ -516,8 +516,8 @@ impl OAuth2AuthenticationRequestDefinitionBuilder {
     }
 
     /// Configures the OAuth2AuthenticationRequestDefinition to build to use the specified encoding
-    pub fn with_encoding(&mut self, encoding: &str) -> &mut Self{
-        self.request.encoding = encoding.to_string();
+    pub fn with_encoding(&mut self, encoding: OAuth2RequestEncoding) -> &mut Self{
+        self.request.encoding = encoding;
         self
     }
 
@@ -0,0 +1,394 @@
+use crate::services::authentication::*;
+use crate::services::task::*;
+use serde_json::Value;
+use serverless_workflow_core::models::call::*;
+use serverless_workflow_core::models::duration::*;
+use serverless_workflow_core::models::event::*;
+use std::collections::HashMap;
+
+/// Represents the service used to build GrpcCallArguments
+pub struct GrpcCallArgumentsBuilder{
+    arguments: GrpcCallArguments
+}
+impl GrpcCallArgumentsBuilder{
+
+    /// Initializes a new GrpcCallArgumentsBuilder
+    pub fn new() -> Self{
+        Self { arguments: GrpcCallArguments::default() }
+    }
+
+    /// Configures the proto resource that describes the gRPC service to call
+    pub fn with_proto<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut ExternalResourceDefinitionBuilder){
+        let mut builder = ExternalResourceDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.proto = builder.build();
+        self
+    }
+
+    /// Configures the gRPC service to call
+    pub fn with_service<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut GrpcServiceDefinitionBuilder){
+        let mut builder = GrpcServiceDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.service = builder.build();
+        self
+    }
+
+    /// Sets the name of the gRPC service method to call
+    pub fn with_method(&mut self, method: &str) -> &mut Self{
+        self.arguments.method = method.to_string();
+        self
+    }
+
+    /// Adds a new argument to call the gRPC method with
+    pub fn with_argument(&mut self, name: &str, value: Value) -> &mut Self{
+        if self.arguments.arguments.is_none(){
+            self.arguments.arguments = Some(HashMap::new());
+        }
+        if let Some(arguments) = &mut self.arguments.arguments {
+            arguments.insert(name.to_string(), value);
+        }
+        self
+    }
+
+    /// Sets the arguments to call the gRPC method with
+    pub fn with_arguments(&mut self, arguments: HashMap<String, Value>) -> &mut Self{
+        self.arguments.arguments = Some(arguments);
+        self
+    }
+
+    /// Builds the configured GrpcCallArguments
+    pub fn build(self) -> GrpcCallArguments{
+        self.arguments
+    }
+
+}
+impl Default for GrpcCallArgumentsBuilder{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+
+/// Represents the service used to build GrpcServiceDefinitions
+pub struct GrpcServiceDefinitionBuilder{
+    service: GrpcServiceDefinition
+}
+impl GrpcServiceDefinitionBuilder{
+
+    /// Initializes a new GrpcServiceDefinitionBuilder
+    pub fn new() -> Self{
+        Self { service: GrpcServiceDefinition::default() }
+    }
+
+    /// Sets the name of the gRPC service to call
+    pub fn with_name(&mut self, name: &str) -> &mut Self{
+        self.service.name = name.to_string();
+        self
+    }
+
+    /// Sets the hostname of the gRPC service to call
+    pub fn with_host(&mut self, host: &str) -> &mut Self{
+        self.service.host = host.to_string();
+        self
+    }
+
+    /// Sets the port of the gRPC service to call
+    pub fn with_port(&mut self, port: u16) -> &mut Self{
+        self.service.port = Some(port);
+        self
+    }
+
+    /// Configures the authentication policy used to call the gRPC service
+    pub fn with_authentication<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut AuthenticationPolicyDefinitionBuilder){
+        let mut builder = AuthenticationPolicyDefinitionBuilder::new();
+        setup(&mut builder);
+        self.service.authentication = Some(builder.build());
+        self
+    }
+
+    /// Builds the configured GrpcServiceDefinition
+    pub fn build(self) -> GrpcServiceDefinition{
+        self.service
+    }
+
+}
+impl Default for GrpcServiceDefinitionBuilder{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+
+/// Represents the service used to build OpenApiCallArguments
+pub struct OpenApiCallArgumentsBuilder{
+    arguments: OpenApiCallArguments
+}
+impl OpenApiCallArgumentsBuilder{
+
+    /// Initializes a new OpenApiCallArgumentsBuilder
+    pub fn new() -> Self{
+        Self { arguments: OpenApiCallArguments::default() }
+    }
+
+    /// Configures the OpenAPI document that describes the operation to call
+    pub fn with_document<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut ExternalResourceDefinitionBuilder){
+        let mut builder = ExternalResourceDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.document = builder.build();
+        self
+    }
+
+    /// Sets the id of the OpenAPI operation to call
+    pub fn with_operation_id(&mut self, operation_id: &str) -> &mut Self{
+        self.arguments.operation_id = operation_id.to_string();
+        self
+    }
+
+    /// Adds a new parameter to call the OpenAPI operation with
+    pub fn with_parameter(&mut self, name: &str, value: Value) -> &mut Self{
+        if self.arguments.parameters.is_none(){
+            self.arguments.parameters = Some(HashMap::new());
+        }
+        if let Some(parameters) = &mut self.arguments.parameters {
+            parameters.insert(name.to_string(), value);
+        }
+        self
+    }
+
+    /// Sets the parameters to call the OpenAPI operation with
+    pub fn with_parameters(&mut self, parameters: HashMap<String, Value>) -> &mut Self{
+        self.arguments.parameters = Some(parameters);
+        self
+    }
+
+    /// Configures the authentication policy used to call the OpenAPI operation
+    pub fn with_authentication<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut AuthenticationPolicyDefinitionBuilder){
+        let mut builder = AuthenticationPolicyDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.authentication = Some(builder.build());
+        self
+    }
+
+    /// Sets the format of the call's output
+    pub fn with_output_format(&mut self, format: HttpOutputFormat) -> &mut Self{
+        self.arguments.output = Some(format);
+        self
+    }
+
+    /// Sets whether or not redirection status codes should be treated as errors
+    pub fn with_redirect(&mut self, redirect: bool) -> &mut Self{
+        self.arguments.redirect = Some(redirect);
+        self
+    }
+
+    /// Builds the configured OpenApiCallArguments
+    pub fn build(self) -> OpenApiCallArguments{
+        self.arguments
+    }
+
+}
+impl Default for OpenApiCallArgumentsBuilder{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+
+/// Represents the service used to build AsyncApiCallArguments
+pub struct AsyncApiCallArgumentsBuilder{
+    arguments: AsyncApiCallArguments
+}
+impl AsyncApiCallArgumentsBuilder{
+
+    /// Initializes a new AsyncApiCallArgumentsBuilder
+    pub fn new() -> Self{
+        Self { arguments: AsyncApiCallArguments::default() }
+    }
+
+    /// Configures the AsyncAPI document that describes the operation to call
+    pub fn with_document<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut ExternalResourceDefinitionBuilder){
+        let mut builder = ExternalResourceDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.document = builder.build();
+        self
+    }
+
+    /// Sets the name of the channel on which to perform the operation
+    pub fn with_channel(&mut self, channel: &str) -> &mut Self{
+        self.arguments.channel = Some(channel.to_string());
+        self
+    }
+
+    /// Sets the name of the operation to perform
+    pub fn with_operation(&mut self, operation: &str) -> &mut Self{
+        self.arguments.operation = operation.to_string();
+        self
+    }
+
+    /// Sets the name of the server to call the operation on
+    pub fn with_server(&mut self, name: &str) -> &mut Self{
+        self.arguments.server = Some(AsyncApiServerDefinition::new(name));
+        self
+    }
+
+    /// Sets the name and variables of the server to call the operation on
+    pub fn with_server_variables(&mut self, name: &str, variables: HashMap<String, Value>) -> &mut Self{
+        let mut server = AsyncApiServerDefinition::new(name);
+        server.variables = Some(variables);
+        self.arguments.server = Some(server);
+        self
+    }
+
+    /// Sets the protocol to use to select the target server
+    pub fn with_protocol(&mut self, protocol: &str) -> &mut Self{
+        self.arguments.protocol = Some(protocol.to_string());
+        self
+    }
+
+    /// Configures the message to publish using the target operation
+    pub fn with_message<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut AsyncApiOutboundMessageDefinitionBuilder){
+        let mut builder = AsyncApiOutboundMessageDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.message = Some(builder.build());
+        self
+    }
+
+    /// Configures the subscription to messages consumed using the target operation
+    pub fn subscribe<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut AsyncApiSubscriptionDefinitionBuilder){
+        let mut builder = AsyncApiSubscriptionDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.subscription = Some(builder.build());
+        self
+    }
+
+    /// Configures the authentication policy used to call the AsyncAPI operation
+    pub fn with_authentication<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut AuthenticationPolicyDefinitionBuilder){
+        let mut builder = AuthenticationPolicyDefinitionBuilder::new();
+        setup(&mut builder);
+        self.arguments.authentication = Some(builder.build());
+        self
+    }
+
+    /// Builds the configured AsyncApiCallArguments
+    pub fn build(self) -> AsyncApiCallArguments{
+        self.arguments
+    }
+
+}
+impl Default for AsyncApiCallArgumentsBuilder{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+
+/// Represents the service used to build AsyncApiOutboundMessageDefinitions
+pub struct AsyncApiOutboundMessageDefinitionBuilder{
+    message: AsyncApiOutboundMessageDefinition
+}
+impl AsyncApiOutboundMessageDefinitionBuilder{
+
+    /// Initializes a new AsyncApiOutboundMessageDefinitionBuilder
+    pub fn new() -> Self{
+        Self { message: AsyncApiOutboundMessageDefinition::default() }
+    }
+
+    /// Sets the message's payload
+    pub fn with_payload(&mut self, payload: Value) -> &mut Self{
+        self.message.payload = Some(payload);
+        self
+    }
+
+    /// Sets the message's headers
+    pub fn with_headers(&mut self, headers: Value) -> &mut Self{
+        self.message.headers = Some(headers);
+        self
+    }
+
+    /// Builds the configured AsyncApiOutboundMessageDefinition
+    pub fn build(self) -> AsyncApiOutboundMessageDefinition{
+        self.message
+    }
+
+}
+impl Default for AsyncApiOutboundMessageDefinitionBuilder{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+
+/// Represents the service used to build AsyncApiSubscriptionDefinitions
+pub struct AsyncApiSubscriptionDefinitionBuilder{
+    subscription: AsyncApiSubscriptionDefinition
+}
+impl AsyncApiSubscriptionDefinitionBuilder{
+
+    /// Initializes a new AsyncApiSubscriptionDefinitionBuilder
+    pub fn new() -> Self{
+        Self { subscription: AsyncApiSubscriptionDefinition::default() }
+    }
+
+    /// Sets the runtime expression used to filter consumed messages
+    pub fn with_filter(&mut self, filter: &str) -> &mut Self{
+        self.subscription.filter = Some(filter.to_string());
+        self
+    }
+
+    /// Configures the subscription to consume the specified amount of messages
+    pub fn consume_amount(&mut self, amount: u32) -> &mut Self{
+        self.subscription.consume.amount = Some(amount);
+        self
+    }
+
+    /// Configures the subscription to consume messages for as long as the specified condition matches
+    pub fn consume_while(&mut self, condition: &str) -> &mut Self{
+        self.subscription.consume.while_ = Some(condition.to_string());
+        self
+    }
+
+    /// Configures the subscription to consume messages until the specified condition matches
+    pub fn consume_until_condition_matches(&mut self, condition: &str) -> &mut Self{
+        self.subscription.consume.until = Some(OneOfEventConsumptionStrategyDefinitionOrExpression::Expression(condition.to_string()));
+        self
+    }
+
+    /// Configures the subscription to consume messages until the specified events are consumed
+    pub fn consume_until<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut EventConsumptionStrategyDefinitionBuilder){
+        let mut builder = EventConsumptionStrategyDefinitionBuilder::new();
+        setup(&mut builder);
+        self.subscription.consume.until = Some(OneOfEventConsumptionStrategyDefinitionOrExpression::Strategy(builder.build()));
+        self
+    }
+
+    /// Configures the subscription to consume messages for the specified duration
+    pub fn consume_for(&mut self, duration: OneOfDurationOrIso8601Expression) -> &mut Self{
+        self.subscription.consume.for_ = Some(duration);
+        self
+    }
+
+    /// Configures the iterator used to process each consumed message
+    pub fn foreach<F>(&mut self, setup: F) -> &mut Self
+    where F: FnOnce(&mut SubscriptionIteratorDefinitionBuilder){
+        let mut builder = SubscriptionIteratorDefinitionBuilder::new();
+        setup(&mut builder);
+        self.subscription.foreach = Some(builder.build());
+        self
+    }
+
+    /// Builds the configured AsyncApiSubscriptionDefinition
+    pub fn build(self) -> AsyncApiSubscriptionDefinition{
+        self.subscription
+    }
+
+}
+impl Default for AsyncApiSubscriptionDefinitionBuilder{
+    fn default() -> Self{
+        Self::new()
+    }
+}
@@ -1,4 +1,5 @@
 pub mod authentication;
+pub mod call;
 pub mod task;
 pub mod timeout;
 pub mod workflow;
\ No newline at end of file
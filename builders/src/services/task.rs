@@ -1,6 +1,8 @@
 use crate::services::authentication::*;
+use crate::services::call::*;
 use crate::services::timeout::*;
 use serde_json::Value;
+use serverless_workflow_core::models::call::*;
 use serverless_workflow_core::models::duration::*;
 use serverless_workflow_core::models::error::*;
 use serverless_workflow_core::models::event::*;
@@ -39,6 +41,47 @@ impl GenericTaskDefinitionBuilder{
         }
     }
 
+    /// Configures the task to call a gRPC service
+    pub fn call_grpc<F>(&mut self, setup: F) -> Result<&mut CalltaskDefinitionBuilder, CallArgumentsError>
+    where F: FnOnce(&mut GrpcCallArgumentsBuilder){
+        let mut arguments_builder = GrpcCallArgumentsBuilder::new();
+        setup(&mut arguments_builder);
+        let arguments = arguments_builder.build();
+        self.call_with(&arguments)
+    }
+
+    /// Configures the task to call an operation described by an OpenAPI document
+    pub fn call_openapi<F>(&mut self, setup: F) -> Result<&mut CalltaskDefinitionBuilder, CallArgumentsError>
+    where F: FnOnce(&mut OpenApiCallArgumentsBuilder){
+        let mut arguments_builder = OpenApiCallArgumentsBuilder::new();
+        setup(&mut arguments_builder);
+        let arguments = arguments_builder.build();
+        self.call_with(&arguments)
+    }
+
+    /// Configures the task to call an operation described by an AsyncAPI document
+    pub fn call_asyncapi<F>(&mut self, setup: F) -> Result<&mut CalltaskDefinitionBuilder, CallArgumentsError>
+    where F: FnOnce(&mut AsyncApiCallArgumentsBuilder){
+        let mut arguments_builder = AsyncApiCallArgumentsBuilder::new();
+        setup(&mut arguments_builder);
+        let arguments = arguments_builder.build();
+        self.call_with(&arguments)
+    }
+
+    /// Configures the task to call the function the specified typed arguments are defined for
+    ///
+    /// Fails, leaving the task unconfigured, if the arguments cannot be converted into a key/value mapping
+    pub fn call_with<T: CallArguments>(&mut self, arguments: &T) -> Result<&mut CalltaskDefinitionBuilder, CallArgumentsError>{
+        let builder = CalltaskDefinitionBuilder::from_arguments(arguments)?;
+        self.builder = Some(TaskDefinitionBuilder::Call(builder));
+        if let Some(TaskDefinitionBuilder::Call(ref mut builder)) = self.builder{
+            Ok(builder)
+        }
+        else {
+            unreachable!("Builder should always be set to Call");
+        }
+    }
+
     /// Configures the task to perform subtasks sequentially
     pub fn do_(&mut self) -> &mut DoTaskDefinitionBuilder{
         let builder = DoTaskDefinitionBuilder::new();
@@ -242,7 +285,7 @@ pub trait TaskDefinitionBuilderBase {
     where F: FnOnce(&mut OutputDataModelDefinitionBuilder);
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self;
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self;
 
     /// Builds the configured TaskDefinition
     fn build(self) -> TaskDefinition;
@@ -260,6 +303,13 @@ impl CalltaskDefinitionBuilder {
         Self { task: CallTaskDefinition::new(function, None, None) }
     }
 
+    /// Initializes a new CallTaskDefinitionBuilder used to call the function the specified typed arguments are defined for
+    ///
+    /// Fails if the arguments cannot be converted into a key/value mapping
+    pub fn from_arguments<T: CallArguments>(arguments: &T) -> Result<Self, CallArgumentsError>{
+        Ok(Self { task: CallTaskDefinition::from_arguments(arguments)? })
+    }
+
     /// Adds a new argument to call the function with
     pub fn with(&mut self, name: &str, value: Value) -> &mut Self{
         if self.task.with.is_none(){
@@ -330,8 +380,8 @@ impl TaskDefinitionBuilderBase for CalltaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -416,8 +466,8 @@ impl TaskDefinitionBuilderBase for DoTaskDefinitionBuilder{
     }
     
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -492,8 +542,8 @@ impl TaskDefinitionBuilderBase for EmitTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -596,8 +646,8 @@ impl TaskDefinitionBuilderBase for ForTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -682,8 +732,8 @@ impl TaskDefinitionBuilderBase for ForkTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -777,8 +827,8 @@ impl TaskDefinitionBuilderBase for ListenTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -872,8 +922,8 @@ impl TaskDefinitionBuilderBase for RaiseTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.common.then = Some(directive.into());
         self
     }
 
@@ -1005,8 +1055,8 @@ impl TaskDefinitionBuilderBase for RunTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.common.then = Some(directive.into());
         self
     }
 
@@ -1121,8 +1171,8 @@ impl TaskDefinitionBuilderBase for SetTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -1207,8 +1257,8 @@ impl TaskDefinitionBuilderBase for SwitchTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -1301,8 +1351,8 @@ impl TaskDefinitionBuilderBase for TryTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
 
@@ -1377,8 +1427,8 @@ impl TaskDefinitionBuilderBase for WaitTaskDefinitionBuilder{
     }
 
     /// Configures the task to build to then execute the specified flow directive
-    fn then(&mut self, directive: &str) -> &mut Self{
-        self.task.common.then = Some(directive.to_string());
+    fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.task.common.then = Some(directive.into());
         self
     }
     
@@ -2079,8 +2129,8 @@ impl SwitchCaseDefinitionBuilder{
     }
 
     /// Sets the flow directive to execute when the case is matched
-    pub fn then(&mut self, directive: &str) -> &mut Self{
-        self.case.then = Some(directive.to_string());
+    pub fn then<D: Into<FlowDirective>>(&mut self, directive: D) -> &mut Self{
+        self.case.then = Some(directive.into());
         self
     }
 
@@ -2554,8 +2604,14 @@ impl SchemaDefinitionBuilder{
     }
 
     /// Sets the schema format
-    pub fn with_format(&mut self, format: &str) -> &mut Self{
-        self.schema.format = format.to_string();
+    pub fn with_format(&mut self, format: SchemaFormat) -> &mut Self{
+        self.schema.format = VersionedSchemaFormat::from(format);
+        self
+    }
+
+    /// Sets the schema format and the version of the format to use
+    pub fn with_format_version(&mut self, format: SchemaFormat, version: &str) -> &mut Self{
+        self.schema.format = VersionedSchemaFormat::new(format, Some(version.to_string()));
         self
     }
 
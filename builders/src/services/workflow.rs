@@ -87,6 +87,24 @@ impl WorkflowBuilder{
         self
     }
 
+    /// Sets the language used to write the workflow's runtime expressions
+    pub fn with_expression_language(mut self, language: RuntimeExpressionLanguage) -> Self{
+        match &mut self.workflow.evaluate {
+            Some(evaluate) => evaluate.language = language,
+            None => self.workflow.evaluate = Some(RuntimeExpressionEvaluationConfiguration { language, mode: None })
+        }
+        self
+    }
+
+    /// Sets the mode used to evaluate the workflow's runtime expressions
+    pub fn with_expression_mode(mut self, mode: RuntimeExpressionEvaluationMode) -> Self{
+        match &mut self.workflow.evaluate {
+            Some(evaluate) => evaluate.mode = Some(mode),
+            None => self.workflow.evaluate = Some(RuntimeExpressionEvaluationConfiguration { language: RuntimeExpressionLanguage::default(), mode: Some(mode) })
+        }
+        self
+    }
+
     /// Uses the specified authentication policy
     pub fn use_authentication<F>(mut self, name: &str, setup: F) -> Self
     where F: FnOnce(&mut AuthenticationPolicyDefinitionBuilder){
@@ -1,12 +1,28 @@
 pub mod models;
+pub mod services;
 
 #[cfg(test)]
 mod unit_tests {
 
+    use crate::models::authentication::*;
+    use crate::models::call::*;
     use crate::models::duration::*;
+    use crate::models::error::*;
     use crate::models::workflow::*;
     use crate::models::task::*;
     use crate::models::map::*;
+    use crate::models::output::*;
+    use crate::models::reference::*;
+    use crate::models::resource::*;
+    use crate::models::schema::*;
+    use crate::models::timeout::*;
+    use crate::services::diff::*;
+    use crate::services::expression::*;
+    use crate::services::graph::*;
+    use crate::services::normalization::*;
+    use crate::services::validation::*;
+    use crate::services::visitor::*;
+    use std::collections::HashMap;
     use serde_json::json;
 
     #[test]
@@ -18,7 +34,7 @@ mod unit_tests {
         let summary = Some("fake-summary".to_string());
         let document = WorkflowDefinitionMetadata::new(namespace, name, version, title.clone(), summary.clone(), None);
         let mut call_task = CallTaskDefinition::new("http", None, Some(true));
-        call_task.common.then = Some("continue".to_string());
+        call_task.common.then = Some(FlowDirective::Continue);
         let do_task = DoTaskDefinition::new(Map::from(vec![("set".to_string(), TaskDefinition::Wait(WaitTaskDefinition::new(OneOfDurationOrIso8601Expression::Duration(Duration::from_milliseconds(200)))))]));
         let mut workflow = WorkflowDefinition::new(document);
         workflow.do_ = Map::new();
@@ -443,4 +459,1573 @@ mod unit_tests {
             }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_validate_reports_undefined_references() {
+        let workflow_json = json!({
+            "document": {
+                "dsl": "1.0.0",
+                "namespace": "test",
+                "name": "invalid-references",
+                "version": "0.1.0"
+            },
+            "use": {
+                "errors": {
+                    "notFound": {
+                        "type": "https://fake.com/errors/not-found",
+                        "title": "Not Found",
+                        "status": 404
+                    }
+                }
+            },
+            "do": [
+                {
+                    "tryCall": {
+                        "try": [
+                            {
+                                "callApi": {
+                                    "call": "http",
+                                    "with": {
+                                        "method": "get",
+                                        "endpoint": "https://fake.com"
+                                    },
+                                    "then": "missingTask"
+                                }
+                            }
+                        ],
+                        "catch": {
+                            "retry": "missingRetry",
+                            "do": [
+                                {
+                                    "raiseError": {
+                                        "raise": {
+                                            "error": "missingError"
+                                        }
+                                    }
+                                }
+                            ]
+                        },
+                        "timeout": "missingTimeout"
+                    }
+                }
+            ]
+        });
+        let workflow: WorkflowDefinition = serde_json::from_value(workflow_json).expect("Failed to deserialize workflow");
+
+        let diagnostics = workflow.validate();
+
+        let find = |code: ValidationCode| diagnostics.iter().find(|d| d.code == code).unwrap_or_else(|| panic!("Expected a '{}' diagnostic, got {:?}", code, diagnostics));
+        assert_eq!(find(ValidationCode::UndefinedTask).path, "/do/0/tryCall/try/0/callApi/then");
+        assert_eq!(find(ValidationCode::UndefinedRetryPolicy).path, "/do/0/tryCall/catch/retry");
+        assert_eq!(find(ValidationCode::UndefinedError).path, "/do/0/tryCall/catch/do/0/raiseError/raise/error");
+        assert_eq!(find(ValidationCode::UndefinedTimeout).path, "/do/0/tryCall/timeout");
+        let unused = find(ValidationCode::UnusedComponent);
+        assert_eq!(unused.path, "/use/errors/notFound");
+        assert_eq!(unused.severity, ValidationSeverity::Warning);
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_workflow() {
+        let workflow_json = json!({
+            "document": {
+                "dsl": "1.0.0",
+                "namespace": "test",
+                "name": "valid-references",
+                "version": "0.1.0"
+            },
+            "use": {
+                "errors": {
+                    "notFound": {
+                        "type": "https://fake.com/errors/not-found",
+                        "title": "Not Found",
+                        "status": 404
+                    }
+                }
+            },
+            "do": [
+                {
+                    "checkStatus": {
+                        "switch": [
+                            { "missing": { "when": ".status == 404", "then": "raiseNotFound" } },
+                            { "default": { "then": "end" } }
+                        ]
+                    }
+                },
+                {
+                    "raiseNotFound": {
+                        "raise": {
+                            "error": "notFound"
+                        },
+                        "then": "exit"
+                    }
+                }
+            ]
+        });
+        let workflow: WorkflowDefinition = serde_json::from_value(workflow_json).expect("Failed to deserialize workflow");
+
+        let diagnostics = workflow.validate();
+
+        assert!(diagnostics.is_empty(), "Expected no diagnostics, got {:?}", diagnostics);
+    }
+
+    #[test]
+    fn test_validate_checks_all_call_authentication_sites() {
+        let yaml = r#"
+document:
+  dsl: 1.0.0
+  namespace: test
+  name: authentication-sites
+  version: 0.1.0
+use:
+  authentications:
+    openapiAuth:
+      bearer:
+        token: fake-token
+    grpcAuth:
+      basic:
+        username: fake-username
+        password: fake-password
+do:
+  - callOpenApi:
+      call: openapi
+      with:
+        document:
+          endpoint:
+            uri: https://fake.com/openapi.json
+            authentication:
+              use: openapiAuth
+        operationId: getPets
+  - callGrpc:
+      call: grpc
+      with:
+        proto:
+          endpoint:
+            uri: https://fake.com/service.proto
+            authentication:
+              use: missingAuth
+        service:
+          name: PetService
+          host: fake.com
+          authentication:
+            use: grpcAuth
+        method: GetPets
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let diagnostics = workflow.validate();
+        let codes: Vec<(ValidationCode, &str)> = diagnostics.iter().map(|d| (d.code.clone(), d.path.as_str())).collect();
+        assert_eq!(codes, vec![(ValidationCode::UndefinedAuthentication, "/do/1/callGrpc/with/proto/endpoint/authentication/use")]);
+    }
+
+    #[test]
+    fn test_validate_reports_duplicate_and_empty_task_lists() {
+        let mut workflow = WorkflowDefinition::new(WorkflowDefinitionMetadata::new("test", "duplicates", "0.1.0", None, None, None));
+        workflow.do_.add("wait".to_string(), TaskDefinition::Wait(WaitTaskDefinition::new(OneOfDurationOrIso8601Expression::Duration(Duration::from_seconds(1)))));
+        workflow.do_.entries.push(HashMap::from([("wait".to_string(), TaskDefinition::Do(DoTaskDefinition::new(Map::new())))]));
+
+        let diagnostics = workflow.validate();
+
+        assert!(diagnostics.iter().any(|d| d.code == ValidationCode::DuplicateTaskName && d.path == "/do/1/wait"));
+        assert!(diagnostics.iter().any(|d| d.code == ValidationCode::EmptyTaskList && d.path == "/do/1/wait/do"));
+        assert!(diagnostics.iter().all(|d| d.is_error()));
+    }
+
+    #[test]
+    fn test_parse_reports_unknown_task_type_location() {
+        let yaml = r#"
+document:
+  dsl: 1.0.0
+  namespace: test
+  name: parse
+  version: 0.1.0
+do:
+  - first:
+      set:
+        foo: bar
+  - notify:
+      unknown: value
+"#;
+
+        let error = WorkflowDefinition::from_yaml_str(yaml).unwrap_err();
+
+        assert_eq!(error.pointer, "/do/1/notify");
+        assert_eq!(error.line, Some(12));
+        assert_eq!(error.column, Some(7));
+        assert!(error.expected.contains(&TaskType::Call.to_string()));
+        assert!(error.to_string().contains("unknown task type"));
+    }
+
+    #[test]
+    fn test_parse_reports_invalid_nested_property() {
+        let json = r#"{
+  "document": { "dsl": "1.0.0", "namespace": "test", "name": "parse", "version": "0.1.0" },
+  "do": [
+    { "outer": { "do": [ { "notify": { "call": 42 } } ] } }
+  ]
+}"#;
+
+        let error = WorkflowDefinition::from_json_str(json).unwrap_err();
+
+        assert_eq!(error.pointer, "/do/0/outer/do/0/notify/call");
+        assert_eq!(error.line, Some(4));
+        assert!(error.expected.is_empty());
+    }
+
+    #[test]
+    fn test_parse_valid_workflow() {
+        let json = r#"{
+  "document": { "dsl": "1.0.0", "namespace": "test", "name": "parse", "version": "0.1.0" },
+  "do": [ { "wait": { "wait": { "seconds": 1 } } } ]
+}"#;
+
+        let workflow = WorkflowDefinition::from_json_str(json).unwrap();
+
+        assert_eq!(workflow.document.name, "parse");
+        assert_eq!(workflow.do_.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_http_call_arguments_round_trip() {
+        let mut arguments = HttpCallArguments::new("post", OneOfEndpointDefinitionOrUri::Uri("https://petstore.com/pets".to_string()));
+        arguments.body = Some(json!({ "name": "Milou" }));
+        arguments.output = Some(HttpOutputFormat::Response);
+
+        let call = CallTaskDefinition::from_arguments(&arguments).unwrap();
+
+        assert_eq!(call.call, CallFunction::Http.as_str());
+        assert_eq!(call.with.as_ref().unwrap().get("method"), Some(&json!("post")));
+        assert_eq!(call.as_http().unwrap(), arguments);
+    }
+
+    #[test]
+    fn test_http_call_arguments_report_missing_keys() {
+        let mut with = HashMap::new();
+        with.insert("method".to_string(), json!("get"));
+        let call = CallTaskDefinition::new(CallFunction::Http.as_str(), Some(with), None);
+
+        let error = call.as_http().unwrap_err();
+
+        assert_eq!(error.function, CallFunction::Http.as_str());
+        assert!(error.message.contains("'endpoint'"));
+        assert!(CallTaskDefinition::new("myFunction", None, None).as_http().is_err());
+        let mut with = HashMap::new();
+        with.insert("document".to_string(), json!({ "endpoint": "https://fake.com/asyncapi.json" }));
+        let error = CallTaskDefinition::new(CallFunction::AsyncApi.as_str(), Some(with), None).as_asyncapi().unwrap_err();
+        assert!(error.message.contains("'operation'"));
+    }
+
+    #[test]
+    fn test_validate_reports_openapi_call_without_operation_id() {
+        let mut with = HashMap::new();
+        with.insert("document".to_string(), json!({ "endpoint": "https://petstore.swagger.io/v2/swagger.json" }));
+        let mut workflow = WorkflowDefinition::new(WorkflowDefinitionMetadata::new("test", "openapi", "0.1.0", None, None, None));
+        workflow.do_.add("findPet".to_string(), TaskDefinition::Call(CallTaskDefinition::new(CallFunction::OpenApi.as_str(), Some(with), None)));
+
+        let diagnostics = workflow.validate();
+
+        assert!(diagnostics.iter().any(|d| d.code == ValidationCode::InvalidCallArguments && d.path == "/do/0/findPet/with" && d.message.contains("operationId")));
+    }
+
+    #[cfg(feature = "agents")]
+    #[test]
+    fn test_agent_call_arguments_round_trip() {
+        use crate::models::agent::*;
+
+        let mut a2a = A2ACallArguments::new("message/send");
+        a2a.server = Some(OneOfEndpointDefinitionOrUri::Uri("https://agents.fake.com".to_string()));
+        a2a.parameters = Some(json!({ "message": { "role": "user", "parts": [ { "kind": "text", "text": "${ .prompt }" } ] } }));
+        let mut mcp = McpCallArguments::new("tools/call", McpTransportDefinition::default());
+        mcp.transport.stdio = Some(McpStdioTransportDefinition { command: "uvx".to_string(), arguments: Some(vec!["mcp-server-fetch".to_string()]), environment: None });
+
+        let a2a_call: CallTaskDefinition = serde_json::from_value(serde_json::to_value(CallTaskDefinition::from_arguments(&a2a).unwrap()).unwrap()).unwrap();
+        let mcp_call: CallTaskDefinition = serde_json::from_value(serde_json::to_value(CallTaskDefinition::from_arguments(&mcp).unwrap()).unwrap()).unwrap();
+
+        assert_eq!(a2a_call.call, CallFunction::A2a.as_str());
+        assert_eq!(a2a_call.as_a2a().unwrap(), a2a);
+        assert_eq!(mcp_call.call, CallFunction::Mcp.as_str());
+        assert_eq!(mcp_call.as_mcp().unwrap(), mcp);
+        assert!(CallTaskDefinition::new(CallFunction::Mcp.as_str(), None, None).as_mcp().unwrap_err().message.contains("'method'"));
+    }
+
+    #[test]
+    fn test_string_enums_round_trip() {
+        let scheme: OAuth2AuthenticationSchemeDefinition = serde_json::from_value(json!({
+            "authority": "https://auth.fake.com",
+            "grant": "client_credentials",
+            "client": { "id": "workflow", "authentication": "private_key_jwt" }
+        })).unwrap();
+
+        assert_eq!(scheme.grant, Some(OAuth2GrantType::ClientCredentials));
+        assert_eq!(scheme.client.as_ref().unwrap().authentication, Some(OAuth2ClientAuthenticationMethod::PrivateKey));
+        assert_eq!(serde_json::to_value(&scheme).unwrap()["grant"], json!("client_credentials"));
+        assert_eq!("call".parse::<TaskType>().unwrap(), TaskType::Call);
+        assert_eq!(TaskType::Listen.to_string(), "listen");
+        assert_eq!("OpenIDConnect".parse::<AuthenticationScheme>().unwrap(), AuthenticationScheme::Oidc);
+    }
+
+    #[test]
+    fn test_string_enums_reject_unknown_values() {
+        let error = serde_json::from_value::<OAuth2AuthenticationSchemeDefinition>(json!({ "grant": "autorization_code" })).unwrap_err();
+        assert!(error.to_string().contains("unknown OAuth2GrantType 'autorization_code'"));
+
+        let error = "jsq".parse::<ProcessType>().unwrap_err();
+        assert_eq!(error.value, "jsq");
+        assert_eq!(error.expected, ProcessType::VALUES);
+
+        let error = serde_json::from_value::<RuntimeExpressionEvaluationMode>(json!("lose")).unwrap_err();
+        assert!(error.to_string().contains("unknown RuntimeExpressionEvaluationMode 'lose'"));
+        assert!("raw".parse::<HttpOutputFormat>().is_ok());
+        assert!("responses".parse::<HttpOutputFormat>().is_err());
+        assert_eq!("invalid-duration".parse::<ValidationCode>(), Ok(ValidationCode::InvalidDuration));
+    }
+
+    #[test]
+    fn test_extensible_string_enums_accept_other_values() {
+        let language: RuntimeExpressionLanguage = serde_json::from_value(json!("jsonata")).unwrap();
+        assert_eq!(language, RuntimeExpressionLanguage::Other("jsonata".to_string()));
+        assert_eq!(serde_json::to_value(&language).unwrap(), json!("jsonata"));
+        assert_eq!(RuntimeExpressionEvaluationConfiguration::default().language, RuntimeExpressionLanguage::Jq);
+
+        let schema: SchemaDefinition = serde_json::from_value(json!({ "format": "json:2020-12", "document": {} })).unwrap();
+        assert_eq!(schema.format, VersionedSchemaFormat::new(SchemaFormat::Json, Some("2020-12".to_string())));
+        assert_eq!(serde_json::to_value(&schema).unwrap()["format"], json!("json:2020-12"));
+        let schema: SchemaDefinition = serde_json::from_value(json!({ "document": {} })).unwrap();
+        assert_eq!(schema.format, VersionedSchemaFormat::from(SchemaFormat::Json));
+    }
+
+    #[test]
+    fn test_flow_directive_wire_format() {
+        let case: SwitchCaseDefinition = serde_json::from_value(json!({ "when": ".ok", "then": "end" })).unwrap();
+        assert_eq!(case.then, Some(FlowDirective::End));
+
+        let mut fields = TaskDefinitionFields::new();
+        fields.then = Some(FlowDirective::Goto("notifyUser".to_string()));
+        assert_eq!(serde_json::to_value(&fields).unwrap()["then"], json!("notifyUser"));
+        assert_eq!(FlowDirective::from("exit"), FlowDirective::Exit);
+        assert!(FlowDirective::Continue.is_reserved());
+        assert_eq!(FlowDirective::from("notifyUser").task_name(), Some("notifyUser"));
+    }
+
+    #[test]
+    fn test_duration_iso8601_round_trip() {
+        let duration = Duration::parse_iso8601("P1DT12H30M5.25S").unwrap();
+
+        assert_eq!(duration.days, Some(1));
+        assert_eq!(duration.hours, Some(12));
+        assert_eq!(duration.minutes, Some(30));
+        assert_eq!(duration.seconds, Some(5));
+        assert_eq!(duration.milliseconds, Some(250));
+        assert_eq!(duration.to_iso8601(), "P1DT12H30M5.25S");
+        assert_eq!(Duration::parse_iso8601("P2W").unwrap().days, Some(14));
+        assert_eq!(Duration::from_milliseconds(0).to_iso8601(), "PT0S");
+        assert_eq!(Duration::from_minutes(5).to_iso8601(), "PT5M");
+    }
+
+    #[test]
+    fn test_duration_iso8601_rejects_malformed_expressions() {
+        for expression in ["P5X", "5M", "P", "PT", "P1M", "PT1.5H", "PT5S5S", "P1DT", "P1W18446744073709551615D", "PT5124095576030432H"] {
+            assert!(Duration::parse_iso8601(expression).is_err(), "'{}' should be rejected", expression);
+        }
+        let duration = Duration { hours: Some(u64::MAX), ..Duration::default() };
+        assert_eq!(duration.to_std(), std::time::Duration::from_millis(u64::MAX));
+    }
+
+    #[test]
+    fn test_duration_normalization() {
+        let iso = OneOfDurationOrIso8601Expression::Iso8601Expression("PT5M".to_string());
+        let duration = OneOfDurationOrIso8601Expression::Duration(Duration::from_seconds(300));
+
+        assert_eq!(iso.to_duration().unwrap().total_milliseconds(), duration.to_duration().unwrap().total_milliseconds());
+        assert_eq!(iso.to_std().unwrap(), std::time::Duration::from_secs(300));
+
+        let mut workflow = WorkflowDefinition::new(WorkflowDefinitionMetadata::new("test", "durations", "0.1.0", None, None, None));
+        workflow.do_.add("wait".to_string(), TaskDefinition::Wait(WaitTaskDefinition::new(OneOfDurationOrIso8601Expression::Iso8601Expression("P5X".to_string()))));
+        let diagnostics = workflow.validate();
+        assert!(diagnostics.iter().any(|d| d.code == ValidationCode::InvalidDuration && d.path == "/do/0/wait/wait"));
+    }
+
+    #[test]
+    fn test_map_ordered_api() {
+        let mut map: Map<String, i32> = vec![("a".to_string(), 1), ("c".to_string(), 3)].into_iter().collect();
+        assert!(map.insert_before(&"c".to_string(), "b".to_string(), 2));
+        map.insert_at(3, "d".to_string(), 4);
+        assert_eq!(map.len(), 4);
+        assert_eq!(map.position(&"b".to_string()), Some(1));
+        assert_eq!(map.get(&"d".to_string()), Some(&4));
+        if let Some(value) = map.get_mut(&"a".to_string()) {
+            *value = 10;
+        }
+        assert!(map.rename(&"c".to_string(), "z".to_string()));
+        assert_eq!(map.remove(&"b".to_string()), Some(2));
+        assert!(!map.contains_key(&"b".to_string()));
+        let entries: Vec<(String, i32)> = map.into_iter().collect();
+        assert_eq!(entries, vec![("a".to_string(), 10), ("z".to_string(), 3), ("d".to_string(), 4)]);
+
+        let mut map: Map<String, i32> = vec![("a".to_string(), 1), ("b".to_string(), 2), ("a".to_string(), 3)].into_iter().collect();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&"a".to_string()), Some(&3));
+        map.insert_at(map.len(), "a".to_string(), 4);
+        assert_eq!(map.keys().cloned().collect::<Vec<_>>(), vec!["b".to_string(), "a".to_string()]);
+        map.add("b".to_string(), 5);
+        let json = serde_json::to_value(&map).unwrap();
+        assert_eq!(json, json!([{ "b": 5 }, { "a": 4 }]));
+        assert_eq!(serde_json::from_value::<Map<String, i32>>(json).unwrap(), map);
+    }
+
+    #[test]
+    fn test_map_serialization_preserves_order_and_rejects_duplicates() {
+        let json = json!([{ "first": 1 }, { "second": 2 }]);
+        let map: Map<String, i32> = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(map.keys().cloned().collect::<Vec<_>>(), vec!["first".to_string(), "second".to_string()]);
+        assert_eq!(serde_json::to_value(&map).unwrap(), json);
+
+        let result = serde_json::from_value::<Map<String, i32>>(json!([{ "first": 1 }, { "first": 2 }]));
+        assert!(result.unwrap_err().to_string().contains("duplicate key \"first\""));
+        assert!(serde_json::from_value::<Map<String, i32>>(json!([{ "a": 1, "b": 2 }, {}])).is_err());
+        assert!(serde_json::from_value::<Map<String, i32>>(json!([{}])).is_err());
+    }
+
+    #[test]
+    fn test_visitor_walks_all_tasks() {
+        struct PathCollector {
+            paths: Vec<String>,
+            lists: Vec<(String, TaskContainer, Option<String>)>
+        }
+        impl Visitor for PathCollector {
+            fn visit_task_list(&mut self, context: &TaskListContext, _tasks: &Map<String, TaskDefinition>) {
+                self.lists.push((context.path.clone(), context.container, context.parent.as_ref().map(|parent| parent.name.clone())));
+            }
+            fn visit_task(&mut self, context: &TaskContext, _task: &TaskDefinition) -> bool {
+                self.paths.push(context.path.clone());
+                true
+            }
+        }
+
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: visitor
+  version: '0.1.0'
+use:
+  functions:
+    greet:
+      set:
+        greeting: hello
+  extensions:
+    - logging:
+        extend: call
+        before:
+          - log:
+              set:
+                logged: true
+do:
+  - loop:
+      for:
+        each: item
+        in: ${ .items }
+      do:
+        - fork:
+            fork:
+              compete: false
+              branches:
+                - left:
+                    set:
+                      side: left
+  - attempt:
+      try:
+        - listen:
+            listen:
+              to:
+                any: []
+            foreach:
+              do:
+                - handle:
+                    set:
+                      handled: true
+      catch:
+        do:
+          - recover:
+              set:
+                recovered: true
+  - consume:
+      call: asyncapi
+      with:
+        document:
+          endpoint: https://fake.com/asyncapi.json
+        operation: onOrder
+        subscription:
+          consume:
+            amount: 1
+          foreach:
+            do:
+              - process:
+                  set:
+                    processed: true
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let mut collector = PathCollector { paths: Vec::new(), lists: Vec::new() };
+        workflow.walk(&mut collector);
+        assert_eq!(collector.paths, vec![
+            "/use/functions/greet",
+            "/use/extensions/0/logging/before/0/log",
+            "/do/0/loop",
+            "/do/0/loop/do/0/fork",
+            "/do/0/loop/do/0/fork/fork/branches/0/left",
+            "/do/1/attempt",
+            "/do/1/attempt/try/0/listen",
+            "/do/1/attempt/try/0/listen/foreach/do/0/handle",
+            "/do/1/attempt/catch/do/0/recover",
+            "/do/2/consume",
+            "/do/2/consume/with/subscription/foreach/do/0/process"
+        ]);
+        assert!(collector.lists.contains(&("/do/2/consume/with/subscription/foreach/do".to_string(), TaskContainer::Foreach, Some("consume".to_string()))));
+        assert!(collector.lists.contains(&("/do/1/attempt/catch/do".to_string(), TaskContainer::Catch, Some("attempt".to_string()))));
+        assert!(collector.lists.contains(&("/use/extensions/0/logging/before".to_string(), TaskContainer::ExtensionBefore, None)));
+    }
+
+    #[test]
+    fn test_visitor_mut_rewrites_tasks() {
+        struct TimeoutSetter;
+        impl VisitorMut for TimeoutSetter {
+            fn visit_task(&mut self, context: &TaskContext, task: &mut TaskDefinition) -> bool {
+                if context.depth > 0 {
+                    task.common_mut().timeout = Some(OneOfTimeoutDefinitionOrReference::Reference("default".to_string()));
+                }
+                true
+            }
+        }
+
+        let mut workflow = WorkflowDefinition::new(WorkflowDefinitionMetadata::new("test", "visitor", "0.1.0", None, None, None));
+        let mut do_ = DoTaskDefinition::new(Map::new());
+        do_.do_.add("inner".to_string(), TaskDefinition::Wait(WaitTaskDefinition::new(OneOfDurationOrIso8601Expression::Duration(Duration::from_seconds(1)))));
+        workflow.do_.add("outer".to_string(), TaskDefinition::Do(do_));
+        workflow.walk_mut(&mut TimeoutSetter);
+
+        let TaskDefinition::Do(outer) = workflow.do_.get(&"outer".to_string()).unwrap() else { panic!("expected a do task") };
+        assert!(outer.common.timeout.is_none());
+        assert!(outer.do_.get(&"inner".to_string()).unwrap().common().timeout.is_some());
+
+        let mut with = HashMap::new();
+        with.insert("document".to_string(), json!({ "endpoint": "https://fake.com/asyncapi.json" }));
+        with.insert("operation".to_string(), json!("onOrder"));
+        with.insert("subscription".to_string(), json!({ "consume": { "amount": 1 }, "foreach": { "do": [ { "process": { "set": { "processed": true } } } ] } }));
+        workflow.do_.add("consume".to_string(), TaskDefinition::Call(CallTaskDefinition::new(CallFunction::AsyncApi.as_str(), Some(with), None)));
+        workflow.walk_mut(&mut TimeoutSetter);
+
+        let TaskDefinition::Call(consume) = workflow.do_.get(&"consume".to_string()).unwrap() else { panic!("expected a call task") };
+        assert_eq!(consume.with.as_ref().unwrap()["subscription"]["foreach"]["do"][0]["process"]["timeout"], json!("default"));
+    }
+
+    #[test]
+    fn test_task_reference_resolution() {
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: references
+  version: '0.1.0'
+do:
+  - initialize:
+      set:
+        count: 0
+  - processOrder:
+      for:
+        each: item
+        in: ${ .items }
+      do:
+        - charge/card:
+            set:
+              charged: true
+"#;
+        let mut workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let reference: TaskReference = "/do/1/processOrder/do/0/charge~1card".parse().unwrap();
+        assert_eq!(reference.name(), "charge/card");
+        let task = workflow.resolve_task(&reference).unwrap();
+        assert_eq!(task.task_type(), TaskType::Set);
+        assert_eq!(workflow.task_reference_of(&task), Some(reference.clone()));
+        assert_eq!(TaskReference::from_segments(&["do", "1", "processOrder", "do", "0", "charge/card"]), reference);
+
+        assert!(workflow.resolve_task(&"/do/0/processOrder".parse().unwrap()).is_none());
+        assert!(workflow.resolve_task(&"/do/01/processOrder".parse().unwrap()).is_none());
+        assert!(workflow.resolve_task(&"/do/1/processOrder/try/0/charge~1card".parse().unwrap()).is_none());
+
+        workflow.resolve_task_mut(&reference).unwrap().common_mut().if_ = Some("${ .enabled }".to_string());
+        let error = ErrorDefinition::new("https://serverlessworkflow.io/spec/1.0.0/errors/runtime", "Runtime Error", json!(500), None, Some(reference.to_string()));
+        let task = workflow.resolve_task(&error.instance_reference().unwrap()).unwrap();
+        assert_eq!(task.common().if_.as_deref(), Some("${ .enabled }"));
+
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: references
+  version: '0.1.0'
+do:
+  - initialize:
+      set:
+        count: 0
+  - processOrder:
+      for:
+        each: item
+        in: ${ .items }
+      do:
+        - charge:
+            wait: PT1S
+"#;
+        let mut workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let reference: TaskReference = "/do/1/processOrder/for/do/0/charge".parse().unwrap();
+        assert_eq!(workflow.resolve_task(&reference).unwrap().task_type(), TaskType::Wait);
+        assert!(workflow.resolve_task_mut(&reference).is_some());
+
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: references
+  version: '0.1.0'
+do:
+  - consume:
+      call: asyncapi
+      with:
+        document:
+          endpoint: https://example.com/asyncapi.json
+        operation: onOrder
+        subscription:
+          consume:
+            amount: 1
+          foreach:
+            do:
+              - process:
+                  for:
+                    each: item
+                    in: ${ .items }
+                  do:
+                    - charge:
+                        wait: PT1S
+"#;
+        let mut workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let reference: TaskReference = "/do/0/consume/with/subscription/foreach/do/0/process/for/do/0/charge".parse().unwrap();
+        assert_eq!(workflow.resolve_task(&reference).unwrap().task_type(), TaskType::Wait);
+        assert!(workflow.resolve_task(&"/do/0/consume/with/subscription/foreach/do/1/process".parse().unwrap()).is_none());
+        workflow.resolve_task_mut(&reference).unwrap().common_mut().if_ = Some("${ .enabled }".to_string());
+        assert_eq!(workflow.resolve_task(&reference).unwrap().common().if_.as_deref(), Some("${ .enabled }"));
+        let TaskDefinition::Call(consume) = workflow.do_.get(&"consume".to_string()).unwrap() else { panic!("expected a call task") };
+        assert_eq!(consume.with.as_ref().unwrap()["subscription"]["foreach"]["do"][0]["process"]["do"][0]["charge"]["if"], json!("${ .enabled }"));
+    }
+
+    #[test]
+    fn test_task_reference_rejects_malformed_pointers() {
+        for pointer in ["", "do/0/task", "/do//task", "/do/0/bad~2"] {
+            assert!(TaskReference::parse(pointer).is_err(), "'{}' should be rejected", pointer);
+        }
+        let copy = TaskDefinition::Wait(WaitTaskDefinition::new(OneOfDurationOrIso8601Expression::Duration(Duration::from_seconds(1))));
+        let mut workflow = WorkflowDefinition::new(WorkflowDefinitionMetadata::new("test", "references", "0.1.0", None, None, None));
+        workflow.do_.add("wait".to_string(), copy.clone());
+        assert!(workflow.task_reference_of(&copy).is_none());
+        assert_eq!(workflow.task_reference_of(workflow.do_.get(&"wait".to_string()).unwrap()).map(|r| r.to_string()), Some("/do/0/wait".to_string()));
+    }
+
+    #[test]
+    fn test_control_flow_graph() {
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: graph
+  version: '0.1.0'
+do:
+  - check:
+      switch:
+        - big:
+            when: ${ .n > 10 }
+            then: large
+        - default:
+            then: small
+  - small:
+      set:
+        size: small
+      then: end
+  - large:
+      set:
+        size: large
+      then: process
+  - orphan:
+      set:
+        orphan: true
+  - process:
+      for:
+        each: item
+        in: ${ .items }
+      do:
+        - step:
+            set:
+              processed: true
+  - parallel:
+      fork:
+        compete: false
+        branches:
+          - left:
+              set:
+                side: left
+          - right:
+              set:
+                side: right
+  - attempt:
+      try:
+        - fail:
+            raise:
+              error:
+                type: https://serverlessworkflow.io/spec/1.0.0/errors/runtime
+                title: Failure
+                status: 500
+      catch:
+        do:
+          - recover:
+              set:
+                recovered: true
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let graph = workflow.control_flow_graph();
+        let id = |pointer: &str| graph.node_of(&pointer.parse().unwrap()).unwrap();
+        let (check, small, large, process, step, parallel, left, attempt, fail, recover) = (
+            id("/do/0/check"), id("/do/1/small"), id("/do/2/large"), id("/do/4/process"), id("/do/4/process/do/0/step"),
+            id("/do/5/parallel"), id("/do/5/parallel/fork/branches/0/left"), id("/do/6/attempt"), id("/do/6/attempt/try/0/fail"), id("/do/6/attempt/catch/do/0/recover"));
+
+        assert_eq!(graph.successors(graph.entry()), vec![check]);
+        assert_eq!(graph.successors(check), vec![large, small]);
+        assert!(graph.outgoing_edges(check).any(|edge| edge.kind == ControlFlowEdgeKind::Case && edge.label.as_deref() == Some("big")));
+        assert_eq!(graph.successors(small), vec![graph.exit()]);
+        assert_eq!(graph.successors(large), vec![process]);
+        assert_eq!(graph.successors(process), vec![step, parallel]);
+        assert!(graph.outgoing_edges(step).all(|edge| edge.target == process && edge.kind == ControlFlowEdgeKind::Loop));
+
+        let join = graph.successors(left)[0];
+        assert_eq!(graph.node(join).unwrap().kind, ControlFlowNodeKind::Join);
+        assert_eq!(graph.predecessors(join).len(), 2);
+        assert_eq!(graph.successors(join), vec![attempt]);
+        assert_eq!(graph.successors(attempt), vec![fail, recover]);
+        assert_eq!(graph.successors(fail), vec![recover]);
+        assert!(!graph.is_reachable(fail, attempt));
+        assert_eq!(graph.successors(recover), vec![graph.exit()]);
+
+        assert!(graph.is_reachable(graph.entry(), graph.exit()));
+        assert!(!graph.is_reachable(recover, check));
+        let unreachable: Vec<&str> = graph.unreachable_tasks().iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(unreachable, vec!["orphan"]);
+
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: graph
+  version: '0.1.0'
+do:
+  - attempt:
+      try:
+        - fail:
+            raise:
+              error:
+                type: https://serverlessworkflow.io/spec/1.0.0/errors/runtime
+                title: Failure
+                status: 500
+      catch:
+        errors:
+          with:
+            status: 500
+  - proceed:
+      set:
+        proceeded: true
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let graph = workflow.control_flow_graph();
+        let (attempt, fail, proceed) = (graph.node_of(&"/do/0/attempt".parse().unwrap()).unwrap(), graph.node_of(&"/do/0/attempt/try/0/fail".parse().unwrap()).unwrap(), graph.node_of(&"/do/1/proceed".parse().unwrap()).unwrap());
+        assert_eq!(graph.successors(fail), vec![proceed]);
+        assert!(!graph.is_reachable(fail, attempt));
+    }
+
+    #[test]
+    fn test_diagram_export() {
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: diagram
+  version: '0.1.0'
+do:
+  - check:
+      switch:
+        - valid:
+            when: ${ .valid }
+            then: notify
+        - default:
+            then: end
+  - notify:
+      fork:
+        compete: false
+        branches:
+          - email:
+              call: http
+              with:
+                method: post
+                endpoint: https://mail.example.com
+          - pause:
+              wait:
+                seconds: 1
+  - attempt:
+      try:
+        - fail:
+            raise:
+              error:
+                type: https://serverlessworkflow.io/spec/1.0.0/errors/runtime
+                title: Failure
+                status: 500
+      catch:
+        do:
+          - recover:
+              set:
+                recovered: true
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let mermaid = workflow.to_mermaid();
+        assert_eq!(mermaid, r#"flowchart TD
+    n0((start))
+    n1(((end)))
+    n2{"check"}
+    n3@{ shape: fork, label: "notify" }
+    n4["attempt"]
+    n5@{ shape: fork, label: " " }
+    n6[["email"]]
+    n7{{"pause"}}
+    subgraph n4_try ["attempt (try)"]
+        n8[/"fail"\]
+    end
+    subgraph n4_catch ["attempt (catch)"]
+        n9["recover"]
+    end
+    n0 --> n2
+    n2 -->|"valid"| n3
+    n2 -->|"default"| n1
+    n3 --> n6
+    n3 --> n7
+    n4 --> n8
+    n4 -.->|"catch"| n9
+    n5 --> n4
+    n6 --> n5
+    n7 --> n5
+    n8 ==>|"raise"| n9
+    n9 --> n1
+"#);
+        assert_eq!(mermaid, workflow.to_mermaid());
+
+        let dot = workflow.to_dot();
+        assert!(dot.starts_with("digraph \"diagram\" {\n"));
+        assert!(dot.contains("    n2 [label=\"check\", shape=diamond];\n"));
+        assert!(dot.contains("    subgraph cluster_n4_catch {\n        label=\"attempt (catch)\";\n        style=dashed;\n        n9 [label=\"recover\", shape=box];\n    }\n"));
+        assert!(dot.contains("    n2 -> n3 [label=\"valid\"];\n"));
+        assert!(dot.contains("    n4 -> n9 [style=dashed, label=\"catch\"];\n"));
+        assert!(dot.ends_with("}\n"));
+        assert_eq!(dot, workflow.to_dot());
+    }
+
+    #[test]
+    fn test_workflow_diff() {
+        let old = WorkflowDefinition::from_yaml_str(r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: diff
+  version: '0.1.0'
+input:
+  schema:
+    format: json
+    document:
+      type: object
+do:
+  - fetch:
+      call: http
+      with:
+        method: get
+        endpoint:
+          uri: https://example.com/orders
+          authentication:
+            basic:
+              username: admin
+              password: admin
+  - process:
+      do:
+        - validate:
+            set:
+              valid: true
+        - legacy:
+            set:
+              legacy: true
+      then: end
+"#).unwrap();
+        let new = WorkflowDefinition::from_yaml_str(r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: diff
+  version: '0.2.0'
+input:
+  schema:
+    format: json
+    document:
+      type: object
+      required: [ id ]
+do:
+  - process:
+      do:
+        - validate:
+            set:
+              valid: true
+      then: exit
+  - fetch:
+      call: http
+      with:
+        method: post
+        endpoint:
+          uri: https://example.com/orders
+          authentication:
+            bearer:
+              token: secret
+  - notify:
+      wait:
+        seconds: 1
+"#).unwrap();
+        let changes = diff(&old, &new);
+        let find = |path: &str, kind: WorkflowChangeKind| changes.iter().find(|change| change.path == path && change.kind == kind);
+
+        assert!(find("/input/schema", WorkflowChangeKind::InputSchemaChanged).unwrap().breaking);
+        let moved = find("/do/1/fetch", WorkflowChangeKind::TaskMoved).unwrap();
+        assert_eq!(moved.previous_path.as_deref(), Some("/do/0/fetch"));
+        assert!(!moved.breaking);
+        assert!(!find("/do/1/fetch/with", WorkflowChangeKind::CallArgumentsChanged).unwrap().breaking);
+        assert!(find("/do/1/fetch/with/endpoint/authentication", WorkflowChangeKind::AuthenticationChanged).unwrap().breaking);
+        assert!(find("/do/1/fetch/with/authentication", WorkflowChangeKind::AuthenticationChanged).is_none());
+        assert_eq!(find("/do/0/process/then", WorkflowChangeKind::TransitionChanged).unwrap().after, Some(json!("exit")));
+        assert!(!find("/do/2/notify", WorkflowChangeKind::TaskAdded).unwrap().breaking);
+        assert!(find("/do/1/process/do/1/legacy", WorkflowChangeKind::TaskRemoved).unwrap().breaking);
+        assert!(find("/do/0/process/do/0/validate", WorkflowChangeKind::TaskMoved).is_none());
+        assert!(!changes.iter().any(|change| change.kind == WorkflowChangeKind::TaskModified));
+        assert!(diff(&new, &new).is_empty());
+
+        let mut shifted = new.clone();
+        shifted.do_.insert_at(0, "prepare".to_string(), TaskDefinition::Set(SetTaskDefinition::new()));
+        let changes = diff(&new, &shifted);
+        assert_eq!(changes.len(), 1);
+        assert_eq!((changes[0].path.as_str(), changes[0].kind), ("/do/0/prepare", WorkflowChangeKind::TaskAdded));
+
+        let consume = |tasks: &str| WorkflowDefinition::from_yaml_str(&format!(r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: diff
+  version: '0.1.0'
+do:
+  - consume:
+      call: asyncapi
+      with:
+        document:
+          endpoint: https://example.com/asyncapi.json
+        operation: onOrder
+        subscription:
+          consume:
+            amount: 1
+          foreach:
+            do: {}
+"#, tasks)).unwrap();
+        let changes = diff(&consume("[ { process: { set: { processed: true } } } ]"), &consume("[ { process: { wait: PT1S } }, { notify: { set: { notified: true } } } ]"));
+        let find = |path: &str, kind: WorkflowChangeKind| changes.iter().find(|change| change.path == path && change.kind == kind);
+        assert!(find("/do/0/consume/with/subscription/foreach/do/0/process", WorkflowChangeKind::TaskTypeChanged).is_some());
+        assert!(find("/do/0/consume/with/subscription/foreach/do/1/notify", WorkflowChangeKind::TaskAdded).is_some());
+    }
+
+    #[test]
+    fn test_inline_and_extract_components() {
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: components
+  version: '0.1.0'
+use:
+  authentications:
+    admin:
+      basic:
+        username: admin
+        password: admin
+  errors:
+    notFound:
+      type: https://serverlessworkflow.io/spec/1.0.0/errors/communication
+      title: Not Found
+      status: 404
+  timeouts:
+    short:
+      after:
+        seconds: 5
+do:
+  - fetch:
+      call: http
+      with:
+        method: get
+        endpoint:
+          uri: https://example.com
+          authentication:
+            use: admin
+      timeout: short
+  - fail:
+      raise:
+        error: notFound
+  - wait:
+      wait:
+        seconds: 1
+      timeout: missing
+  - register:
+      call: http
+      with:
+        method: post
+        endpoint: https://example.com/users
+        body:
+          authentication:
+            basic:
+              username: ${ .username }
+              password: ${ .password }
+"#;
+        let original = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let mut workflow = original.clone();
+        let diagnostics = inline_components(&mut workflow);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, ValidationCode::UndefinedTimeout);
+        assert_eq!(diagnostics[0].path, "/do/2/wait/timeout");
+
+        let fetch = workflow.do_.get(&"fetch".to_string()).unwrap();
+        assert!(matches!(fetch.common().timeout, Some(OneOfTimeoutDefinitionOrReference::Timeout(_))));
+        let TaskDefinition::Call(call) = fetch else { panic!("expected a call task") };
+        assert_eq!(call.with.as_ref().unwrap()["endpoint"]["authentication"], json!({ "basic": { "username": "admin", "password": "admin" } }));
+        let TaskDefinition::Raise(raise) = workflow.do_.get(&"fail".to_string()).unwrap() else { panic!("expected a raise task") };
+        assert!(matches!(&raise.raise.error, OneOfErrorDefinitionOrReference::Error(error) if error.title == "Not Found"));
+
+        let added = extract_components(&mut workflow);
+        assert!(added.is_empty());
+        assert_eq!(workflow, original);
+        let TaskDefinition::Call(register) = workflow.do_.get(&"register".to_string()).unwrap() else { panic!("expected a call task") };
+        assert_eq!(register.with.as_ref().unwrap()["body"]["authentication"]["basic"]["username"], json!("${ .username }"));
+
+        let mut workflow = WorkflowDefinition::new(WorkflowDefinitionMetadata::new("test", "components", "0.1.0", None, None, None));
+        for name in ["first", "second"] {
+            let mut task = TaskDefinition::Wait(WaitTaskDefinition::new(OneOfDurationOrIso8601Expression::Duration(Duration::from_seconds(1))));
+            task.common_mut().timeout = Some(OneOfTimeoutDefinitionOrReference::Timeout(TimeoutDefinition { after: OneOfDurationOrIso8601Expression::Iso8601Expression("PT1M".to_string()) }));
+            workflow.do_.add(name.to_string(), task);
+        }
+        assert_eq!(extract_components(&mut workflow), vec!["timeouts/timeout-1".to_string()]);
+        assert!(workflow.use_.as_ref().unwrap().timeouts.as_ref().unwrap().contains_key("timeout-1"));
+        assert!(workflow.do_.values().all(|task| task.common().timeout == Some(OneOfTimeoutDefinitionOrReference::Reference("timeout-1".to_string()))));
+    }
+
+    #[test]
+    fn test_expand_extensions() {
+        let yaml = r#"
+document:
+  dsl: '1.0.0'
+  namespace: test
+  name: extensions
+  version: '0.1.0'
+use:
+  extensions:
+    - log:
+        extend: all
+        before:
+          - logStart:
+              set:
+                started: true
+    - audit:
+        extend: call
+        when: ${ .audited }
+        after:
+          - record:
+              set:
+                audited: true
+do:
+  - fetch:
+      call: http
+      with:
+        method: get
+        endpoint: https://example.com
+      if: ${ .enabled }
+      then: end
+  - pause:
+      wait:
+        seconds: 1
+  - route:
+      switch:
+        - again:
+            when: ${ .retry }
+            then: pause
+        - default:
+            then: exit
+  - log-before:
+      set:
+        collided: true
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let extensions = workflow.use_.as_ref().unwrap().extensions.as_ref().unwrap();
+        assert_eq!(extensions.keys().cloned().collect::<Vec<_>>(), vec!["log".to_string(), "audit".to_string()]);
+
+        let expanded = expand_extensions(&workflow);
+        assert!(expanded.use_.as_ref().unwrap().extensions.is_none());
+        let TaskDefinition::Do(fetch) = expanded.do_.get(&"fetch".to_string()).unwrap() else { panic!("expected a do task") };
+        assert_eq!(fetch.do_.keys().cloned().collect::<Vec<_>>(), vec!["log-before".to_string(), "fetch".to_string(), "audit-after".to_string()]);
+        assert_eq!(fetch.common.if_.as_deref(), Some("${ .enabled }"));
+        assert_eq!(fetch.common.then, Some(FlowDirective::End));
+        assert_eq!(fetch.do_.get(&"audit-after".to_string()).unwrap().common().if_.as_deref(), Some("${ .audited }"));
+        let inner = fetch.do_.get(&"fetch".to_string()).unwrap();
+        assert_eq!(inner.task_type(), TaskType::Call);
+        assert!(inner.common().if_.is_none() && inner.common().then.is_none());
+
+        let TaskDefinition::Do(pause) = expanded.do_.get(&"pause".to_string()).unwrap() else { panic!("expected a do task") };
+        assert_eq!(pause.do_.keys().cloned().collect::<Vec<_>>(), vec!["log-before".to_string(), "pause".to_string()]);
+        let passthrough = pause.do_.get(&"log-before".to_string()).unwrap().common().output.as_ref().and_then(|output| output.as_.clone());
+        assert_eq!(passthrough, Some(json!("${ $input }")));
+
+        assert_eq!(expanded.do_.keys().cloned().collect::<Vec<_>>(), vec!["fetch", "pause", "route", "route-switch", "log-before"]);
+        let TaskDefinition::Do(route) = expanded.do_.get(&"route".to_string()).unwrap() else { panic!("expected a do task") };
+        assert_eq!(route.do_.keys().cloned().collect::<Vec<_>>(), vec!["log-before".to_string()]);
+        assert_eq!(route.common.then, Some(FlowDirective::Goto("route-switch".to_string())));
+        let TaskDefinition::Switch(switch) = expanded.do_.get(&"route-switch".to_string()).unwrap() else { panic!("expected a switch task") };
+        assert_eq!(switch.switch.get(&"again".to_string()).unwrap().then, Some(FlowDirective::Goto("pause".to_string())));
+        assert_eq!(switch.switch.get(&"default".to_string()).unwrap().then, Some(FlowDirective::Exit));
+        let TaskDefinition::Do(collided) = expanded.do_.get(&"log-before".to_string()).unwrap() else { panic!("expected a do task") };
+        assert_eq!(collided.do_.keys().cloned().collect::<Vec<_>>(), vec!["log-before-1".to_string(), "log-before".to_string()]);
+        assert!(expanded.validate().iter().all(|diagnostic| !diagnostic.is_error()));
+        assert_eq!(expand_extensions(&expanded), expanded);
+    }
+
+    #[test]
+    fn test_runtime_expression_helpers() {
+        assert!(is_runtime_expression(" ${ .foo } "));
+        assert!(!is_runtime_expression(".foo"));
+        assert!(!is_runtime_expression("${"));
+        assert_eq!(runtime_expression_body("${ .foo | length }"), ".foo | length");
+        assert_eq!(runtime_expression_body(".foo"), ".foo");
+    }
+
+    #[cfg(feature = "jq")]
+    #[test]
+    fn test_jq_expression_evaluator() {
+        use crate::services::jq::*;
+        let evaluator = JqExpressionEvaluator::new();
+        let arguments = RuntimeExpressionArguments {
+            context: json!({ "tenant": "acme" }),
+            input: json!({ "id": 1 }),
+            secrets: json!({ "token": "s3cr3t" }),
+            task: json!({ "name": "greet" }),
+            workflow: json!({ "id": "wf-1" }),
+            runtime: json!({ "name": "test" }),
+            variables: std::collections::BTreeMap::from([("item".to_string(), json!({ "name": "first" }))])
+        };
+        let data = json!({ "name": "John", "items": [1, 2, 3] });
+        assert_eq!(evaluator.evaluate("${ .name }", &data, &arguments).unwrap(), json!("John"));
+        assert_eq!(evaluator.evaluate("${ .items | map(. * 2) | add }", &data, &arguments).unwrap(), json!(12));
+        assert_eq!(evaluator.evaluate("${ [$context.tenant, $input.id, $secrets.token, $task.name, $workflow.id, $runtime.name] }", &data, &arguments).unwrap(), json!(["acme", 1, "s3cr3t", "greet", "wf-1", "test"]));
+        assert_eq!(evaluator.evaluate("${ .items[] }", &data, &arguments).unwrap(), json!([1, 2, 3]));
+        assert_eq!(evaluator.evaluate("${ $item.name }", &data, &arguments).unwrap(), json!("first"));
+        assert_eq!(evaluator.evaluate("${ empty }", &data, &arguments).unwrap(), json!(null));
+        assert_eq!(evaluator.evaluate_value(&json!({ "greeting": "${ \"Hello \" + .name }", "count": "${ .items | length }", "static": "text" }), &data, &arguments).unwrap(), json!({ "greeting": "Hello John", "count": 3, "static": "text" }));
+        assert!(evaluator.check("${ .foo | }", &[]).is_err());
+        assert!(evaluator.check("${ $unknown }", &[]).is_err());
+        assert!(evaluator.check("${ $item.name }", &["item"]).is_ok());
+        assert!(evaluator.evaluate("${ error(\"boom\") }", &data, &arguments).is_err());
+    }
+
+    #[cfg(feature = "js")]
+    #[test]
+    fn test_javascript_expression_evaluator() {
+        use crate::services::js::*;
+        let evaluator = JavaScriptExpressionEvaluator::new();
+        let arguments = RuntimeExpressionArguments { input: json!({ "id": 1 }), secrets: json!({ "token": "s3cr3t" }), ..Default::default() };
+        let data = json!({ "name": "John", "items": [1, 2, 3] });
+        assert_eq!(evaluator.evaluate("${ this.name }", &data, &arguments).unwrap(), json!("John"));
+        assert_eq!(evaluator.evaluate("${ this.items.map(i => i * 2) }", &data, &arguments).unwrap(), json!([2, 4, 6]));
+        assert_eq!(evaluator.evaluate("${ [$input.id, $secrets.token, $context] }", &data, &arguments).unwrap(), json!([1, "s3cr3t", null]));
+        assert_eq!(evaluator.evaluate("${ undefined }", &data, &arguments).unwrap(), json!(null));
+        assert!(evaluator.check("${ this.name + }", &[]).is_err());
+        assert!(evaluator.evaluate("${ (() => { throw new Error('boom') })() }", &data, &arguments).is_err());
+        assert!(evaluator.check("${ 1); while(true){} (0 }", &[]).unwrap_err().message.contains("single JavaScript expression"));
+        assert!(evaluator.evaluate("${ 1]; while(true){} [0 }", &data, &arguments).is_err());
+        let evaluator = evaluator.with_timeout(std::time::Duration::from_millis(50)).with_memory_limit(8 * 1024 * 1024);
+        assert!(evaluator.evaluate("${ (() => { while(true){} })() }", &data, &arguments).is_err());
+        assert!(evaluator.evaluate("${ (() => { const items = []; while(true){ items.push('x'.repeat(1024)); } })() }", &data, &arguments).is_err());
+    }
+
+    #[test]
+    fn test_find_expressions_respects_evaluation_mode() {
+        let yaml = r#"
+document:
+  dsl: 1.0.0
+  namespace: test
+  name: expressions
+  version: 1.0.0
+  title: ${ not an expression }
+evaluate:
+  mode: strict
+do:
+  - check:
+      switch:
+        - adult:
+            when: .age >= 18
+            then: greet
+        - minor:
+            when: ${ .age < 18 }
+            then: end
+  - greet:
+      if: .enabled
+      set:
+        message: ${ "Hello " + .name }
+      output:
+        as: .message
+"#;
+        let mut workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        assert_eq!(workflow.evaluate.as_ref().unwrap().mode, Some(RuntimeExpressionEvaluationMode::Strict));
+        let paths = |workflow: &WorkflowDefinition| find_expressions(workflow).into_iter().map(|location| location.path).collect::<Vec<_>>();
+        assert_eq!(paths(&workflow), vec!["/do/0/check/switch/1/minor/when", "/do/1/greet/set/message"]);
+        workflow.evaluate.as_mut().unwrap().mode = Some(RuntimeExpressionEvaluationMode::Loose);
+        assert_eq!(paths(&workflow), vec![
+            "/do/0/check/switch/0/adult/when",
+            "/do/0/check/switch/1/minor/when",
+            "/do/1/greet/if",
+            "/do/1/greet/output/as",
+            "/do/1/greet/set/message"
+        ]);
+    }
+
+    #[cfg(feature = "jq")]
+    #[test]
+    fn test_check_expressions() {
+        use crate::services::jq::*;
+        let yaml = r#"
+document:
+  dsl: 1.0.0
+  namespace: test
+  name: expressions
+  version: 1.0.0
+do:
+  - loop:
+      for:
+        each: item
+        in: ${ .items[] | }
+      do:
+        - log:
+            set:
+              value: ${ $item.name }
+  - done:
+      if: ${ .done == true }
+      set: '${ { result: (.items | length) } }'
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let diagnostics = workflow.check_expressions(&JqExpressionEvaluator::new());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "/do/0/loop/for/in");
+        assert_eq!(diagnostics[0].code, ValidationCode::InvalidExpression);
+        let mut workflow = workflow;
+        workflow.do_.get_mut(&"done".to_string()).unwrap().common_mut().if_ = Some(".done == true".to_string());
+        let diagnostics = workflow.check_expressions(&JqExpressionEvaluator::new());
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!((diagnostics[1].path.as_str(), diagnostics[1].code.clone()), ("/do/1/done/if", ValidationCode::InvalidExpression));
+        workflow.evaluate = Some(RuntimeExpressionEvaluationConfiguration { language: RuntimeExpressionLanguage::Jq, mode: Some(RuntimeExpressionEvaluationMode::Loose) });
+        assert_eq!(workflow.check_expressions(&JqExpressionEvaluator::new()).len(), 1);
+        workflow.evaluate = Some(RuntimeExpressionEvaluationConfiguration { language: RuntimeExpressionLanguage::JavaScript, mode: None });
+        let diagnostics = workflow.check_expressions(&JqExpressionEvaluator::new());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, ValidationCode::UnsupportedExpressionLanguage);
+
+        let yaml = r#"
+document:
+  dsl: 1.0.0
+  namespace: test
+  name: expressions
+  version: 1.0.0
+do:
+  - consume:
+      call: asyncapi
+      with:
+        document:
+          endpoint: https://example.com/asyncapi.json
+        operation: onOrder
+        subscription:
+          consume:
+            amount: 5
+          foreach:
+            item: message
+            do:
+              - log:
+                  set:
+                    value: '${ { message: $message, position: $index } }'
+            output:
+              as: ${ $message.payload }
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        assert!(workflow.check_expressions(&JqExpressionEvaluator::new()).is_empty());
+    }
+
+    #[cfg(feature = "jq")]
+    #[test]
+    fn test_data_flow_processor() {
+        use crate::services::dataflow::*;
+        use crate::services::jq::*;
+        let yaml = r#"
+document:
+  dsl: 1.0.0
+  namespace: test
+  name: data-flow
+  version: 1.0.0
+do:
+  - greet:
+      input:
+        from:
+          name: ${ .user.firstName }
+          tenant: ${ $context.tenant }
+      set:
+        greeting: placeholder
+      output:
+        as: '${ { message: .greeting, to: $input.name } }'
+      export:
+        as: '${ $context + { lastGreeted: .to } }'
+  - passthrough:
+      set:
+        value: 1
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        let evaluator = JqExpressionEvaluator::new();
+        let processor = DataFlowProcessor::for_workflow(&evaluator, &workflow);
+        let arguments = RuntimeExpressionArguments::default();
+        let context = json!({ "tenant": "acme" });
+        let greet = workflow.do_.get(&"greet".to_string()).unwrap();
+        let flow = processor.process_task(greet, &json!({ "user": { "firstName": "John" } }), &json!({ "greeting": "Hello" }), &context, &arguments).unwrap();
+        assert_eq!(flow.input, json!({ "name": "John", "tenant": "acme" }));
+        assert_eq!(flow.output, json!({ "message": "Hello", "to": "John" }));
+        assert_eq!(flow.context, json!({ "tenant": "acme", "lastGreeted": "John" }));
+        let passthrough = workflow.do_.get(&"passthrough".to_string()).unwrap();
+        let flow = processor.process_task(passthrough, &json!({ "a": 1 }), &json!({ "value": 1 }), &context, &arguments).unwrap();
+        assert_eq!(flow, TaskDataFlow { input: json!({ "a": 1 }), output: json!({ "value": 1 }), context: context.clone() });
+        let loose = DataFlowProcessor::new(&evaluator, RuntimeExpressionEvaluationMode::Loose);
+        let output = OutputDataModelDefinition { schema: None, as_: Some(json!(".value * 2")) };
+        assert_eq!(loose.transform_output(Some(&output), &json!({ "value": 21 }), &arguments).unwrap(), json!(42));
+        assert_eq!(processor.transform_output(Some(&output), &json!({ "value": 21 }), &arguments).unwrap(), json!(".value * 2"));
+    }
+
+    #[test]
+    fn test_schema_validation() {
+        use crate::services::schema::*;
+        let yaml = r#"
+document:
+  dsl: 1.0.0
+  namespace: test
+  name: schema
+  version: 1.0.0
+input:
+  schema:
+    format: json
+    document:
+      type: object
+      required: [ name ]
+      properties:
+        name:
+          type: string
+        age:
+          type: integer
+          minimum: 0
+do:
+  - noop:
+      set:
+        ok: true
+"#;
+        let workflow = WorkflowDefinition::from_yaml_str(yaml).unwrap();
+        assert!(workflow.validate_input(&json!({ "name": "John", "age": 42 })).is_ok());
+        let error = workflow.validate_input(&json!({ "age": -1 })).unwrap_err();
+        let mut paths: Vec<&str> = error.violations.iter().map(|violation| violation.instance_path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["", "/age"]);
+        let definition = error.to_error_definition(Some("/do/0/noop"));
+        assert_eq!(definition.type_, VALIDATION_ERROR_TYPE);
+        assert_eq!(definition.status, json!(400));
+        assert_eq!(definition.instance.as_deref(), Some("/do/0/noop"));
+        let output = OutputDataModelDefinition { schema: Some(SchemaDefinition { format: SchemaFormat::Avro.into(), resource: None, document: Some(json!({})) }), as_: None };
+        assert!(validate_output(&output, &json!({})).unwrap_err().violations.is_empty());
+        assert!(validate_output(&OutputDataModelDefinition::default(), &json!(1)).is_ok());
+    }
+
+    #[cfg(feature = "avro")]
+    #[test]
+    fn test_avro_schema_validation() {
+        use crate::services::avro::*;
+        let schema = json!({
+            "type": "record",
+            "name": "Order",
+            "namespace": "com.example",
+            "fields": [
+                { "name": "id", "type": "long" },
+                { "name": "status", "type": { "type": "enum", "name": "Status", "symbols": [ "PENDING", "SHIPPED" ] } },
+                { "name": "items", "type": { "type": "array", "items": { "type": "record", "name": "Item", "fields": [ { "name": "sku", "type": "string" }, { "name": "quantity", "type": "int" } ] } } },
+                { "name": "note", "type": [ "null", "string" ] },
+                { "name": "next", "type": [ "null", "Item" ], "default": null }
+            ]
+        });
+        assert!(validate_avro_schema(&schema, &json!({ "id": 1, "status": "PENDING", "items": [ { "sku": "A1", "quantity": 2 } ] })).is_ok());
+        assert!(validate_avro_schema(&json!(schema.to_string()), &json!({ "id": 1, "status": "SHIPPED", "items": [], "next": { "sku": "B2", "quantity": 1 } })).is_ok());
+        let error = validate_avro_schema(&schema, &json!({ "status": "LOST", "items": [ { "sku": "A1", "quantity": "two" } ], "note": 3 })).unwrap_err();
+        let paths: Vec<&str> = error.violations.iter().map(|violation| violation.instance_path.as_str()).collect();
+        assert_eq!(paths, vec!["", "/status", "/items/0/quantity", "/note"]);
+        assert!(validate_avro_schema(&json!({ "type": "Unknown" }), &json!(1)).unwrap_err().violations.is_empty());
+    }
+
+    #[cfg(feature = "xml")]
+    #[test]
+    fn test_xml_schema_validation() {
+        use crate::services::xml::*;
+        let schema = json!(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">
+  <xs:simpleType name="Quantity">
+    <xs:restriction base="xs:positiveInteger">
+      <xs:maxInclusive value="100"/>
+    </xs:restriction>
+  </xs:simpleType>
+  <xs:element name="order">
+    <xs:complexType>
+      <xs:sequence>
+        <xs:element name="customer" type="xs:string"/>
+        <xs:element name="item" maxOccurs="unbounded">
+          <xs:complexType>
+            <xs:attribute name="sku" type="xs:string" use="required"/>
+            <xs:attribute name="quantity" type="Quantity"/>
+          </xs:complexType>
+        </xs:element>
+        <xs:element name="note" type="xs:string" minOccurs="0"/>
+      </xs:sequence>
+      <xs:attribute name="date" type="xs:date"/>
+    </xs:complexType>
+  </xs:element>
+</xs:schema>"#);
+        assert!(validate_xml_schema(&schema, &json!(r#"<order date="2024-01-31"><customer>John</customer><item sku="A1" quantity="2"/><item sku="B2"/></order>"#)).is_ok());
+        let error = validate_xml_schema(&schema, &json!(r#"<order date="tomorrow"><customer>John</customer><item quantity="200"/><item sku="B2" color="red"/></order>"#)).unwrap_err();
+        let paths: Vec<&str> = error.violations.iter().map(|violation| violation.instance_path.as_str()).collect();
+        assert_eq!(paths, vec!["/order", "/order/item[1]", "/order/item[1]", "/order/item[2]"]);
+        let error = validate_xml_schema(&schema, &json!("<order><item sku=\"A1\"/></order>")).unwrap_err();
+        assert_eq!(error.violations[0].message, "expected the element 'customer' but found 'item'");
+        assert!(validate_xml_schema(&schema, &json!({ "order": {} })).unwrap_err().violations.is_empty());
+        let error = validate_xml_schema(&schema, &json!(r#"<o:order xmlns:o="urn:orders"><customer>John</customer><item sku="A1"/></o:order>"#)).unwrap_err();
+        assert_eq!(error.violations[0].message, "the element '{urn:orders}order' is not declared");
+        let error = validate_xml_schema(&schema, &json!(r#"<order xmlns:o="urn:orders"><customer>John</customer><item o:sku="A1"/></order>"#)).unwrap_err();
+        assert_eq!(error.violations.len(), 2);
+        let unsupported = [
+            r#"<xs:simpleType name="Code"><xs:restriction base="xs:string"><xs:pattern value="[A-Z]+"/></xs:restriction></xs:simpleType>"#,
+            r#"<xs:group name="Lines"><xs:sequence><xs:element name="line" type="xs:string"/></xs:sequence></xs:group>"#,
+            r#"<xs:element name="order"><xs:complexType><xs:sequence><xs:element name="id" type="xs:string"/></xs:sequence></xs:complexType><xs:key name="id"><xs:selector xpath="."/><xs:field xpath="id"/></xs:key></xs:element>"#,
+            r#"<xs:element name="year" type="xs:gYear"/>"#,
+            r#"<xs:element ref="xs:schema"/>"#
+        ];
+        for construct in unsupported{
+            let schema = json!(format!(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}</xs:schema>"#, construct));
+            let error = validate_xml_schema(&schema, &json!("<order/>")).unwrap_err();
+            assert!(error.message.starts_with("unsupported XSD construct"), "{}", error.message);
+        }
+        let schema = json!(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema" targetNamespace="urn:orders"><xs:element name="order"/></xs:schema>"#);
+        assert!(validate_xml_schema(&schema, &json!("<order/>")).unwrap_err().message.starts_with("unsupported XSD construct"));
+        let cyclic = [
+            r#"<xs:complexType name="A"><xs:complexContent><xs:extension base="A"/></xs:complexContent></xs:complexType><xs:element name="order" type="A"/>"#,
+            r#"<xs:complexType name="A"><xs:complexContent><xs:restriction base="B"/></xs:complexContent></xs:complexType><xs:complexType name="B"><xs:complexContent><xs:extension base="A"/></xs:complexContent></xs:complexType><xs:element name="order" type="A"/>"#,
+            r#"<xs:simpleType name="Code"><xs:restriction base="Code"/></xs:simpleType><xs:element name="order" type="Code"/>"#,
+            r#"<xs:simpleType name="Codes"><xs:list itemType="Code"/></xs:simpleType><xs:simpleType name="Code"><xs:union memberTypes="xs:int Codes"/></xs:simpleType><xs:element name="order" type="Codes"/>"#
+        ];
+        for types in cyclic{
+            let schema = json!(format!(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema">{}</xs:schema>"#, types));
+            let error = validate_xml_schema(&schema, &json!("<order>A1</order>")).unwrap_err();
+            assert!(error.message.starts_with("invalid XML schema") && error.message.ends_with("derives from itself"), "{}", error.message);
+        }
+        let schema = json!(r#"<xs:schema xmlns:xs="http://www.w3.org/2001/XMLSchema"><xs:simpleType name="Code"><xs:union memberTypes="xs:int xs:int"/></xs:simpleType><xs:element name="order" type="Code"/></xs:schema>"#);
+        assert!(validate_xml_schema(&schema, &json!("<order>1</order>")).is_ok());
+    }
+
+    #[test]
+    fn test_retry_delays() {
+        use crate::models::retry::*;
+        use std::time::Duration as StdDuration;
+        let yaml = r#"
+delay:
+  seconds: 1
+backoff:
+  exponential: {}
+jitter:
+  from:
+    milliseconds: 0
+  to:
+    milliseconds: 100
+limit:
+  attempt:
+    count: 5
+    duration:
+      seconds: 3
+"#;
+        let policy: RetryPolicyDefinition = serde_yaml::from_str(yaml).unwrap();
+        let delays: Vec<u128> = policy.delays_with(|| 0.5).map(|delay| delay.as_millis()).collect();
+        assert_eq!(delays, vec![1050, 2050, 4050, 8050, 16050]);
+        assert_eq!(policy.delays().attempt_timeout(), Some(StdDuration::from_secs(3)));
+        let delays: Vec<u128> = policy.delays_with(|| f64::NAN).map(|delay| delay.as_millis()).collect();
+        assert_eq!(delays, vec![1000, 2000, 4000, 8000, 16000]);
+        let mut policy = RetryPolicyDefinition {
+            delay: Some(Duration::from_seconds(2)),
+            backoff: Some(BackoffStrategyDefinition { linear: Some(LinearBackoffDefinition { increment: Some(Duration::from_seconds(1)) }), ..Default::default() }),
+            limit: Some(RetryPolicyLimitDefinition { duration: Some(Duration::from_seconds(12)), ..Default::default() }),
+            ..Default::default()
+        };
+        let delays: Vec<u64> = policy.delays().map(|delay| delay.as_secs()).collect();
+        assert_eq!(delays, vec![2, 3, 4]);
+        let mut delays = policy.delays();
+        delays.next();
+        delays.record_elapsed(StdDuration::from_secs(8));
+        assert_eq!(delays.next(), None);
+        assert_eq!(delays.attempt(), 1);
+        policy.backoff = None;
+        policy.limit = None;
+        assert!(policy.delays().take(100).all(|delay| delay == StdDuration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_error_filter_matching() {
+        let error = ErrorDefinition::new("https://serverlessworkflow.io/spec/1.0.0/errors/communication", "Communication Error", json!(503), None, Some("/do/0/fetch".to_string()));
+        let filter = |with: serde_json::Value| ErrorFilterDefinition { with: serde_json::from_value(with).unwrap() };
+        assert!(ErrorFilterDefinition::default().matches(&error));
+        assert!(filter(json!({ "type": "https://serverlessworkflow.io/spec/1.0.0/errors/communication", "status": 503 })).matches(&error));
+        assert!(filter(json!({ "status": "503", "detail": null })).matches(&error));
+        assert!(!filter(json!({ "status": 500 })).matches(&error));
+        assert!(!filter(json!({ "instance": "/do/1/other" })).matches(&error));
+        assert!(!filter(json!({ "unknown": "value" })).matches(&error));
+    }
+
+    #[cfg(feature = "jq")]
+    #[test]
+    fn test_error_catcher_catches() {
+        use crate::services::jq::*;
+        let evaluator = JqExpressionEvaluator::new();
+        let error = ErrorDefinition::new("https://serverlessworkflow.io/spec/1.0.0/errors/communication", "Communication Error", json!(503), None, None);
+        let arguments = RuntimeExpressionArguments::default();
+        let catcher: ErrorCatcherDefinition = serde_yaml::from_str(r#"
+errors:
+  with:
+    status: 503
+as: failure
+when: ${ $failure.status >= 500 and .retryable }
+exceptWhen: ${ $failure.title == "Ignored" }
+"#).unwrap();
+        let caught = catcher.catches(&error, &json!({ "retryable": true }), &arguments, &evaluator).unwrap().unwrap();
+        assert_eq!(caught.variables.get("failure").and_then(|failure| failure.get("status")), Some(&json!(503)));
+        assert!(catcher.catches(&error, &json!({ "retryable": false }), &arguments, &evaluator).unwrap().is_none());
+        let ignored = ErrorDefinition::new(&error.type_, "Ignored", json!(503), None, None);
+        assert!(catcher.catches(&ignored, &json!({ "retryable": true }), &arguments, &evaluator).unwrap().is_none());
+        let other = ErrorDefinition::new(&error.type_, &error.title, json!(400), None, None);
+        assert!(catcher.catches(&other, &json!({ "retryable": true }), &arguments, &evaluator).unwrap().is_none());
+        let catcher = ErrorCatcherDefinition { when: Some("${ .retryable }".to_string()), ..Default::default() };
+        assert!(catcher.catches(&error, &json!({ "retryable": "yes" }), &arguments, &evaluator).is_err());
+        let caught = catcher.catches(&error, &json!({ "retryable": true }), &arguments, &evaluator).unwrap().unwrap();
+        assert!(caught.variables.contains_key("error"));
+    }
+}
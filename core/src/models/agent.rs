@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::call::*;
+use crate::models::duration::*;
+use crate::models::resource::*;
+
+/// Represents the arguments of a call to the 'a2a' function, used to interact with agents implementing the Agent-to-Agent protocol
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct A2ACallArguments{
+
+    /// Gets/sets the agent card, if any, that describes the agent to call
+    #[serde(rename = "agentCard", skip_serializing_if = "Option::is_none")]
+    pub agent_card: Option<ExternalResourceDefinition>,
+
+    /// Gets/sets the endpoint, if any, of the server that hosts the agent to call. Required if no agent card has been set
+    #[serde(rename = "server", skip_serializing_if = "Option::is_none")]
+    pub server: Option<OneOfEndpointDefinitionOrUri>,
+
+    /// Gets/sets the A2A method to call, such as 'message/send'
+    #[serde(rename = "method")]
+    pub method: String,
+
+    /// Gets/sets the parameters, if any, of the A2A method to call. Can be an object or a runtime expression
+    #[serde(rename = "parameters", skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>
+
+}
+impl CallArguments for A2ACallArguments {
+    const FUNCTION: CallFunction = CallFunction::A2a;
+    const REQUIRED_ARGUMENTS: &'static [&'static str] = &["method"];
+}
+impl A2ACallArguments{
+
+    /// Initializes new A2ACallArguments
+    pub fn new(method: &str) -> Self{
+        Self {
+            agent_card: None,
+            server: None,
+            method: method.to_string(),
+            parameters: None
+        }
+    }
+
+}
+
+/// Represents the arguments of a call to the 'mcp' function, used to interact with Model Context Protocol servers
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpCallArguments{
+
+    /// Gets/sets the version, if any, of the MCP protocol to use
+    #[serde(rename = "protocolVersion", skip_serializing_if = "Option::is_none")]
+    pub protocol_version: Option<String>,
+
+    /// Gets/sets the MCP method to call, such as 'tools/call'
+    #[serde(rename = "method")]
+    pub method: String,
+
+    /// Gets/sets a key/value mapping, if any, of the parameters of the MCP method to call
+    #[serde(rename = "parameters", skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<HashMap<String, Value>>,
+
+    /// Gets/sets the maximum amount of time, if any, to wait for the MCP server to respond
+    #[serde(rename = "timeout", skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<OneOfDurationOrIso8601Expression>,
+
+    /// Gets/sets the transport used to communicate with the MCP server
+    #[serde(rename = "transport")]
+    pub transport: McpTransportDefinition,
+
+    /// Gets/sets the client, if any, the workflow identifies itself as to the MCP server
+    #[serde(rename = "client", skip_serializing_if = "Option::is_none")]
+    pub client: Option<McpClientDefinition>
+
+}
+impl CallArguments for McpCallArguments {
+    const FUNCTION: CallFunction = CallFunction::Mcp;
+    const REQUIRED_ARGUMENTS: &'static [&'static str] = &["method", "transport"];
+}
+impl McpCallArguments{
+
+    /// Initializes new McpCallArguments
+    pub fn new(method: &str, transport: McpTransportDefinition) -> Self{
+        Self {
+            protocol_version: None,
+            method: method.to_string(),
+            parameters: None,
+            timeout: None,
+            transport,
+            client: None
+        }
+    }
+
+}
+
+/// Represents the definition of the transport used to communicate with a MCP server
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpTransportDefinition{
+
+    /// Gets/sets the HTTP transport, if any, used to communicate with the MCP server
+    #[serde(rename = "http", skip_serializing_if = "Option::is_none")]
+    pub http: Option<McpHttpTransportDefinition>,
+
+    /// Gets/sets the STDIO transport, if any, used to communicate with the MCP server
+    #[serde(rename = "stdio", skip_serializing_if = "Option::is_none")]
+    pub stdio: Option<McpStdioTransportDefinition>
+
+}
+
+/// Represents the definition of a HTTP transport used to communicate with a MCP server
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpHttpTransportDefinition{
+
+    /// Gets/sets the endpoint of the MCP server
+    #[serde(rename = "endpoint")]
+    pub endpoint: OneOfEndpointDefinitionOrUri,
+
+    /// Gets/sets the headers, if any, to send with requests to the MCP server
+    #[serde(rename = "headers", skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>
+
+}
+
+/// Represents the definition of a STDIO transport used to communicate with a MCP server
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpStdioTransportDefinition{
+
+    /// Gets/sets the command used to start the MCP server
+    #[serde(rename = "command")]
+    pub command: String,
+
+    /// Gets/sets the arguments, if any, of the command used to start the MCP server
+    #[serde(rename = "arguments", skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<Vec<String>>,
+
+    /// Gets/sets the environment variables, if any, of the MCP server process
+    #[serde(rename = "environment", skip_serializing_if = "Option::is_none")]
+    pub environment: Option<HashMap<String, String>>
+
+}
+
+/// Represents the definition of the client a workflow identifies itself as to a MCP server
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpClientDefinition{
+
+    /// Gets/sets the name of the client
+    #[serde(rename = "name")]
+    pub name: String,
+
+    /// Gets/sets the version of the client
+    #[serde(rename = "version")]
+    pub version: String
+
+}
@@ -1,8 +1,9 @@
 use serde_derive::{Deserialize, Serialize};
+use crate::models::enumeration::*;
 
 /// Provvides the default OAUTH2 rezquest encoding
-fn default_oauth2_request_encoding() -> String{
-    OAuth2RequestEncoding::FORM_URL.to_string()
+fn default_oauth2_request_encoding() -> OAuth2RequestEncoding{
+    OAuth2RequestEncoding::FormUrl
 }
 
 /// Provides the default OAUTH2 token endpoint
@@ -20,45 +21,69 @@ fn default_introspection_endpoint() -> String{
     "/oauth2/introspect".to_string()
 }
 
-/// Enumerates all supported authentication schemes
-pub struct AuthenticationScheme;
-impl AuthenticationScheme {
-    /// Gets the Basic authentication scheme
-    pub const BASIC: &'static str = "Basic";
-    /// Gets the Bearer authentication scheme
-    pub const BEARER: &'static str = "Bearer";
-    /// Gets the Certificate authentication scheme
-    pub const CERTIFICATE: &'static str = "Certificate";
-    /// Gets the Digest authentication scheme
-    pub const DIGEST: &'static str = "Digest";
-    /// Gets the OAuth2 authentication scheme
-    pub const OAUTH2: &'static str = "OAuth2";
-    /// Gets the OpenIDConnect authentication scheme
-    pub const OIDC: &'static str = "OpenIDConnect";
+string_enum! {
+    /// Enumerates all supported authentication schemes
+    pub enum AuthenticationScheme {
+        /// Indicates the Basic authentication scheme
+        Basic => "Basic",
+        /// Indicates the Bearer authentication scheme
+        Bearer => "Bearer",
+        /// Indicates the Certificate authentication scheme
+        Certificate => "Certificate",
+        /// Indicates the Digest authentication scheme
+        Digest => "Digest",
+        /// Indicates the OAuth2 authentication scheme
+        OAuth2 => "OAuth2",
+        /// Indicates the OpenIDConnect authentication scheme
+        Oidc => "OpenIDConnect"
+    }
+}
+
+string_enum! {
+    /// Enumerates all supported OAUTH2 authentication methods
+    pub enum OAuth2ClientAuthenticationMethod {
+        /// Represents the "client_secret_basic" authentication method, where the client secret is sent using HTTP Basic Authentication.
+        Basic => "client_secret_basic",
+        /// Represents the "client_secret_post" authentication method, where the client secret is sent in the body of the POST request.
+        Post => "client_secret_post",
+        /// Represents the "client_secret_jwt" authentication method, where the client authenticates using a JWT signed with the client secret.
+        Jwt => "client_secret_jwt",
+        /// Represents the "private_key_jwt" authentication method, where the client authenticates using a JWT signed with a private key.
+        PrivateKey => "private_key_jwt",
+        /// Represents the "none" authentication method, where no client authentication is performed.
+        None => "none"
+    }
 }
 
-/// Enumerates all supported OAUTH2 authentication methods
-pub struct OAuth2ClientAuthenticationMethod;
-impl OAuth2ClientAuthenticationMethod{
-    /// Represents the "client_secret_basic" authentication method, where the client secret is sent using HTTP Basic Authentication.
-    pub const BASIC: &'static str = "client_secret_basic";
-    /// Represents the "client_secret_post" authentication method, where the client secret is sent in the body of the POST request.
-    pub const POST: &'static str = "client_secret_post";
-    /// Represents the "client_secret_jwt" authentication method, where the client authenticates using a JWT signed with the client secret.
-    pub const JWT: &'static str = "client_secret_jwt";
-    /// Represents the "private_key_jwt" authentication method, where the client authenticates using a JWT signed with a private key.
-    pub const PRIVATE_KEY: &'static str = "private_key_jwt";
-    /// Represents the "none" authentication method, where no client authentication is performed.
-    pub const NONE: &'static str = "none";
+string_enum! {
+    /// Exposes all supported request encodings for OAUTH2 requests
+    pub enum OAuth2RequestEncoding {
+        /// Represents the "application/x-www-form-urlencoded" content type
+        FormUrl => "application/x-www-form-urlencoded",
+        /// Represents the "application/json" content type
+        Json => "application/json"
+    }
+}
+impl Default for OAuth2RequestEncoding {
+    fn default() -> Self {
+        default_oauth2_request_encoding()
+    }
 }
 
-/// Exposes all supported request encodings for OAUTH2 requests
-pub struct OAuth2RequestEncoding;
-impl OAuth2RequestEncoding{
-    /// Represents the "application/x-www-form-urlencoded" content type
-    pub const FORM_URL: &'static str = "application/x-www-form-urlencoded";
-    /// Represents the "application/json" content type
-    pub const JSON: &'static str = "application/json";
+string_enum! {
+    /// Enumerates all supported OAUTH2 grant types
+    pub enum OAuth2GrantType {
+        /// Represents the "authorization_code" grant type
+        AuthorizationCode => "authorization_code",
+        /// Represents the "client_credentials" grant type
+        ClientCredentials => "client_credentials",
+        /// Represents the "password" grant type
+        Password => "password",
+        /// Represents the "refresh_token" grant type
+        RefreshToken => "refresh_token",
+        /// Represents the "urn:ietf:params:oauth:grant-type:token-exchange" grant type
+        TokenExchange => "urn:ietf:params:oauth:grant-type:token-exchange"
+    }
 }
 
 /// Represents the definition of an authentication policy
@@ -97,7 +122,7 @@ pub struct AuthenticationPolicyDefinition{
 /// A trait that all authentication schemes must implement
 pub trait AuthenticationSchemeDefinition {
     /// Returns the name of the authentication scheme
-    fn scheme(&self) -> &str;
+    fn scheme(&self) -> AuthenticationScheme;
 }
 
 /// Represents the definition of a basic authentication scheme
@@ -118,8 +143,8 @@ pub struct BasicAuthenticationSchemeDefinition{
 
 }
 impl AuthenticationSchemeDefinition for BasicAuthenticationSchemeDefinition {
-    fn scheme(&self) -> &str {
-        AuthenticationScheme::BASIC
+    fn scheme(&self) -> AuthenticationScheme {
+        AuthenticationScheme::Basic
     }
 }
 
@@ -137,8 +162,8 @@ pub struct BearerAuthenticationSchemeDefinition{
 
 }
 impl AuthenticationSchemeDefinition for BearerAuthenticationSchemeDefinition{
-    fn scheme(&self) -> &str {
-        AuthenticationScheme::BEARER
+    fn scheme(&self) -> AuthenticationScheme {
+        AuthenticationScheme::Bearer
     }
 }
 
@@ -152,8 +177,8 @@ pub struct CertificateAuthenticationSchemeDefinition{
 
 }
 impl AuthenticationSchemeDefinition for CertificateAuthenticationSchemeDefinition{
-    fn scheme(&self) -> &str {
-        AuthenticationScheme::CERTIFICATE
+    fn scheme(&self) -> AuthenticationScheme {
+        AuthenticationScheme::Certificate
     }
 }
 
@@ -175,8 +200,8 @@ pub struct DigestAuthenticationSchemeDefinition{
 
 }
 impl AuthenticationSchemeDefinition for DigestAuthenticationSchemeDefinition{
-    fn scheme(&self) -> &str {
-        AuthenticationScheme::DIGEST
+    fn scheme(&self) -> AuthenticationScheme {
+        AuthenticationScheme::Digest
     }
 }
 
@@ -198,7 +223,7 @@ pub struct OAuth2AuthenticationClientDefinition{
 
     /// Gets/sets the authentication method to use to authenticate the client. Defaults to 'client_secret_post'
     #[serde(rename = "authentication", skip_serializing_if = "Option::is_none")]
-    pub authentication : Option<String>,
+    pub authentication : Option<OAuth2ClientAuthenticationMethod>,
 
 }
 
@@ -208,7 +233,7 @@ pub struct OAuth2AuthenticationRequestDefinition{
 
     /// Gets/sets the encoding of the authentication request. Defaults to 'application/x-www-form-urlencoded'
     #[serde(rename = "encoding", default = "default_oauth2_request_encoding")]
-    pub encoding : String
+    pub encoding : OAuth2RequestEncoding
 
 }
 
@@ -262,7 +287,7 @@ pub struct OAuth2AuthenticationSchemeDefinition{
 
     /// Gets/sets the grant type to use.
     #[serde(rename = "grant", skip_serializing_if = "Option::is_none")]
-    pub grant: Option<String>,
+    pub grant: Option<OAuth2GrantType>,
 
     /// Gets/sets the definition of the client to use.
     #[serde(rename = "client", skip_serializing_if = "Option::is_none")]
@@ -302,8 +327,8 @@ pub struct OAuth2AuthenticationSchemeDefinition{
 
 }
 impl AuthenticationSchemeDefinition for OAuth2AuthenticationSchemeDefinition{
-    fn scheme(&self) -> &str {
-        AuthenticationScheme::OAUTH2
+    fn scheme(&self) -> AuthenticationScheme {
+        AuthenticationScheme::OAuth2
     }
 }
 
@@ -321,7 +346,7 @@ pub struct OpenIDConnectSchemeDefinition{
 
     /// Gets/sets the grant type to use.
     #[serde(rename = "grant", skip_serializing_if = "Option::is_none")]
-    pub grant: Option<String>,
+    pub grant: Option<OAuth2GrantType>,
 
     /// Gets/sets the definition of the client to use.
     #[serde(rename = "client", skip_serializing_if = "Option::is_none")]
@@ -361,7 +386,7 @@ pub struct OpenIDConnectSchemeDefinition{
 
 }
 impl AuthenticationSchemeDefinition for OpenIDConnectSchemeDefinition{
-    fn scheme(&self) -> &str {
-        AuthenticationScheme::OIDC
+    fn scheme(&self) -> AuthenticationScheme {
+        AuthenticationScheme::Oidc
     }
 }
\ No newline at end of file
@@ -0,0 +1,432 @@
+use std::collections::HashMap;
+use std::fmt;
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::models::authentication::*;
+use crate::models::duration::*;
+use crate::models::enumeration::*;
+use crate::models::event::*;
+use crate::models::resource::*;
+use crate::models::task::*;
+
+string_enum! {
+    /// Enumerates all functions natively supported by call tasks
+    pub extensible enum CallFunction {
+        /// Indicates the function used to perform HTTP calls
+        Http => "http",
+        /// Indicates the function used to perform gRPC calls
+        Grpc => "grpc",
+        /// Indicates the function used to call operations described by OpenAPI documents
+        OpenApi => "openapi",
+        /// Indicates the function used to call operations described by AsyncAPI documents
+        AsyncApi => "asyncapi",
+        /// Indicates the function used to interact with agents implementing the Agent-to-Agent protocol
+        A2a => "a2a",
+        /// Indicates the function used to interact with Model Context Protocol servers
+        Mcp => "mcp"
+    }
+}
+
+string_enum! {
+    /// Enumerates all supported HTTP output formats
+    pub enum HttpOutputFormat {
+        /// Indicates that the call outputs the raw, base64 encoded, response content
+        Raw => "raw",
+        /// Indicates that the call outputs the deserialized response content
+        Content => "content",
+        /// Indicates that the call outputs the HTTP response
+        Response => "response"
+    }
+}
+
+/// Defines the fundamentals of the typed arguments of a natively supported function
+pub trait CallArguments: serde::Serialize + DeserializeOwned {
+
+    /// Gets the name of the function the arguments are used to call
+    const FUNCTION: CallFunction;
+
+    /// Gets the names of the arguments that must be supplied to the function
+    const REQUIRED_ARGUMENTS: &'static [&'static str];
+
+    /// Converts the arguments into a generic key/value mapping, as used by call tasks
+    fn to_arguments(&self) -> Result<HashMap<String, Value>, CallArgumentsError>{
+        match serde_json::to_value(self) {
+            Ok(Value::Object(arguments)) => Ok(arguments.into_iter().collect()),
+            Ok(_) => Err(CallArgumentsError::new(Self::FUNCTION.as_str(), "the arguments must serialize into a key/value mapping".to_string())),
+            Err(error) => Err(CallArgumentsError::new(Self::FUNCTION.as_str(), error.to_string()))
+        }
+    }
+
+    /// Converts the specified generic key/value mapping into typed arguments
+    fn from_arguments(arguments: &HashMap<String, Value>) -> Result<Self, CallArgumentsError>{
+        if let Some(missing) = Self::REQUIRED_ARGUMENTS.iter().find(|name| !arguments.contains_key(**name)){
+            return Err(CallArgumentsError::new(Self::FUNCTION.as_str(), format!("missing required argument '{}'", missing)));
+        }
+        let value = Value::Object(arguments.iter().map(|(name, value)| (name.clone(), value.clone())).collect());
+        serde_json::from_value(value).map_err(|error| CallArgumentsError::new(Self::FUNCTION.as_str(), error.to_string()))
+    }
+
+}
+
+/// Represents an error that occurred while converting the arguments of a call task into typed arguments
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallArgumentsError{
+
+    /// Gets/sets the name of the function the arguments were expected to be used with
+    pub function: String,
+
+    /// Gets/sets a human-readable message that describes the error
+    pub message: String
+
+}
+impl CallArgumentsError{
+
+    /// Initializes a new CallArgumentsError
+    pub fn new(function: &str, message: String) -> Self{
+        Self {
+            function: function.to_string(),
+            message
+        }
+    }
+
+}
+impl fmt::Display for CallArgumentsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid '{}' call arguments: {}", self.function, self.message)
+    }
+}
+impl std::error::Error for CallArgumentsError {}
+
+/// Represents a value that can be either a key/value mapping of strings or a runtime expression that evaluates to one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum OneOfStringMapOrExpression{
+    /// Variant holding a key/value mapping of strings
+    Map(HashMap<String, String>),
+    /// Variant holding a runtime expression
+    Expression(String)
+}
+impl Default for OneOfStringMapOrExpression {
+    fn default() -> Self {
+        OneOfStringMapOrExpression::Map(HashMap::new())
+    }
+}
+
+/// Represents the arguments of a call to the 'http' function
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HttpCallArguments{
+
+    /// Gets/sets the HTTP method of the request to perform
+    #[serde(rename = "method")]
+    pub method: String,
+
+    /// Gets/sets the endpoint to send the request to
+    #[serde(rename = "endpoint")]
+    pub endpoint: OneOfEndpointDefinitionOrUri,
+
+    /// Gets/sets the headers, if any, of the request to perform
+    #[serde(rename = "headers", skip_serializing_if = "Option::is_none")]
+    pub headers: Option<OneOfStringMapOrExpression>,
+
+    /// Gets/sets the body, if any, of the request to perform
+    #[serde(rename = "body", skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+
+    /// Gets/sets the query parameters, if any, of the request to perform
+    #[serde(rename = "query", skip_serializing_if = "Option::is_none")]
+    pub query: Option<OneOfStringMapOrExpression>,
+
+    /// Gets/sets the format of the call's output, if any. Defaults to 'content'
+    #[serde(rename = "output", skip_serializing_if = "Option::is_none")]
+    pub output: Option<HttpOutputFormat>,
+
+    /// Gets/sets a boolean indicating whether or not redirection status codes (300-399) should be treated as errors
+    #[serde(rename = "redirect", skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<bool>
+
+}
+impl CallArguments for HttpCallArguments {
+    const FUNCTION: CallFunction = CallFunction::Http;
+    const REQUIRED_ARGUMENTS: &'static [&'static str] = &["method", "endpoint"];
+}
+impl HttpCallArguments{
+
+    /// Initializes new HttpCallArguments
+    pub fn new(method: &str, endpoint: OneOfEndpointDefinitionOrUri) -> Self{
+        Self {
+            method: method.to_string(),
+            endpoint,
+            headers: None,
+            body: None,
+            query: None,
+            output: None,
+            redirect: None
+        }
+    }
+
+    /// Gets the authentication policy, if any, of the endpoint to call
+    pub fn authentication(&self) -> Option<&AuthenticationPolicyDefinition>{
+        match &self.endpoint {
+            OneOfEndpointDefinitionOrUri::Endpoint(endpoint) => endpoint.authentication.as_ref(),
+            OneOfEndpointDefinitionOrUri::Uri(_) => None
+        }
+    }
+
+}
+
+/// Represents the definition of the gRPC service to call
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrpcServiceDefinition{
+
+    /// Gets/sets the name of the gRPC service to call
+    #[serde(rename = "name")]
+    pub name: String,
+
+    /// Gets/sets the hostname of the gRPC service to call
+    #[serde(rename = "host")]
+    pub host: String,
+
+    /// Gets/sets the port, if any, of the gRPC service to call
+    #[serde(rename = "port", skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+
+    /// Gets/sets the authentication policy, if any, used to call the gRPC service
+    #[serde(rename = "authentication", skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<AuthenticationPolicyDefinition>
+
+}
+impl GrpcServiceDefinition{
+
+    /// Initializes a new GrpcServiceDefinition
+    pub fn new(name: &str, host: &str, port: Option<u16>, authentication: Option<AuthenticationPolicyDefinition>) -> Self{
+        Self {
+            name: name.to_string(),
+            host: host.to_string(),
+            port,
+            authentication
+        }
+    }
+
+}
+
+/// Represents the arguments of a call to the 'grpc' function
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GrpcCallArguments{
+
+    /// Gets/sets the proto resource that describes the gRPC service to call
+    #[serde(rename = "proto")]
+    pub proto: ExternalResourceDefinition,
+
+    /// Gets/sets the gRPC service to call
+    #[serde(rename = "service")]
+    pub service: GrpcServiceDefinition,
+
+    /// Gets/sets the name of the gRPC service method to call
+    #[serde(rename = "method")]
+    pub method: String,
+
+    /// Gets/sets a key/value mapping of the method's arguments, if any
+    #[serde(rename = "arguments", skip_serializing_if = "Option::is_none")]
+    pub arguments: Option<HashMap<String, Value>>
+
+}
+impl CallArguments for GrpcCallArguments {
+    const FUNCTION: CallFunction = CallFunction::Grpc;
+    const REQUIRED_ARGUMENTS: &'static [&'static str] = &["proto", "service", "method"];
+}
+impl GrpcCallArguments{
+
+    /// Initializes new GrpcCallArguments
+    pub fn new(proto: ExternalResourceDefinition, service: GrpcServiceDefinition, method: &str) -> Self{
+        Self {
+            proto,
+            service,
+            method: method.to_string(),
+            arguments: None
+        }
+    }
+
+}
+
+/// Represents the arguments of a call to the 'openapi' function
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenApiCallArguments{
+
+    /// Gets/sets the OpenAPI document that describes the operation to call
+    #[serde(rename = "document")]
+    pub document: ExternalResourceDefinition,
+
+    /// Gets/sets the id of the OpenAPI operation to call
+    #[serde(rename = "operationId")]
+    pub operation_id: String,
+
+    /// Gets/sets a key/value mapping of the parameters, if any, of the OpenAPI operation to call
+    #[serde(rename = "parameters", skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<HashMap<String, Value>>,
+
+    /// Gets/sets the authentication policy, if any, used to call the OpenAPI operation
+    #[serde(rename = "authentication", skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<AuthenticationPolicyDefinition>,
+
+    /// Gets/sets the format of the call's output, if any. Defaults to 'content'
+    #[serde(rename = "output", skip_serializing_if = "Option::is_none")]
+    pub output: Option<HttpOutputFormat>,
+
+    /// Gets/sets a boolean indicating whether or not redirection status codes (300-399) should be treated as errors
+    #[serde(rename = "redirect", skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<bool>
+
+}
+impl CallArguments for OpenApiCallArguments {
+    const FUNCTION: CallFunction = CallFunction::OpenApi;
+    const REQUIRED_ARGUMENTS: &'static [&'static str] = &["document", "operationId"];
+}
+impl OpenApiCallArguments{
+
+    /// Initializes new OpenApiCallArguments
+    pub fn new(document: ExternalResourceDefinition, operation_id: &str) -> Self{
+        Self {
+            document,
+            operation_id: operation_id.to_string(),
+            parameters: None,
+            authentication: None,
+            output: None,
+            redirect: None
+        }
+    }
+
+}
+
+/// Represents the arguments of a call to the 'asyncapi' function
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsyncApiCallArguments{
+
+    /// Gets/sets the AsyncAPI document that describes the operation to call
+    #[serde(rename = "document")]
+    pub document: ExternalResourceDefinition,
+
+    /// Gets/sets the name of the channel, if any, on which to perform the operation. Used only for AsyncAPI v2 documents
+    #[serde(rename = "channel", skip_serializing_if = "Option::is_none")]
+    pub channel: Option<String>,
+
+    /// Gets/sets the name of the operation to perform
+    #[serde(rename = "operation")]
+    pub operation: String,
+
+    /// Gets/sets the server, if any, to call the operation on
+    #[serde(rename = "server", skip_serializing_if = "Option::is_none")]
+    pub server: Option<AsyncApiServerDefinition>,
+
+    /// Gets/sets the protocol, if any, to use to select the target server
+    #[serde(rename = "protocol", skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+
+    /// Gets/sets the message, if any, to publish using the target operation
+    #[serde(rename = "message", skip_serializing_if = "Option::is_none")]
+    pub message: Option<AsyncApiOutboundMessageDefinition>,
+
+    /// Gets/sets the subscription, if any, to messages consumed using the target operation
+    #[serde(rename = "subscription", skip_serializing_if = "Option::is_none")]
+    pub subscription: Option<AsyncApiSubscriptionDefinition>,
+
+    /// Gets/sets the authentication policy, if any, used to call the AsyncAPI operation
+    #[serde(rename = "authentication", skip_serializing_if = "Option::is_none")]
+    pub authentication: Option<AuthenticationPolicyDefinition>
+
+}
+impl CallArguments for AsyncApiCallArguments {
+    const FUNCTION: CallFunction = CallFunction::AsyncApi;
+    const REQUIRED_ARGUMENTS: &'static [&'static str] = &["document", "operation"];
+}
+impl AsyncApiCallArguments{
+
+    /// Initializes new AsyncApiCallArguments
+    pub fn new(document: ExternalResourceDefinition, operation: &str) -> Self{
+        Self {
+            document,
+            operation: operation.to_string(),
+            ..Default::default()
+        }
+    }
+
+}
+
+/// Represents the definition of the server to call an AsyncAPI operation on
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsyncApiServerDefinition{
+
+    /// Gets/sets the name of the server to call the operation on
+    #[serde(rename = "name")]
+    pub name: String,
+
+    /// Gets/sets a key/value mapping, if any, of the server's variables
+    #[serde(rename = "variables", skip_serializing_if = "Option::is_none")]
+    pub variables: Option<HashMap<String, Value>>
+
+}
+impl AsyncApiServerDefinition{
+
+    /// Initializes a new AsyncApiServerDefinition
+    pub fn new(name: &str) -> Self{
+        Self {
+            name: name.to_string(),
+            variables: None
+        }
+    }
+
+}
+
+/// Represents the definition of a message to publish using an AsyncAPI operation
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsyncApiOutboundMessageDefinition{
+
+    /// Gets/sets the message's payload, if any
+    #[serde(rename = "payload", skip_serializing_if = "Option::is_none")]
+    pub payload: Option<Value>,
+
+    /// Gets/sets the message's headers, if any
+    #[serde(rename = "headers", skip_serializing_if = "Option::is_none")]
+    pub headers: Option<Value>
+
+}
+
+/// Represents the definition of a subscription to messages consumed using an AsyncAPI operation
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsyncApiSubscriptionDefinition{
+
+    /// Gets/sets a runtime expression, if any, used to filter consumed messages
+    #[serde(rename = "filter", skip_serializing_if = "Option::is_none")]
+    pub filter: Option<String>,
+
+    /// Gets/sets the policy that defines how many messages to consume, and for how long
+    #[serde(rename = "consume")]
+    pub consume: AsyncApiMessageConsumptionPolicyDefinition,
+
+    /// Gets/sets the configuration of the iterator, if any, for processing each consumed message
+    #[serde(rename = "foreach", skip_serializing_if = "Option::is_none")]
+    pub foreach: Option<SubscriptionIteratorDefinition>
+
+}
+
+/// Represents the policy that defines how many messages an AsyncAPI subscription consumes, and for how long
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AsyncApiMessageConsumptionPolicyDefinition{
+
+    /// Gets/sets the amount, if any, of messages to consume
+    #[serde(rename = "amount", skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u32>,
+
+    /// Gets/sets a runtime expression, if any, evaluated after each consumed message to determine whether or not to keep consuming
+    #[serde(rename = "while", skip_serializing_if = "Option::is_none")]
+    pub while_: Option<String>,
+
+    /// Gets/sets the events or runtime expression, if any, that stop the consumption of messages
+    #[serde(rename = "until", skip_serializing_if = "Option::is_none")]
+    pub until: Option<OneOfEventConsumptionStrategyDefinitionOrExpression>,
+
+    /// Gets/sets the duration, if any, during which to consume messages
+    #[serde(rename = "for", skip_serializing_if = "Option::is_none")]
+    pub for_: Option<OneOfDurationOrIso8601Expression>
+
+}
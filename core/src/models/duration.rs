@@ -83,6 +83,139 @@ impl Duration{
         }
     }
 
+    /// Parses the specified ISO 8601 duration expression, such as 'PT5M' or 'P1DT12H'
+    ///
+    /// Years and months are rejected, as they do not represent a fixed amount of time. Weeks are converted into days.
+    pub fn parse_iso8601(expression: &str) -> Result<Self, Iso8601DurationError>{
+        let error = |message: &str| Iso8601DurationError::new(expression, message);
+        let Some(body) = expression.strip_prefix('P') else {
+            return Err(error("the expression must start with 'P'"));
+        };
+        let (date, time) = match body.split_once('T') {
+            Some((date, time)) => {
+                if time.is_empty(){
+                    return Err(error("the time designator 'T' must be followed by at least one component"));
+                }
+                (date, Some(time))
+            },
+            None => (body, None)
+        };
+        if date.is_empty() && time.is_none(){
+            return Err(error("the expression must define at least one component"));
+        }
+        let mut duration = Duration::default();
+        let mut days: Option<u64> = None;
+        for (value, designator) in Self::parse_iso8601_components(date).map_err(|message| error(&message))?{
+            let value = Self::parse_iso8601_integer(&value).map_err(|message| error(&message))?;
+            let component_days = match designator {
+                'W' => value.checked_mul(7).ok_or_else(|| error("the amount of weeks is too large"))?,
+                'D' => value,
+                'Y' | 'M' => return Err(error("years and months are not supported, as they do not represent a fixed amount of time")),
+                _ => return Err(error(&format!("unexpected date designator '{}'", designator)))
+            };
+            days = Some(days.unwrap_or(0).checked_add(component_days).ok_or_else(|| error("the amount of days is too large"))?);
+        }
+        duration.days = days;
+        if let Some(time) = time{
+            for (value, designator) in Self::parse_iso8601_components(time).map_err(|message| error(&message))?{
+                match designator {
+                    'H' => duration.hours = Some(Self::parse_iso8601_integer(&value).map_err(|message| error(&message))?),
+                    'M' => duration.minutes = Some(Self::parse_iso8601_integer(&value).map_err(|message| error(&message))?),
+                    'S' => {
+                        let (seconds, fraction) = match value.split_once(['.', ',']) {
+                            Some((seconds, fraction)) => (seconds, Some(fraction)),
+                            None => (value.as_str(), None)
+                        };
+                        duration.seconds = Some(Self::parse_iso8601_integer(seconds).map_err(|message| error(&message))?);
+                        if let Some(fraction) = fraction{
+                            if fraction.is_empty() || fraction.len() > 3 || !fraction.chars().all(|c| c.is_ascii_digit()){
+                                return Err(error("fractional seconds must be expressed with 1 to 3 digits"));
+                            }
+                            let milliseconds = format!("{:0<3}", fraction).parse::<u64>().map_err(|e| error(&e.to_string()))?;
+                            if milliseconds > 0{
+                                duration.milliseconds = Some(milliseconds);
+                            }
+                        }
+                    },
+                    _ => return Err(error(&format!("unexpected time designator '{}'", designator)))
+                }
+            }
+        }
+        if duration.checked_total_milliseconds().is_none(){
+            return Err(error("the duration is too large to be represented in milliseconds"));
+        }
+        Ok(duration)
+    }
+
+    /// Formats the duration as an ISO 8601 duration expression
+    pub fn to_iso8601(&self) -> String{
+        let mut expression = String::from("P");
+        let days = self.days.unwrap_or(0);
+        if days > 0{
+            expression.push_str(&format!("{}D", days));
+        }
+        let hours = self.hours.unwrap_or(0);
+        let minutes = self.minutes.unwrap_or(0);
+        let seconds = self.seconds.unwrap_or(0);
+        let milliseconds = self.milliseconds.unwrap_or(0);
+        if hours > 0 || minutes > 0 || seconds > 0 || milliseconds > 0 || days == 0{
+            expression.push('T');
+            if hours > 0{
+                expression.push_str(&format!("{}H", hours));
+            }
+            if minutes > 0{
+                expression.push_str(&format!("{}M", minutes));
+            }
+            let seconds = seconds + milliseconds / 1000;
+            let milliseconds = milliseconds % 1000;
+            if milliseconds > 0{
+                let fraction = format!("{:03}", milliseconds);
+                expression.push_str(&format!("{}.{}S", seconds, fraction.trim_end_matches('0')));
+            }
+            else if seconds > 0 || expression.ends_with('T'){
+                expression.push_str(&format!("{}S", seconds));
+            }
+        }
+        expression
+    }
+
+    /// Converts the duration into a std::time::Duration, saturating at u64::MAX milliseconds
+    pub fn to_std(&self) -> std::time::Duration{
+        std::time::Duration::from_millis(self.total_milliseconds())
+    }
+
+    // Splits the specified ISO 8601 duration part into its value/designator components
+    fn parse_iso8601_components(part: &str) -> Result<Vec<(String, char)>, String>{
+        let mut components = Vec::new();
+        let mut value = String::new();
+        for character in part.chars(){
+            if character.is_ascii_digit() || character == '.' || character == ','{
+                value.push(character);
+            }
+            else if character.is_ascii_uppercase(){
+                if value.is_empty(){
+                    return Err(format!("the designator '{}' must be preceded by a value", character));
+                }
+                if components.iter().any(|(_, designator)| *designator == character){
+                    return Err(format!("the designator '{}' is defined more than once", character));
+                }
+                components.push((std::mem::take(&mut value), character));
+            }
+            else{
+                return Err(format!("unexpected character '{}'", character));
+            }
+        }
+        if !value.is_empty(){
+            return Err(format!("the value '{}' must be followed by a designator", value));
+        }
+        Ok(components)
+    }
+
+    // Parses the specified integer value of an ISO 8601 duration component
+    fn parse_iso8601_integer(value: &str) -> Result<u64, String>{
+        value.parse::<u64>().map_err(|_| format!("'{}' is not a valid integer value", value))
+    }
+
     /// Gets the the duration's total amount of days
     pub fn total_days(&self) -> f64{
         self.total_hours() / 24.0
@@ -103,14 +236,19 @@ impl Duration{
         (self.total_milliseconds() as f64) / 1000.0
     }
 
-    /// Gets the the duration's total amount of milliseconds
+    /// Gets the the duration's total amount of milliseconds, saturating at u64::MAX
     pub fn total_milliseconds(&self) -> u64{
-        let days_ms = self.days.unwrap_or(0) * 24 * 60 * 60 * 1000;
-        let hours_ms = self.hours.unwrap_or(0) * 60 * 60 * 1000;
-        let minutes_ms = self.minutes.unwrap_or(0) * 60 * 1000;
-        let seconds_ms = self.seconds.unwrap_or(0) * 1000;
+        self.checked_total_milliseconds().unwrap_or(u64::MAX)
+    }
+
+    // Gets the duration's total amount of milliseconds, or None if it overflows a u64
+    fn checked_total_milliseconds(&self) -> Option<u64>{
+        let days_ms = self.days.unwrap_or(0).checked_mul(24 * 60 * 60 * 1000)?;
+        let hours_ms = self.hours.unwrap_or(0).checked_mul(60 * 60 * 1000)?;
+        let minutes_ms = self.minutes.unwrap_or(0).checked_mul(60 * 1000)?;
+        let seconds_ms = self.seconds.unwrap_or(0).checked_mul(1000)?;
         let millis = self.milliseconds.unwrap_or(0);
-        days_ms + hours_ms + minutes_ms + seconds_ms + millis
+        days_ms.checked_add(hours_ms)?.checked_add(minutes_ms)?.checked_add(seconds_ms)?.checked_add(millis)
     }
 
 }
@@ -136,6 +274,46 @@ impl fmt::Display for Duration {
     }
 }
 
+impl From<&Duration> for std::time::Duration {
+    fn from(duration: &Duration) -> Self {
+        duration.to_std()
+    }
+}
+impl From<Duration> for std::time::Duration {
+    fn from(duration: Duration) -> Self {
+        duration.to_std()
+    }
+}
+
+/// Represents the error returned when parsing an invalid ISO 8601 duration expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Iso8601DurationError{
+
+    /// Gets/sets the expression that could not be parsed
+    pub expression: String,
+
+    /// Gets/sets a human-readable message that describes the error
+    pub message: String
+
+}
+impl Iso8601DurationError{
+
+    /// Initializes a new Iso8601DurationError
+    pub fn new(expression: &str, message: &str) -> Self{
+        Self {
+            expression: expression.to_string(),
+            message: message.to_string()
+        }
+    }
+
+}
+impl fmt::Display for Iso8601DurationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid ISO 8601 duration '{}': {}", self.expression, self.message)
+    }
+}
+impl std::error::Error for Iso8601DurationError {}
+
 /// Represents a value that can be either a Duration or an ISO 8601 duration expression
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -158,4 +336,20 @@ impl fmt::Display for OneOfDurationOrIso8601Expression {
             OneOfDurationOrIso8601Expression::Iso8601Expression(expr) => write!(f, "{}", expr),
         }
     }
-}
\ No newline at end of file
+}
+impl OneOfDurationOrIso8601Expression {
+
+    /// Gets the duration, parsing the ISO 8601 expression if necessary
+    pub fn to_duration(&self) -> Result<Duration, Iso8601DurationError>{
+        match self {
+            OneOfDurationOrIso8601Expression::Duration(duration) => Ok(duration.clone()),
+            OneOfDurationOrIso8601Expression::Iso8601Expression(expression) => Duration::parse_iso8601(expression)
+        }
+    }
+
+    /// Gets the duration as a std::time::Duration, parsing the ISO 8601 expression if necessary
+    pub fn to_std(&self) -> Result<std::time::Duration, Iso8601DurationError>{
+        self.to_duration().map(|duration| duration.to_std())
+    }
+
+}
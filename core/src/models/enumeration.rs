@@ -0,0 +1,155 @@
+use std::fmt;
+
+/// Represents the error returned when parsing a string that does not match any of the values of an enumeration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError{
+
+    /// Gets/sets the name of the enumeration the string was parsed into
+    pub enumeration: &'static str,
+
+    /// Gets/sets the string that could not be parsed
+    pub value: String,
+
+    /// Gets/sets the values supported by the enumeration
+    pub expected: &'static [&'static str]
+
+}
+impl ParseEnumError{
+
+    /// Initializes a new ParseEnumError
+    pub fn new(enumeration: &'static str, value: &str, expected: &'static [&'static str]) -> Self{
+        Self {
+            enumeration,
+            value: value.to_string(),
+            expected
+        }
+    }
+
+}
+impl fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown {} '{}', expected one of: {}", self.enumeration, self.value, self.expected.join(", "))
+    }
+}
+impl std::error::Error for ParseEnumError {}
+
+/// Declares an enumeration that is serialized as, and parsed from, a string.
+///
+/// Enumerations declared as `extensible` get an additional `Other` variant that accepts any string, which allows extending the values defined by the specification.
+macro_rules! string_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident => $value:literal ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
+        #[serde(try_from = "String", into = "String")]
+        pub enum $name {
+            $( $(#[$variant_meta])* $variant ),+
+        }
+        impl $name {
+
+            /// Gets all the values of the enumeration
+            pub const ALL: &'static [$name] = &[ $( $name::$variant ),+ ];
+
+            /// Gets the string representation of all the values of the enumeration
+            pub const VALUES: &'static [&'static str] = &[ $( $value ),+ ];
+
+            /// Gets the string representation of the value
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( $name::$variant => $value ),+
+                }
+            }
+
+        }
+        impl std::str::FromStr for $name {
+            type Err = $crate::models::enumeration::ParseEnumError;
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                match value {
+                    $( $value => Ok($name::$variant), )+
+                    _ => Err($crate::models::enumeration::ParseEnumError::new(stringify!($name), value, Self::VALUES))
+                }
+            }
+        }
+        impl TryFrom<String> for $name {
+            type Error = $crate::models::enumeration::ParseEnumError;
+            fn try_from(value: String) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+        $crate::models::enumeration::string_enum!(@common $name);
+    };
+    (
+        $(#[$meta:meta])*
+        pub extensible enum $name:ident {
+            $( $(#[$variant_meta:meta])* $variant:ident => $value:literal ),+ $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, serde_derive::Serialize, serde_derive::Deserialize)]
+        #[serde(from = "String", into = "String")]
+        pub enum $name {
+            $( $(#[$variant_meta])* $variant, )+
+            /// Variant holding a value that is not defined by the specification
+            Other(String)
+        }
+        impl $name {
+
+            /// Gets the string representation of the values defined by the specification
+            pub const VALUES: &'static [&'static str] = &[ $( $value ),+ ];
+
+            /// Gets the string representation of the value
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( $name::$variant => $value, )+
+                    $name::Other(value) => value
+                }
+            }
+
+        }
+        impl std::str::FromStr for $name {
+            type Err = std::convert::Infallible;
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                Ok(match value {
+                    $( $value => $name::$variant, )+
+                    _ => $name::Other(value.to_string())
+                })
+            }
+        }
+        impl From<String> for $name {
+            fn from(value: String) -> Self {
+                match value.parse() {
+                    Ok(value) => value,
+                    Err(never) => match never {}
+                }
+            }
+        }
+        $crate::models::enumeration::string_enum!(@common $name);
+    };
+    (@common $name:ident) => {
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                value.as_str().to_string()
+            }
+        }
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.as_str() == other
+            }
+        }
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.as_str() == *other
+            }
+        }
+    };
+}
+pub(crate) use string_enum;
@@ -1,5 +1,6 @@
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
+use crate::models::reference::*;
 
 /// Represents the definition an error to raise
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
@@ -39,6 +40,11 @@ impl ErrorDefinition{
         }
     }
 
+    /// Gets the reference to the task the error occurred in, if the error's instance is a valid JSON pointer
+    pub fn instance_reference(&self) -> Option<TaskReference>{
+        self.instance.as_deref().and_then(|instance| TaskReference::parse(instance).ok())
+    }
+
 }
 
 /// Represents a value that can be either a ErrorDefinition or a reference to a ErrorDefinition
@@ -1,5 +1,5 @@
 use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::models::map::*;
 use crate::models::task::*;
 
 /// Represents the definition of a an extension
@@ -16,9 +16,20 @@ pub struct ExtensionDefinition{
 
     /// Gets/sets a name/definition list, if any, of the tasks to execute before the extended task
     #[serde(rename = "before", skip_serializing_if = "Option::is_none")]
-    pub before: Option<Vec<HashMap<String, TaskDefinition>>>,
+    pub before: Option<Map<String, TaskDefinition>>,
 
     /// Gets/sets a name/definition list, if any, of the tasks to execute after the extended task
     #[serde(rename = "after", skip_serializing_if = "Option::is_none")]
-    pub after: Option<Vec<HashMap<String, TaskDefinition>>>
-}
\ No newline at end of file
+    pub after: Option<Map<String, TaskDefinition>>
+}
+impl ExtensionDefinition{
+
+    /// Gets the value of the 'extend' property used to extend all tasks, regardless of their type
+    pub const ALL: &'static str = "all";
+
+    /// Determines whether or not the extension applies to tasks of the specified type
+    pub fn extends(&self, task_type: &TaskType) -> bool{
+        self.extend == Self::ALL || self.extend == task_type.as_str()
+    }
+
+}
@@ -1,13 +1,21 @@
-use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use serde::{Serialize, Deserialize, Deserializer};
+use std::collections::hash_map;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
 use std::hash::Hash;
 
+/// Represents an iterator over the key/value pairs of a map
+pub type Iter<'a, TKey, TValue> = std::iter::FlatMap<std::slice::Iter<'a, HashMap<TKey, TValue>>, hash_map::Iter<'a, TKey, TValue>, fn(&'a HashMap<TKey, TValue>) -> hash_map::Iter<'a, TKey, TValue>>;
+
+/// Represents an iterator over the key/value pairs of a map, with mutable references to the values
+pub type IterMut<'a, TKey, TValue> = std::iter::FlatMap<std::slice::IterMut<'a, HashMap<TKey, TValue>>, hash_map::IterMut<'a, TKey, TValue>, fn(&'a mut HashMap<TKey, TValue>) -> hash_map::IterMut<'a, TKey, TValue>>;
+
+/// Represents an iterator that moves the key/value pairs out of a map
+pub type IntoIter<TKey, TValue> = std::iter::FlatMap<std::vec::IntoIter<HashMap<TKey, TValue>>, hash_map::IntoIter<TKey, TValue>, fn(HashMap<TKey, TValue>) -> hash_map::IntoIter<TKey, TValue>>;
+
 /// Represents an ordered key/value map array
-#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
-#[serde(bound(
-    serialize = "TKey: Serialize, TValue: Serialize",
-    deserialize = "TKey: for<'d> Deserialize<'d> + Eq + Hash + Clone + PartialEq, TValue: for<'d> Deserialize<'d> + Clone + PartialEq"
-))]
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize)]
+#[serde(bound(serialize = "TKey: Serialize, TValue: Serialize"))]
 #[serde(transparent)]
 pub struct Map<TKey, TValue>
 where
@@ -31,6 +39,30 @@ where
     }
 }
 
+// Manual Deserialize implementation for Map, which rejects entries that do not define exactly one key, and duplicate keys
+impl<'de, TKey, TValue> Deserialize<'de> for Map<TKey, TValue>
+where
+    TKey: Eq + Hash + Clone + Debug + Deserialize<'de> + PartialEq,
+    TValue: Clone + Deserialize<'de> + PartialEq,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<HashMap<TKey, TValue>>::deserialize(deserializer)?;
+        if let Some(entry) = entries.iter().find(|entry| entry.len() != 1) {
+            return Err(serde::de::Error::custom(format!("each entry must define exactly one key, found {}", entry.len())));
+        }
+        let mut keys = HashSet::new();
+        for key in entries.iter().flat_map(|entry| entry.keys()) {
+            if !keys.insert(key) {
+                return Err(serde::de::Error::custom(format!("duplicate key {:?}", key)));
+            }
+        }
+        Ok(Map { entries })
+    }
+}
+
 impl<TKey, TValue> Map<TKey, TValue>
 where
     TKey: Eq + Hash + Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
@@ -42,24 +74,224 @@ where
         Self::default()
     }
 
-    /// Initializes a new map with the provided entries
+    /// Initializes a new map with the provided entries. Later entries replace the values of earlier entries with the same key
     pub fn from(entries: Vec<(TKey, TValue)>) -> Self {
-        let mut vec_entries = Vec::new();
-        for (key, value) in entries {
-            let mut single_entry = HashMap::new();
-            single_entry.insert(key, value);
-            vec_entries.push(single_entry);
-        }
-        Map {
-            entries: vec_entries,
-        }
+        entries.into_iter().collect()
     }
 
-    /// Adds the specified entry
+    /// Adds the specified entry. If the map already contains an entry with the same key, its value is replaced in place
     pub fn add(&mut self, key: TKey, value: TValue) {
+        if let Some(existing) = self.get_mut(&key) {
+            *existing = value;
+            return;
+        }
         let mut single_entry = HashMap::new();
         single_entry.insert(key, value);
         self.entries.push(single_entry);
     }
 
-}
\ No newline at end of file
+    /// Gets the number of entries in the map
+    pub fn len(&self) -> usize {
+        self.entries.iter().map(|entry| entry.len()).sum()
+    }
+
+    /// Determines whether or not the map is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.iter().all(|entry| entry.is_empty())
+    }
+
+    /// Determines whether or not the map contains the specified key
+    pub fn contains_key(&self, key: &TKey) -> bool {
+        self.entries.iter().any(|entry| entry.contains_key(key))
+    }
+
+    /// Gets the value associated with the specified key, if any
+    pub fn get(&self, key: &TKey) -> Option<&TValue> {
+        self.entries.iter().find_map(|entry| entry.get(key))
+    }
+
+    /// Gets a mutable reference to the value associated with the specified key, if any
+    pub fn get_mut(&mut self, key: &TKey) -> Option<&mut TValue> {
+        self.entries.iter_mut().find_map(|entry| entry.get_mut(key))
+    }
+
+    /// Gets the key/value pair at the specified index, if any
+    pub fn get_index(&self, index: usize) -> Option<(&TKey, &TValue)> {
+        self.iter().nth(index)
+    }
+
+    /// Gets the index of the entry with the specified key, if any
+    pub fn position(&self, key: &TKey) -> Option<usize> {
+        self.keys().position(|candidate| candidate == key)
+    }
+
+    /// Gets an iterator over the map's key/value pairs, in order
+    pub fn iter(&self) -> Iter<'_, TKey, TValue> {
+        self.entries.iter().flat_map(HashMap::iter as fn(&HashMap<TKey, TValue>) -> hash_map::Iter<'_, TKey, TValue>)
+    }
+
+    /// Gets an iterator over the map's key/value pairs, in order, with mutable references to the values
+    pub fn iter_mut(&mut self) -> IterMut<'_, TKey, TValue> {
+        self.entries.iter_mut().flat_map(HashMap::iter_mut as fn(&mut HashMap<TKey, TValue>) -> hash_map::IterMut<'_, TKey, TValue>)
+    }
+
+    /// Gets an iterator over the map's keys, in order
+    pub fn keys(&self) -> impl Iterator<Item = &TKey> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Gets an iterator over the map's values, in order
+    pub fn values(&self) -> impl Iterator<Item = &TValue> {
+        self.iter().map(|(_, value)| value)
+    }
+
+    /// Inserts the specified entry at the specified index. Any existing entry with the same key is removed first
+    ///
+    /// Panics if the index is greater than the map's length.
+    pub fn insert_at(&mut self, index: usize, key: TKey, value: TValue) {
+        assert!(index <= self.len(), "insertion index (is {}) should be <= len (is {})", index, self.len());
+        let index = match self.position(&key) {
+            Some(position) if position < index => index - 1,
+            _ => index
+        };
+        self.remove(&key);
+        self.split_entries();
+        let mut single_entry = HashMap::new();
+        single_entry.insert(key, value);
+        self.entries.insert(index, single_entry);
+    }
+
+    /// Inserts the specified entry before the entry with the specified key. Returns false if there is no entry with such key
+    pub fn insert_before(&mut self, before: &TKey, key: TKey, value: TValue) -> bool {
+        if before == &key || !self.contains_key(before) {
+            return false;
+        }
+        self.remove(&key);
+        match self.position(before) {
+            Some(index) => {
+                self.insert_at(index, key, value);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Inserts the specified entry after the entry with the specified key. Returns false if there is no entry with such key
+    pub fn insert_after(&mut self, after: &TKey, key: TKey, value: TValue) -> bool {
+        if after == &key || !self.contains_key(after) {
+            return false;
+        }
+        self.remove(&key);
+        match self.position(after) {
+            Some(index) => {
+                self.insert_at(index + 1, key, value);
+                true
+            },
+            None => false
+        }
+    }
+
+    /// Removes the entry with the specified key, and returns its value, if any
+    pub fn remove(&mut self, key: &TKey) -> Option<TValue> {
+        let index = self.entries.iter().position(|entry| entry.contains_key(key))?;
+        let value = self.entries[index].remove(key);
+        if self.entries[index].is_empty() {
+            self.entries.remove(index);
+        }
+        value
+    }
+
+    /// Renames the entry with the specified key, preserving its position. Returns false if there is no entry with such key, or if the new key is already in use
+    pub fn rename(&mut self, key: &TKey, new_key: TKey) -> bool {
+        if key == &new_key {
+            return self.contains_key(key);
+        }
+        if self.contains_key(&new_key) {
+            return false;
+        }
+        match self.entries.iter_mut().find(|entry| entry.contains_key(key)) {
+            Some(entry) => {
+                if let Some(value) = entry.remove(key) {
+                    entry.insert(new_key, value);
+                }
+                true
+            },
+            None => false
+        }
+    }
+
+    // Splits entries that define more than one key, so that each entry of the map defines exactly one key
+    fn split_entries(&mut self) {
+        if self.entries.iter().all(|entry| entry.len() <= 1) {
+            return;
+        }
+        self.entries = std::mem::take(&mut self.entries)
+            .into_iter()
+            .flat_map(|entry| entry.into_iter().map(|(key, value)| HashMap::from([(key, value)])))
+            .collect();
+    }
+
+}
+
+impl<TKey, TValue> IntoIterator for Map<TKey, TValue>
+where
+    TKey: Eq + Hash + Clone + PartialEq,
+    TValue: Clone + PartialEq,
+{
+    type Item = (TKey, TValue);
+    type IntoIter = IntoIter<TKey, TValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter().flat_map(HashMap::into_iter as fn(HashMap<TKey, TValue>) -> hash_map::IntoIter<TKey, TValue>)
+    }
+}
+
+impl<'a, TKey, TValue> IntoIterator for &'a Map<TKey, TValue>
+where
+    TKey: Eq + Hash + Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+    TValue: Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+{
+    type Item = (&'a TKey, &'a TValue);
+    type IntoIter = Iter<'a, TKey, TValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, TKey, TValue> IntoIterator for &'a mut Map<TKey, TValue>
+where
+    TKey: Eq + Hash + Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+    TValue: Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+{
+    type Item = (&'a TKey, &'a mut TValue);
+    type IntoIter = IterMut<'a, TKey, TValue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<TKey, TValue> FromIterator<(TKey, TValue)> for Map<TKey, TValue>
+where
+    TKey: Eq + Hash + Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+    TValue: Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+{
+    fn from_iter<I: IntoIterator<Item = (TKey, TValue)>>(iter: I) -> Self {
+        let mut map = Map::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<TKey, TValue> Extend<(TKey, TValue)> for Map<TKey, TValue>
+where
+    TKey: Eq + Hash + Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+    TValue: Clone + Serialize + for<'d> Deserialize<'d> + PartialEq,
+{
+    fn extend<I: IntoIterator<Item = (TKey, TValue)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.add(key, value);
+        }
+    }
+}
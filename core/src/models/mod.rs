@@ -1,12 +1,17 @@
+#[cfg(feature = "agents")]
+pub mod agent;
 pub mod authentication;
+pub mod call;
 pub mod catalog;
 pub mod duration;
+pub mod enumeration;
 pub mod error;
 pub mod event;
 pub mod extension;
 pub mod input;
 pub mod map;
 pub mod output;
+pub mod reference;
 pub mod resource;
 pub mod retry;
 pub mod schema;
@@ -0,0 +1,329 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+use crate::models::map::*;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::pointer::{escape_json_pointer_segment, unescape_json_pointer_segment};
+use crate::services::visitor::*;
+
+/// Represents a reference to a task, in the form of a JSON pointer to its definition, such as '/do/1/processOrder/for/do/0/charge'
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TaskReference{
+    pointer: String,
+    segments: Vec<String>
+}
+impl TaskReference{
+
+    /// Parses the specified JSON pointer into a new TaskReference
+    pub fn parse(pointer: &str) -> Result<Self, TaskReferenceError>{
+        let error = |message: &str| TaskReferenceError::new(pointer, message);
+        let Some(body) = pointer.strip_prefix('/') else {
+            return Err(error("the reference must start with '/'"));
+        };
+        let mut segments = Vec::new();
+        for segment in body.split('/'){
+            if segment.is_empty(){
+                return Err(error("the reference must not contain empty segments"));
+            }
+            let mut characters = segment.chars();
+            while let Some(character) = characters.next(){
+                if character == '~' && !matches!(characters.next(), Some('0') | Some('1')){
+                    return Err(error("the character '~' must be escaped as '~0'"));
+                }
+            }
+            segments.push(unescape_json_pointer_segment(segment));
+        }
+        Ok(Self {
+            pointer: pointer.to_string(),
+            segments
+        })
+    }
+
+    /// Initializes a new TaskReference from the specified unescaped segments
+    pub fn from_segments<S: AsRef<str>>(segments: &[S]) -> Self{
+        let segments: Vec<String> = segments.iter().map(|segment| segment.as_ref().to_string()).collect();
+        let pointer = segments.iter().map(|segment| format!("/{}", escape_json_pointer_segment(segment))).collect();
+        Self {
+            pointer,
+            segments
+        }
+    }
+
+    /// Gets the JSON pointer the reference is made of
+    pub fn as_str(&self) -> &str{
+        &self.pointer
+    }
+
+    /// Gets the unescaped segments of the reference
+    pub fn segments(&self) -> &[String]{
+        &self.segments
+    }
+
+    /// Gets the name of the referenced task, which is the last segment of the reference
+    pub fn name(&self) -> &str{
+        self.segments.last().map(String::as_str).unwrap_or_default()
+    }
+
+    /// Gets a reference to the specified task, if it is defined by the specified workflow definition
+    ///
+    /// The task is matched by identity, not by value, so it must be borrowed from the workflow definition itself.
+    pub fn of(task: &TaskDefinition, workflow: &WorkflowDefinition) -> Option<Self>{
+        let mut finder = TaskReferenceFinder { task, reference: None };
+        walk(workflow, &mut finder);
+        finder.reference
+    }
+
+    /// Resolves the task referenced in the specified workflow definition, if any
+    ///
+    /// The subtasks of a 'for' task can be referenced either as '{task}/do/...' or, as runtimes do, as '{task}/for/do/...'.
+    /// Tasks defined by the arguments of a call, such as the 'with/subscription/foreach/do' tasks of AsyncAPI calls, are deserialized from them, and are therefore returned owned.
+    pub fn resolve<'a>(&self, workflow: &'a WorkflowDefinition) -> Option<Cow<'a, TaskDefinition>>{
+        let segments: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+        let (task, rest) = match segments.as_slice(){
+            ["use", "functions", name, rest @ ..] => (workflow.use_.as_ref()?.functions.as_ref()?.get(*name)?, rest),
+            ["use", "extensions", index, name, list, rest @ ..] => {
+                let extension = workflow.use_.as_ref()?.extensions.as_ref()?.entries.get(parse_index(index)?)?.get(*name)?;
+                let tasks = match *list{
+                    "before" => extension.before.as_ref()?,
+                    "after" => extension.after.as_ref()?,
+                    _ => return None
+                };
+                resolve_in_list(tasks, rest, &mut Vec::new())?
+            },
+            ["do", rest @ ..] => resolve_in_list(&workflow.do_, rest, &mut Vec::new())?,
+            _ => return None
+        };
+        resolve_in_task(task, rest, &mut Vec::new())
+    }
+
+    /// Resolves the task referenced in the specified workflow definition, if any, for modification
+    ///
+    /// Tasks defined by the arguments of a call are deserialized from them, and written back into them once the returned TaskDefinitionMut is dropped.
+    pub fn resolve_mut<'a>(&self, workflow: &'a mut WorkflowDefinition) -> Option<TaskDefinitionMut<'a>>{
+        let segments: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+        let (task, rest) = match segments.as_slice(){
+            ["use", "functions", name, rest @ ..] => (workflow.use_.as_mut()?.functions.as_mut()?.get_mut(*name)?, rest),
+            ["use", "extensions", index, name, list, rest @ ..] => {
+                let extension = workflow.use_.as_mut()?.extensions.as_mut()?.entries.get_mut(parse_index(index)?)?.get_mut(*name)?;
+                let tasks = match *list{
+                    "before" => extension.before.as_mut()?,
+                    "after" => extension.after.as_mut()?,
+                    _ => return None
+                };
+                resolve_in_list_mut(tasks, rest)?
+            },
+            ["do", rest @ ..] => resolve_in_list_mut(&mut workflow.do_, rest)?,
+            _ => return None
+        };
+        resolve_in_task_mut(task, rest)
+    }
+
+}
+impl FromStr for TaskReference {
+    type Err = TaskReferenceError;
+    fn from_str(pointer: &str) -> Result<Self, Self::Err> {
+        Self::parse(pointer)
+    }
+}
+impl TryFrom<String> for TaskReference {
+    type Error = TaskReferenceError;
+    fn try_from(pointer: String) -> Result<Self, Self::Error> {
+        Self::parse(&pointer)
+    }
+}
+impl From<TaskReference> for String {
+    fn from(reference: TaskReference) -> Self {
+        reference.pointer
+    }
+}
+impl fmt::Display for TaskReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pointer)
+    }
+}
+
+/// Represents a task resolved for modification
+///
+/// The task is either borrowed from the workflow definition or, if it is defined by the arguments of a call, deserialized from them and written back into them when dropped.
+pub struct TaskDefinitionMut<'a>{
+    task: ResolvedTaskMut<'a>
+}
+impl<'a> TaskDefinitionMut<'a>{
+
+    // Initializes a new TaskDefinitionMut for the specified task, borrowed from the workflow definition
+    fn borrowed(task: &'a mut TaskDefinition) -> Self{
+        Self { task: ResolvedTaskMut::Borrowed(task) }
+    }
+
+}
+impl Deref for TaskDefinitionMut<'_>{
+    type Target = TaskDefinition;
+    fn deref(&self) -> &TaskDefinition{
+        match &self.task{
+            ResolvedTaskMut::Borrowed(task) => task,
+            ResolvedTaskMut::Embedded { task, .. } => task
+        }
+    }
+}
+impl DerefMut for TaskDefinitionMut<'_>{
+    fn deref_mut(&mut self) -> &mut TaskDefinition{
+        match &mut self.task{
+            ResolvedTaskMut::Borrowed(task) => task,
+            ResolvedTaskMut::Embedded { task, .. } => task
+        }
+    }
+}
+impl Drop for TaskDefinitionMut<'_>{
+    fn drop(&mut self){
+        if let ResolvedTaskMut::Embedded { task, target } = &mut self.task{
+            if let Ok(value) = serde_json::to_value(&**task){
+                if **target != value{
+                    **target = value;
+                }
+            }
+        }
+    }
+}
+
+// Enumerates the ways a task can be resolved for modification
+enum ResolvedTaskMut<'a>{
+    // The task is borrowed from the workflow definition
+    Borrowed(&'a mut TaskDefinition),
+    // The task has been deserialized from the specified value of the arguments of a call
+    Embedded { task: Box<TaskDefinition>, target: &'a mut Value }
+}
+
+/// Represents the error returned when parsing an invalid task reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskReferenceError{
+
+    /// Gets/sets the reference that could not be parsed
+    pub reference: String,
+
+    /// Gets/sets a human-readable message that describes the error
+    pub message: String
+
+}
+impl TaskReferenceError{
+
+    /// Initializes a new TaskReferenceError
+    pub fn new(reference: &str, message: &str) -> Self{
+        Self {
+            reference: reference.to_string(),
+            message: message.to_string()
+        }
+    }
+
+}
+impl fmt::Display for TaskReferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid task reference '{}': {}", self.reference, self.message)
+    }
+}
+impl std::error::Error for TaskReferenceError {}
+
+// Represents the visitor used to find the reference of a task
+struct TaskReferenceFinder<'a>{
+    task: &'a TaskDefinition,
+    reference: Option<TaskReference>
+}
+impl Visitor for TaskReferenceFinder<'_>{
+
+    fn visit_task(&mut self, context: &TaskContext, task: &TaskDefinition) -> bool{
+        if self.reference.is_some(){
+            return false;
+        }
+        if std::ptr::eq(self.task, task){
+            self.reference = TaskReference::parse(&context.path).ok();
+            return false;
+        }
+        true
+    }
+
+}
+
+// Parses the specified JSON pointer array index
+fn parse_index(segment: &str) -> Option<usize>{
+    if segment.is_empty() || !segment.bytes().all(|byte| byte.is_ascii_digit()) || (segment.len() > 1 && segment.starts_with('0')){
+        return None;
+    }
+    segment.parse().ok()
+}
+
+// Resolves the task referenced by the leading '{index}/{name}' segments in the specified task list, and appends those segments to the specified canonical path
+fn resolve_in_list<'a, 's>(tasks: &'a Map<String, TaskDefinition>, segments: &'s [&'s str], canonical: &mut Vec<String>) -> Option<(&'a TaskDefinition, &'s [&'s str])>{
+    let [index, name, rest @ ..] = segments else {
+        return None;
+    };
+    let task = tasks.entries.get(parse_index(index)?)?.get(*name)?;
+    canonical.extend([index.to_string(), name.to_string()]);
+    Some((task, rest))
+}
+
+// Resolves the task referenced by the specified segments, relative to the specified task, and appends the canonical form of those segments, as serialized, to the specified path
+fn resolve_in_task<'a>(task: &'a TaskDefinition, segments: &[&str], canonical: &mut Vec<String>) -> Option<Cow<'a, TaskDefinition>>{
+    if segments.is_empty(){
+        return Some(Cow::Borrowed(task));
+    }
+    let (tasks, container, rest): (&Map<String, TaskDefinition>, &[&str], &[&str]) = match (task, segments){
+        (TaskDefinition::Call(call), ["with", "subscription", "foreach", "do", rest @ ..]) => {
+            let tasks = subscription_tasks(call)?;
+            canonical.extend(["with", "subscription", "foreach", "do"].map(String::from));
+            let (subtask, rest) = resolve_in_list(&tasks, rest, canonical)?;
+            return resolve_in_task(subtask, rest, canonical).map(|task| Cow::Owned(task.into_owned()));
+        },
+        (TaskDefinition::Do(do_), ["do", rest @ ..]) => (&do_.do_, &["do"], rest),
+        (TaskDefinition::For(for_), ["do", rest @ ..] | ["for", "do", rest @ ..]) => (&for_.do_, &["do"], rest),
+        (TaskDefinition::Fork(fork), ["fork", "branches", rest @ ..]) => (&fork.fork.branches, &["fork", "branches"], rest),
+        (TaskDefinition::Listen(listen), ["foreach", "do", rest @ ..]) => (listen.foreach.as_ref()?.do_.as_ref()?, &["foreach", "do"], rest),
+        (TaskDefinition::Try(try_), ["try", rest @ ..]) => (&try_.try_, &["try"], rest),
+        (TaskDefinition::Try(try_), ["catch", "do", rest @ ..]) => (try_.catch.do_.as_ref()?, &["catch", "do"], rest),
+        _ => return None
+    };
+    canonical.extend(container.iter().map(|segment| segment.to_string()));
+    let (subtask, rest) = resolve_in_list(tasks, rest, canonical)?;
+    resolve_in_task(subtask, rest, canonical)
+}
+
+// Resolves the task referenced by the leading '{index}/{name}' segments in the specified task list, for modification
+fn resolve_in_list_mut<'a, 's>(tasks: &'a mut Map<String, TaskDefinition>, segments: &'s [&'s str]) -> Option<(&'a mut TaskDefinition, &'s [&'s str])>{
+    let [index, name, rest @ ..] = segments else {
+        return None;
+    };
+    let task = tasks.entries.get_mut(parse_index(index)?)?.get_mut(*name)?;
+    Some((task, rest))
+}
+
+// Resolves the task referenced by the specified segments, relative to the specified task, for modification
+fn resolve_in_task_mut<'a>(task: &'a mut TaskDefinition, segments: &[&str]) -> Option<TaskDefinitionMut<'a>>{
+    if segments.is_empty(){
+        return Some(TaskDefinitionMut::borrowed(task));
+    }
+    let (subtask, rest) = match (task, segments){
+        (TaskDefinition::Call(call), ["with", "subscription", "foreach", "do", rest @ ..]) => return resolve_in_subscription_mut(call, rest),
+        (TaskDefinition::Do(do_), ["do", rest @ ..]) => resolve_in_list_mut(&mut do_.do_, rest)?,
+        (TaskDefinition::For(for_), ["do", rest @ ..] | ["for", "do", rest @ ..]) => resolve_in_list_mut(&mut for_.do_, rest)?,
+        (TaskDefinition::Fork(fork), ["fork", "branches", rest @ ..]) => resolve_in_list_mut(&mut fork.fork.branches, rest)?,
+        (TaskDefinition::Listen(listen), ["foreach", "do", rest @ ..]) => resolve_in_list_mut(listen.foreach.as_mut()?.do_.as_mut()?, rest)?,
+        (TaskDefinition::Try(try_), ["try", rest @ ..]) => resolve_in_list_mut(&mut try_.try_, rest)?,
+        (TaskDefinition::Try(try_), ["catch", "do", rest @ ..]) => resolve_in_list_mut(try_.catch.do_.as_mut()?, rest)?,
+        _ => return None
+    };
+    resolve_in_task_mut(subtask, rest)
+}
+
+// Resolves the task referenced by the specified segments in the subscription tasks of the specified AsyncAPI call, for modification
+fn resolve_in_subscription_mut<'a>(call: &'a mut CallTaskDefinition, segments: &[&str]) -> Option<TaskDefinitionMut<'a>>{
+    let tasks = subscription_tasks(call)?;
+    let mut canonical = Vec::new();
+    let (subtask, rest) = resolve_in_list(&tasks, segments, &mut canonical)?;
+    let task = Box::new(resolve_in_task(subtask, rest, &mut canonical)?.into_owned());
+    let pointer: String = canonical.iter().map(|segment| format!("/{}", escape_json_pointer_segment(segment))).collect();
+    let target = call.with.as_mut()?.get_mut("subscription")?.pointer_mut(&format!("/foreach/do{}", pointer))?;
+    Some(TaskDefinitionMut { task: ResolvedTaskMut::Embedded { task, target } })
+}
@@ -1,5 +1,6 @@
 use serde_derive::{Deserialize, Serialize};
 use crate::models::duration::*;
+use crate::services::retry::*;
 
 /// Represents the definition of a retry policy
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
@@ -29,6 +30,19 @@ pub struct RetryPolicyDefinition{
     #[serde(rename = "jitter", skip_serializing_if = "Option::is_none")]
     pub jitter: Option<JitterDefinition>
 
+}
+impl RetryPolicyDefinition{
+
+    /// Gets an iterator over the delays to wait before each retry attempt, until a limit of the policy is hit
+    pub fn delays(&self) -> RetryDelays<'_>{
+        RetryDelays::new(self)
+    }
+
+    /// Gets an iterator over the delays to wait before each retry attempt, using the specified source of randomness to compute jitter
+    pub fn delays_with<R: RandomSource>(&self, random: R) -> RetryDelays<'_, R>{
+        RetryDelays::with_random(self, random)
+    }
+
 }
 
 /// Represents the configuration of the limits of a retry policy
@@ -1,21 +1,76 @@
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
+use std::fmt;
+use crate::models::enumeration::*;
 use crate::models::resource::*;
 
 /// Provvides the default schema format
-fn default_schema_format() -> String{
-    SchemaFormat::JSON.to_string()
+fn default_schema_format() -> VersionedSchemaFormat{
+    VersionedSchemaFormat::new(SchemaFormat::Json, None)
 }
 
-/// Enumerates all supported schema formats
-pub struct SchemaFormat;
-impl SchemaFormat {
-    /// Gets the Avro schema format
-    pub const AVRO: &'static str = "avro";
-    /// Gets the JSON schema format
-    pub const JSON: &'static str = "json";
-    /// Gets the XML schema format
-    pub const XML: &'static str = "xml";
+string_enum! {
+    /// Enumerates all supported schema formats
+    pub extensible enum SchemaFormat {
+        /// Indicates the Avro schema format
+        Avro => "avro",
+        /// Indicates the JSON schema format
+        Json => "json",
+        /// Indicates the XML schema format
+        Xml => "xml"
+    }
+}
+
+/// Represents the format of a schema, along with its optional version, serialized as `{format}:{version}`
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct VersionedSchemaFormat{
+
+    /// Gets/sets the schema's format
+    pub format: SchemaFormat,
+
+    /// Gets/sets the version, if any, of the schema's format
+    pub version: Option<String>
+
+}
+impl VersionedSchemaFormat{
+
+    /// Initializes a new VersionedSchemaFormat
+    pub fn new(format: SchemaFormat, version: Option<String>) -> Self{
+        Self { format, version }
+    }
+
+}
+impl Default for VersionedSchemaFormat{
+    fn default() -> Self{
+        default_schema_format()
+    }
+}
+impl From<SchemaFormat> for VersionedSchemaFormat{
+    fn from(format: SchemaFormat) -> Self{
+        Self::new(format, None)
+    }
+}
+impl From<String> for VersionedSchemaFormat{
+    fn from(value: String) -> Self{
+        match value.split_once(':'){
+            Some((format, version)) => Self::new(SchemaFormat::from(format.to_string()), Some(version.to_string())),
+            None => Self::new(SchemaFormat::from(value), None)
+        }
+    }
+}
+impl From<VersionedSchemaFormat> for String{
+    fn from(value: VersionedSchemaFormat) -> Self{
+        value.to_string()
+    }
+}
+impl fmt::Display for VersionedSchemaFormat{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result{
+        match &self.version{
+            Some(version) => write!(f, "{}:{}", self.format, version),
+            None => write!(f, "{}", self.format)
+        }
+    }
 }
 
 /// Represents the definition of a schema
@@ -24,7 +79,7 @@ pub struct SchemaDefinition{
 
     /// Gets/sets the schema's format. Defaults to 'json'. The (optional) version of the format can be set using `{format}:{version}`.
     #[serde(rename = "format", default = "default_schema_format")]
-    pub format : String,
+    pub format : VersionedSchemaFormat,
 
     /// Gets/sets the schema's external resource, if any. Required if <see cref="Document"/> has not been set.
     #[serde(rename = "resource", skip_serializing_if = "Option::is_none")]
@@ -34,4 +89,4 @@ pub struct SchemaDefinition{
     #[serde(rename = "document", skip_serializing_if = "Option::is_none")]
     pub document : Option<Value>
 
-}
\ No newline at end of file
+}
@@ -1,57 +1,146 @@
 use std::collections::HashMap;
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
+#[cfg(feature = "agents")]
+use crate::models::agent::*;
+use crate::models::call::*;
 use crate::models::duration::*;
+use crate::models::enumeration::*;
 use crate::models::event::*;
 use crate::models::error::*;
 use crate::models::map::*;
 use crate::models::input::*;
 use crate::models::resource::*;
 use crate::models::retry::*;
+use crate::services::catch;
+use crate::services::expression::*;
 
 use super::output::OutputDataModelDefinition;
 use super::timeout::OneOfTimeoutDefinitionOrReference;
 
-/// Enumerates all supported task types
-pub struct TaskType;
-impl TaskType {
-    /// Gets the type of a 'call' task
-    pub const CALL: &'static str = "call";
-    /// Gets the type of a 'do' task
-    pub const DO: &'static str = "do";
-    /// Gets the type of a 'emit' task
-    pub const EMIT: &'static str = "emit";
-    /// Gets the type of a 'for' task
-    pub const FOR: &'static str = "for";
-    /// Gets the type of a 'fork' task
-    pub const FORK: &'static str = "fork";
-    /// Gets the type of a 'listen' task
-    pub const LISTEN: &'static str = "listen";
-    /// Gets the type of a 'raise' task
-    pub const RAISE: &'static str = "raise";
-    /// Gets the type of a 'run' task
-    pub const RUN: &'static str = "run";
-    /// Gets the type of a 'set' task
-    pub const SET: &'static str = "set";
-    /// Gets the type of a 'switch' task
-    pub const SWITCH: &'static str = "switch";
-    /// Gets the type of a 'try' task
-    pub const TRY: &'static str = "try";
-    /// Gets the type of a 'wait' task
-    pub const WAIT: &'static str = "wait";
-}
-
-/// Enumerates all supported process types
-pub struct ProcessType;
-impl ProcessType {
-    /// Gets the type of a 'container' process
-    pub const CONTAINER: &'static str = "container";
-    /// Gets the type of a 'shell' process
-    pub const SCRIPT: &'static str = "script";
-    /// Gets the type of a 'shell' process
-    pub const SHELL: &'static str = "shell";
-    /// Gets the type of a 'workflow' process
-    pub const WORKFLOW: &'static str = "workflow";
+string_enum! {
+    /// Enumerates all supported task types
+    pub enum TaskType {
+        /// Indicates a 'call' task
+        Call => "call",
+        /// Indicates a 'do' task
+        Do => "do",
+        /// Indicates an 'emit' task
+        Emit => "emit",
+        /// Indicates a 'for' task
+        For => "for",
+        /// Indicates a 'fork' task
+        Fork => "fork",
+        /// Indicates a 'listen' task
+        Listen => "listen",
+        /// Indicates a 'raise' task
+        Raise => "raise",
+        /// Indicates a 'run' task
+        Run => "run",
+        /// Indicates a 'set' task
+        Set => "set",
+        /// Indicates a 'switch' task
+        Switch => "switch",
+        /// Indicates a 'try' task
+        Try => "try",
+        /// Indicates a 'wait' task
+        Wait => "wait"
+    }
+}
+
+string_enum! {
+    /// Enumerates all supported process types
+    pub enum ProcessType {
+        /// Indicates a 'container' process
+        Container => "container",
+        /// Indicates a 'script' process
+        Script => "script",
+        /// Indicates a 'shell' process
+        Shell => "shell",
+        /// Indicates a 'workflow' process
+        Workflow => "workflow"
+    }
+}
+
+/// Enumerates all supported flow directives
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum FlowDirective{
+    /// Instructs the workflow to continue with the next task, if any
+    Continue,
+    /// Instructs the workflow to exit the current composite task, if any
+    Exit,
+    /// Instructs the workflow to end gracefully
+    End,
+    /// Instructs the workflow to transition to the task with the specified name
+    Goto(String)
+}
+impl FlowDirective {
+
+    /// Gets the 'continue' flow directive
+    pub const CONTINUE: &'static str = "continue";
+    /// Gets the 'exit' flow directive
+    pub const EXIT: &'static str = "exit";
+    /// Gets the 'end' flow directive
+    pub const END: &'static str = "end";
+
+    /// Gets the string representation of the flow directive
+    pub fn as_str(&self) -> &str{
+        match self {
+            FlowDirective::Continue => Self::CONTINUE,
+            FlowDirective::Exit => Self::EXIT,
+            FlowDirective::End => Self::END,
+            FlowDirective::Goto(task) => task
+        }
+    }
+
+    /// Gets the name of the task to transition to, if the flow directive is not a reserved one
+    pub fn task_name(&self) -> Option<&str>{
+        match self {
+            FlowDirective::Goto(task) => Some(task),
+            _ => None
+        }
+    }
+
+    /// Determines whether or not the flow directive is a reserved one
+    pub fn is_reserved(&self) -> bool{
+        !matches!(self, FlowDirective::Goto(_))
+    }
+
+}
+impl std::str::FromStr for FlowDirective {
+    type Err = std::convert::Infallible;
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(FlowDirective::from(value))
+    }
+}
+impl From<&str> for FlowDirective {
+    fn from(value: &str) -> Self {
+        match value {
+            Self::CONTINUE => FlowDirective::Continue,
+            Self::EXIT => FlowDirective::Exit,
+            Self::END => FlowDirective::End,
+            _ => FlowDirective::Goto(value.to_string())
+        }
+    }
+}
+impl From<String> for FlowDirective {
+    fn from(value: String) -> Self {
+        FlowDirective::from(value.as_str())
+    }
+}
+impl From<FlowDirective> for String {
+    fn from(value: FlowDirective) -> Self {
+        match value {
+            FlowDirective::Goto(task) => task,
+            directive => directive.as_str().to_string()
+        }
+    }
+}
+impl std::fmt::Display for FlowDirective {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Represents a value that can be any of the supported task definitions
@@ -83,98 +172,122 @@ pub enum TaskDefinition{
     /// Variant holding the definition of a 'wait' task
     Wait(WaitTaskDefinition)
 }
+impl TaskDefinition {
 
-// Custom deserializer to handle For vs Do ambiguity
-impl<'de> serde::Deserialize<'de> for TaskDefinition {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        let value = Value::deserialize(deserializer)?;
-
-        // Check for 'for' field first - if present, it's a For task
-        if value.get("for").is_some() {
-            return ForTaskDefinition::deserialize(value)
-                .map(TaskDefinition::For)
-                .map_err(serde::de::Error::custom);
-        }
-
-        // Try other variants in priority order
-        if value.get("call").is_some() {
-            return CallTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Call)
-                .map_err(serde::de::Error::custom);
-        }
-
-        if value.get("set").is_some() {
-            return SetTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Set)
-                .map_err(serde::de::Error::custom);
-        }
-
-        if value.get("fork").is_some() {
-            return ForkTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Fork)
-                .map_err(serde::de::Error::custom);
-        }
-
-        if value.get("run").is_some() {
-            return RunTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Run)
-                .map_err(serde::de::Error::custom);
-        }
-
-        if value.get("switch").is_some() {
-            return SwitchTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Switch)
-                .map_err(serde::de::Error::custom);
-        }
-
-        if value.get("try").is_some() {
-            return TryTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Try)
-                .map_err(serde::de::Error::custom);
-        }
-
-        if value.get("emit").is_some() {
-            return EmitTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Emit)
-                .map_err(serde::de::Error::custom);
+    /// Gets the task's type
+    pub fn task_type(&self) -> TaskType{
+        match self {
+            TaskDefinition::Call(task) => task.task_type(),
+            TaskDefinition::Do(task) => task.task_type(),
+            TaskDefinition::Emit(task) => task.task_type(),
+            TaskDefinition::For(task) => task.task_type(),
+            TaskDefinition::Fork(task) => task.task_type(),
+            TaskDefinition::Listen(task) => task.task_type(),
+            TaskDefinition::Raise(task) => task.task_type(),
+            TaskDefinition::Run(task) => task.task_type(),
+            TaskDefinition::Set(task) => task.task_type(),
+            TaskDefinition::Switch(task) => task.task_type(),
+            TaskDefinition::Try(task) => task.task_type(),
+            TaskDefinition::Wait(task) => task.task_type()
         }
+    }
 
-        if value.get("raise").is_some() {
-            return RaiseTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Raise)
-                .map_err(serde::de::Error::custom);
+    /// Gets the fields common to all tasks
+    pub fn common(&self) -> &TaskDefinitionFields{
+        match self {
+            TaskDefinition::Call(task) => &task.common,
+            TaskDefinition::Do(task) => &task.common,
+            TaskDefinition::Emit(task) => &task.common,
+            TaskDefinition::For(task) => &task.common,
+            TaskDefinition::Fork(task) => &task.common,
+            TaskDefinition::Listen(task) => &task.common,
+            TaskDefinition::Raise(task) => &task.common,
+            TaskDefinition::Run(task) => &task.common,
+            TaskDefinition::Set(task) => &task.common,
+            TaskDefinition::Switch(task) => &task.common,
+            TaskDefinition::Try(task) => &task.common,
+            TaskDefinition::Wait(task) => &task.common
         }
+    }
 
-        if value.get("wait").is_some() {
-            return WaitTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Wait)
-                .map_err(serde::de::Error::custom);
+    /// Gets a mutable reference to the fields common to all tasks
+    pub fn common_mut(&mut self) -> &mut TaskDefinitionFields{
+        match self {
+            TaskDefinition::Call(task) => &mut task.common,
+            TaskDefinition::Do(task) => &mut task.common,
+            TaskDefinition::Emit(task) => &mut task.common,
+            TaskDefinition::For(task) => &mut task.common,
+            TaskDefinition::Fork(task) => &mut task.common,
+            TaskDefinition::Listen(task) => &mut task.common,
+            TaskDefinition::Raise(task) => &mut task.common,
+            TaskDefinition::Run(task) => &mut task.common,
+            TaskDefinition::Set(task) => &mut task.common,
+            TaskDefinition::Switch(task) => &mut task.common,
+            TaskDefinition::Try(task) => &mut task.common,
+            TaskDefinition::Wait(task) => &mut task.common
         }
+    }
 
-        if value.get("listen").is_some() {
-            return ListenTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Listen)
-                .map_err(serde::de::Error::custom);
-        }
+}
 
-        // If we get here and there's a 'do' field, it's a Do task (not a For task)
-        if value.get("do").is_some() {
-            return DoTaskDefinition::deserialize(value)
-                .map(TaskDefinition::Do)
-                .map_err(serde::de::Error::custom);
+// Custom deserializer to handle For vs Do ambiguity
+impl<'de> serde::Deserialize<'de> for TaskDefinition {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let task_type = match TaskDefinition::detect_type(&value) {
+            Some(task_type) => task_type,
+            None => return Err(serde::de::Error::custom(format!("unknown task type, expected one of: {}", TaskType::VALUES.join(", "))))
+        };
+        match task_type {
+            TaskType::Call => CallTaskDefinition::deserialize(value).map(TaskDefinition::Call),
+            TaskType::Do => DoTaskDefinition::deserialize(value).map(TaskDefinition::Do),
+            TaskType::Emit => EmitTaskDefinition::deserialize(value).map(TaskDefinition::Emit),
+            TaskType::For => ForTaskDefinition::deserialize(value).map(TaskDefinition::For),
+            TaskType::Fork => ForkTaskDefinition::deserialize(value).map(TaskDefinition::Fork),
+            TaskType::Listen => ListenTaskDefinition::deserialize(value).map(TaskDefinition::Listen),
+            TaskType::Raise => RaiseTaskDefinition::deserialize(value).map(TaskDefinition::Raise),
+            TaskType::Run => RunTaskDefinition::deserialize(value).map(TaskDefinition::Run),
+            TaskType::Set => SetTaskDefinition::deserialize(value).map(TaskDefinition::Set),
+            TaskType::Switch => SwitchTaskDefinition::deserialize(value).map(TaskDefinition::Switch),
+            TaskType::Try => TryTaskDefinition::deserialize(value).map(TaskDefinition::Try),
+            TaskType::Wait => WaitTaskDefinition::deserialize(value).map(TaskDefinition::Wait)
         }
-
-        Err(serde::de::Error::custom("unknown task type"))
+        .map_err(serde::de::Error::custom)
     }
 }
+impl TaskDefinition {
+
+    /// Detects the type of the task described by the specified value, if any
+    ///
+    /// A task defining a 'for' keyword is always a 'for' task, and a task is a 'do' task only if it defines no other task keyword.
+    pub fn detect_type(value: &Value) -> Option<TaskType>{
+        [
+            TaskType::For,
+            TaskType::Call,
+            TaskType::Set,
+            TaskType::Fork,
+            TaskType::Run,
+            TaskType::Switch,
+            TaskType::Try,
+            TaskType::Emit,
+            TaskType::Raise,
+            TaskType::Wait,
+            TaskType::Listen,
+            TaskType::Do
+        ]
+        .into_iter()
+        .find(|task_type| value.get(task_type.as_str()).is_some())
+    }
+
+}
 
 /// A trait that all task definitions must implement
 pub trait TaskDefinitionBase {
     /// Gets the task's type
-    fn task_type(&self) -> &str;
+    fn task_type(&self) -> TaskType;
 }
 
 /// Holds the fields common to all tasks
@@ -203,7 +316,7 @@ pub struct TaskDefinitionFields{
 
     /// Gets/sets the flow directive to be performed upon completion of the task
     #[serde(rename = "then", skip_serializing_if = "Option::is_none")]
-    pub then: Option<String>,
+    pub then: Option<FlowDirective>,
 
     /// Gets/sets a key/value mapping of additional information associated with the task
     #[serde(rename = "metadata", skip_serializing_if = "Option::is_none")]
@@ -254,11 +367,14 @@ pub struct CallTaskDefinition{
 
 }
 impl TaskDefinitionBase for CallTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::CALL
+    fn task_type(&self) -> TaskType {
+        TaskType::Call
     }
 }
 impl  CallTaskDefinition {
+
+    /// Gets the JSON pointers, relative to the call's arguments, of all the locations where the natively supported functions accept an authentication policy
+    pub const AUTHENTICATION_PATHS: [&'static str; 5] = ["/authentication", "/endpoint/authentication", "/document/endpoint/authentication", "/proto/endpoint/authentication", "/service/authentication"];
     
     /// Initializes a new CalltaskDefinition
     pub fn new(call: &str, with: Option<HashMap<String, Value>>, await_: Option<bool>) -> Self{
@@ -270,6 +386,92 @@ impl  CallTaskDefinition {
         }
     }
 
+    /// Initializes a new CallTaskDefinition used to call the function the specified typed arguments are defined for
+    pub fn from_arguments<T: CallArguments>(arguments: &T) -> Result<Self, CallArgumentsError>{
+        Ok(Self::new(T::FUNCTION.as_str(), Some(arguments.to_arguments()?), None))
+    }
+
+    /// Gets the typed arguments of the call, failing if the task does not call the function they are defined for or if its arguments are invalid
+    pub fn as_arguments<T: CallArguments>(&self) -> Result<T, CallArgumentsError>{
+        if T::FUNCTION != *self.call{
+            return Err(CallArgumentsError::new(T::FUNCTION.as_str(), format!("the task calls the '{}' function", self.call)));
+        }
+        match &self.with {
+            Some(with) => T::from_arguments(with),
+            None => T::from_arguments(&HashMap::new())
+        }
+    }
+
+    /// Gets the authentication policies defined by the call's arguments, along with their JSON pointer relative to the arguments. See [`CallTaskDefinition::AUTHENTICATION_PATHS`]
+    pub fn authentications(&self) -> Vec<(&'static str, &Value)>{
+        let Some(with) = &self.with else { return Vec::new() };
+        Self::AUTHENTICATION_PATHS.iter()
+            .filter_map(|path| {
+                let (name, rest) = split_argument_path(path);
+                let argument = with.get(name)?;
+                let value = if rest.is_empty() { argument } else { argument.pointer(rest)? };
+                value.is_object().then_some((*path, value))
+            })
+            .collect()
+    }
+
+    /// Gets mutable references to the authentication policies defined by the call's arguments, along with their JSON pointer relative to the arguments
+    pub fn authentications_mut(&mut self) -> Vec<(&'static str, &mut Value)>{
+        let Some(with) = &mut self.with else { return Vec::new() };
+        let mut authentications: Vec<(&'static str, &mut Value)> = Vec::new();
+        for (name, argument) in with.iter_mut(){
+            let Some(path) = Self::AUTHENTICATION_PATHS.iter().find(|path| split_argument_path(path).0 == name) else { continue };
+            let rest = split_argument_path(path).1;
+            let value = if rest.is_empty() { Some(argument) } else { argument.pointer_mut(rest) };
+            if let Some(value) = value.filter(|value| value.is_object()){
+                authentications.push((path, value));
+            }
+        }
+        authentications.sort_by_key(|(path, _)| Self::AUTHENTICATION_PATHS.iter().position(|candidate| candidate == path));
+        authentications
+    }
+
+    /// Gets the typed arguments of the 'http' call performed by the task
+    pub fn as_http(&self) -> Result<HttpCallArguments, CallArgumentsError>{
+        self.as_arguments()
+    }
+
+    /// Gets the typed arguments of the 'grpc' call performed by the task
+    pub fn as_grpc(&self) -> Result<GrpcCallArguments, CallArgumentsError>{
+        self.as_arguments()
+    }
+
+    /// Gets the typed arguments of the 'openapi' call performed by the task
+    pub fn as_openapi(&self) -> Result<OpenApiCallArguments, CallArgumentsError>{
+        self.as_arguments()
+    }
+
+    /// Gets the typed arguments of the 'asyncapi' call performed by the task
+    pub fn as_asyncapi(&self) -> Result<AsyncApiCallArguments, CallArgumentsError>{
+        self.as_arguments()
+    }
+
+    /// Gets the typed arguments of the 'a2a' call performed by the task
+    #[cfg(feature = "agents")]
+    pub fn as_a2a(&self) -> Result<A2ACallArguments, CallArgumentsError>{
+        self.as_arguments()
+    }
+
+    /// Gets the typed arguments of the 'mcp' call performed by the task
+    #[cfg(feature = "agents")]
+    pub fn as_mcp(&self) -> Result<McpCallArguments, CallArgumentsError>{
+        self.as_arguments()
+    }
+
+}
+
+// Splits the specified JSON pointer, relative to the arguments of a call, into the name of the argument it targets and the JSON pointer relative to that argument
+fn split_argument_path(path: &str) -> (&str, &str){
+    let path = path.trim_start_matches('/');
+    match path.find('/'){
+        Some(index) => (&path[..index], &path[index..]),
+        None => (path, "")
+    }
 }
 
 /// Represents the configuration of a task that is composed of multiple subtasks to run sequentially
@@ -286,8 +488,8 @@ pub struct DoTaskDefinition{
 
 }
 impl TaskDefinitionBase for DoTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::DO
+    fn task_type(&self) -> TaskType {
+        TaskType::Do
     }
 }
 impl DoTaskDefinition {
@@ -316,8 +518,8 @@ pub struct EmitTaskDefinition{
 
 }
 impl TaskDefinitionBase for EmitTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::EMIT
+    fn task_type(&self) -> TaskType {
+        TaskType::Emit
     }
 }
 impl EmitTaskDefinition {
@@ -371,8 +573,8 @@ pub struct ForTaskDefinition{
 
 }
 impl TaskDefinitionBase for ForTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::FOR
+    fn task_type(&self) -> TaskType {
+        TaskType::For
     }
 }
 impl ForTaskDefinition {
@@ -433,8 +635,8 @@ pub struct ForkTaskDefinition{
 
 }
 impl TaskDefinitionBase for ForkTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::FORK
+    fn task_type(&self) -> TaskType {
+        TaskType::Fork
     }
 }
 impl ForkTaskDefinition {
@@ -487,8 +689,8 @@ pub struct ListenTaskDefinition{
 
 }
 impl TaskDefinitionBase for ListenTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::LISTEN
+    fn task_type(&self) -> TaskType {
+        TaskType::Listen
     }
 }
 impl ListenTaskDefinition {
@@ -538,8 +740,8 @@ pub struct RaiseTaskDefinition{
 
 }
 impl TaskDefinitionBase for RaiseTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::RAISE
+    fn task_type(&self) -> TaskType {
+        TaskType::Raise
     }
 }
 impl RaiseTaskDefinition {
@@ -584,8 +786,8 @@ pub struct RunTaskDefinition{
 
 }
 impl TaskDefinitionBase for RunTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::RUN
+    fn task_type(&self) -> TaskType {
+        TaskType::Run
     }
 }
 impl RunTaskDefinition {
@@ -670,18 +872,18 @@ impl ProcessTypeDefinition {
     }
     
     /// Gets the type of the defined process
-    pub fn get_process_type(&self) -> &str{
+    pub fn get_process_type(&self) -> ProcessType{
         if self.container.is_some(){
-            ProcessType::CONTAINER
+            ProcessType::Container
         }
         else if self.script.is_some(){
-            ProcessType::SCRIPT
+            ProcessType::Script
         }
         else if self.shell.is_some(){
-            ProcessType::SHELL
+            ProcessType::Shell
         }
         else{
-            ProcessType::WORKFLOW
+            ProcessType::Workflow
         }
     }
 
@@ -883,8 +1085,8 @@ pub struct SetTaskDefinition{
 
 }
 impl TaskDefinitionBase for SetTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::SET
+    fn task_type(&self) -> TaskType {
+        TaskType::Set
     }
 }
 impl SetTaskDefinition {
@@ -911,8 +1113,8 @@ pub struct SwitchTaskDefinition{
 
 }
 impl TaskDefinitionBase for SwitchTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::SWITCH
+    fn task_type(&self) -> TaskType {
+        TaskType::Switch
     }
 }
 impl SwitchTaskDefinition {
@@ -935,7 +1137,7 @@ pub struct SwitchCaseDefinition{
 
     /// Gets/sets the transition to perform when the case matches
     #[serde(rename = "then", skip_serializing_if = "Option::is_none")]
-    pub then: Option<String>
+    pub then: Option<FlowDirective>
 
 }
 
@@ -957,8 +1159,8 @@ pub struct TryTaskDefinition{
 
 }
 impl TaskDefinitionBase for TryTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::TRY
+    fn task_type(&self) -> TaskType {
+        TaskType::Try
     }
 }
 impl TryTaskDefinition {
@@ -1002,6 +1204,14 @@ pub struct ErrorCatcherDefinition{
     #[serde(rename = "do", skip_serializing_if = "Option::is_none")]
     pub do_: Option<Map<String, TaskDefinition>>
 
+}
+impl ErrorCatcherDefinition{
+
+    /// Determines whether or not the catcher catches the specified error, raised while processing the specified data, and returns the arguments, with the error bound, to run the catcher with
+    pub fn catches(&self, error: &ErrorDefinition, data: &Value, arguments: &RuntimeExpressionArguments, evaluator: &dyn RuntimeExpressionEvaluator) -> Result<Option<RuntimeExpressionArguments>, RuntimeExpressionError>{
+        catch::catches(self, error, data, arguments, evaluator)
+    }
+
 }
 
 /// Represents the definition an an error filter
@@ -1012,6 +1222,14 @@ pub struct ErrorFilterDefinition{
     #[serde(rename = "with", skip_serializing_if = "Option::is_none")]
     pub with: Option<HashMap<String, Value>>
 
+}
+impl ErrorFilterDefinition{
+
+    /// Determines whether or not the specified error matches the filter
+    pub fn matches(&self, error: &ErrorDefinition) -> bool{
+        catch::error_matches_filter(self, error)
+    }
+
 }
 
 /// Represents the definition of a task used to wait a certain amount of time
@@ -1028,8 +1246,8 @@ pub struct WaitTaskDefinition{
 
 }
 impl TaskDefinitionBase for WaitTaskDefinition {
-    fn task_type(&self) -> &str {
-        TaskType::WAIT
+    fn task_type(&self) -> TaskType {
+        TaskType::Wait
     }
 }
 impl WaitTaskDefinition {
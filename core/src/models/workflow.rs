@@ -1,18 +1,28 @@
 use serde_derive::{Deserialize, Serialize};
 use serde_json::Value;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use crate::models::authentication::*;
 use crate::models::catalog::*;
 use crate::models::duration::*;
+use crate::models::enumeration::*;
 use crate::models::error::*;
 use crate::models::event::*;
 use crate::models::extension::*;
 use crate::models::input::*;
 use crate::models::map::*;
 use crate::models::output::*;
+use crate::models::reference::*;
 use crate::models::retry::*;
 use crate::models::task::*;
 use crate::models::timeout::*;
+use crate::services::diagram;
+use crate::services::expression::{self, RuntimeExpressionEvaluator};
+use crate::services::graph::ControlFlowGraph;
+use crate::services::parsing::{self, DocumentFormat, ParseError};
+use crate::services::schema::{self, SchemaValidationError};
+use crate::services::validation::{self, ValidationDiagnostic};
+use crate::services::visitor::{self, Visitor, VisitorMut};
 
 /// Gets the namespace to use by default for workflow definitions
 pub const DEFAULT_NAMESPACE: &str = "default";
@@ -29,17 +39,35 @@ fn default_dsl_version() -> String {
 }
 
 // Provides the default runtime expression language
-fn default_runtime_expression_language() -> String{
-    RuntimeExpressionLanguage::JQ.to_string()
+fn default_runtime_expression_language() -> RuntimeExpressionLanguage{
+    RuntimeExpressionLanguage::Jq
 }
 
-/// Enumerates all supported runtime expression languages
-pub struct RuntimeExpressionLanguage;
-impl RuntimeExpressionLanguage {
-    /// Gets the 'jq' runtime expression language
-    pub const JQ: &'static str = "jq";
-    /// Gets the 'js' runtime expression language
-    pub const JAVASCRIPT: &'static str = "js";
+string_enum! {
+    /// Enumerates all supported runtime expression languages
+    pub extensible enum RuntimeExpressionLanguage {
+        /// Indicates the 'jq' runtime expression language
+        Jq => "jq",
+        /// Indicates the 'js' runtime expression language
+        JavaScript => "js"
+    }
+}
+impl Default for RuntimeExpressionLanguage {
+    fn default() -> Self {
+        default_runtime_expression_language()
+    }
+}
+
+string_enum! {
+    /// Enumerates all supported runtime expression evaluation modes
+    #[derive(Default)]
+    pub enum RuntimeExpressionEvaluationMode {
+        /// Indicates that runtime expressions must be enclosed in '${' and '}'
+        #[default]
+        Strict => "strict",
+        /// Indicates that runtime expressions may be written without enclosing them in '${' and '}' in fields that only accept runtime expressions
+        Loose => "loose"
+    }
 }
 
 /// Represents the definition of a workflow
@@ -99,6 +127,76 @@ impl WorkflowDefinition {
             metadata: None
         }
     }
+
+    /// Parses the specified YAML document into a new workflow definition
+    pub fn from_yaml_str(document: &str) -> Result<Self, ParseError>{
+        parsing::parse_workflow(document, DocumentFormat::Yaml)
+    }
+
+    /// Parses the specified JSON document into a new workflow definition
+    pub fn from_json_str(document: &str) -> Result<Self, ParseError>{
+        parsing::parse_workflow(document, DocumentFormat::Json)
+    }
+
+    /// Validates the semantics of the workflow definition, such as the references it makes to tasks and reusable components
+    ///
+    /// Returns the diagnostics produced during validation. The workflow is valid if none of them is an error.
+    pub fn validate(&self) -> Vec<ValidationDiagnostic>{
+        validation::validate(self)
+    }
+
+    /// Validates the specified input data against the schema, if any, of the workflow's input
+    pub fn validate_input(&self, input: &Value) -> Result<(), SchemaValidationError>{
+        match &self.input{
+            Some(definition) => schema::validate_input(definition, input),
+            None => Ok(())
+        }
+    }
+
+    /// Checks the syntax of all the runtime expressions of the workflow definition, using the specified evaluator
+    pub fn check_expressions(&self, evaluator: &dyn RuntimeExpressionEvaluator) -> Vec<ValidationDiagnostic>{
+        expression::check_expressions(self, evaluator)
+    }
+
+    /// Resolves the task referenced by the specified JSON pointer, if any
+    pub fn resolve_task(&self, reference: &TaskReference) -> Option<Cow<'_, TaskDefinition>>{
+        reference.resolve(self)
+    }
+
+    /// Resolves the task referenced by the specified JSON pointer, if any, for modification
+    pub fn resolve_task_mut(&mut self, reference: &TaskReference) -> Option<TaskDefinitionMut<'_>>{
+        reference.resolve_mut(self)
+    }
+
+    /// Gets the JSON pointer reference of the specified task, which must be borrowed from the workflow definition. Returns None if the task does not belong to the workflow definition
+    pub fn task_reference_of(&self, task: &TaskDefinition) -> Option<TaskReference>{
+        TaskReference::of(task, self)
+    }
+
+    /// Builds the control-flow graph of the workflow definition
+    pub fn control_flow_graph(&self) -> ControlFlowGraph{
+        ControlFlowGraph::new(self)
+    }
+
+    /// Renders the workflow definition as a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String{
+        diagram::to_mermaid(self)
+    }
+
+    /// Renders the workflow definition as a Graphviz DOT digraph
+    pub fn to_dot(&self) -> String{
+        diagram::to_dot(self)
+    }
+
+    /// Walks all the tasks of the workflow definition with the specified visitor
+    pub fn walk<V: Visitor + ?Sized>(&self, visitor: &mut V){
+        visitor::walk(self, visitor)
+    }
+
+    /// Walks all the tasks of the workflow definition with the specified visitor, which may rewrite them
+    pub fn walk_mut<V: VisitorMut + ?Sized>(&mut self, visitor: &mut V){
+        visitor::walk_mut(self, visitor)
+    }
     
 }
 
@@ -181,11 +279,11 @@ pub struct RuntimeExpressionEvaluationConfiguration{
 
     /// Gets/sets the language used for writing runtime expressions
     #[serde(rename = "language", default = "default_runtime_expression_language")]
-    pub language: String,
+    pub language: RuntimeExpressionLanguage,
 
-    /// Gets/sets the language used for writing runtime expressions
+    /// Gets/sets the mode, if any, used to evaluate runtime expressions. Defaults to 'strict'
     #[serde(rename = "mode", skip_serializing_if = "Option::is_none")]
-    pub mode: Option<String>
+    pub mode: Option<RuntimeExpressionEvaluationMode>
 
 }
 
@@ -207,7 +305,7 @@ pub struct ComponentDefinitionCollection{
 
     /// Gets/sets a list containing the workflow's extensions, if any
     #[serde(rename = "extensions", skip_serializing_if = "Option::is_none")]
-    pub extensions: Option<Vec<HashMap<String, ExtensionDefinition>>>,
+    pub extensions: Option<Map<String, ExtensionDefinition>>,
 
     /// Gets/sets a name/value mapping of the workflow's reusable functions
     #[serde(rename = "functions", skip_serializing_if = "Option::is_none")]
@@ -0,0 +1,222 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::services::schema::*;
+use crate::services::pointer::escape_json_pointer_segment;
+
+/// Validates the specified JSON data against the specified Avro schema
+///
+/// The schema can be set either as a JSON value or as a string containing its JSON representation. Data is expected in its natural JSON form, as produced and consumed by workflows:
+/// unions accept any value that matches one of their types, 'bytes' and 'fixed' values are strings, and record fields that are not declared by the schema are ignored.
+/// Logical types are validated against their underlying type.
+pub fn validate_avro_schema(document: &Value, value: &Value) -> Result<(), SchemaValidationError>{
+    let parsed;
+    let schema = match document{
+        Value::String(text) => match serde_json::from_str::<Value>(text){
+            Ok(value) => {
+                parsed = value;
+                &parsed
+            },
+            Err(_) => document
+        },
+        _ => document
+    };
+    let mut validator = AvroValidator { types: HashMap::new() };
+    validator.register(schema, None)
+        .map_err(|message| SchemaValidationError::new(&format!("invalid Avro schema: {}", message), Vec::new()))?;
+    let violations = validator.validate(schema, value, None, "", "")
+        .map_err(|message| SchemaValidationError::new(&format!("invalid Avro schema: {}", message), Vec::new()))?;
+    if violations.is_empty(){
+        Ok(())
+    }
+    else{
+        Err(SchemaValidationError::new("the data does not conform to its Avro schema", violations))
+    }
+}
+
+// Represents the service used to validate data against an Avro schema
+struct AvroValidator<'a>{
+    types: HashMap<String, (&'a Value, Option<String>)>
+}
+impl<'a> AvroValidator<'a>{
+
+    // Registers the named types defined by the specified schema, in the specified enclosing namespace
+    fn register(&mut self, schema: &'a Value, namespace: Option<&str>) -> Result<(), String>{
+        match schema{
+            Value::Array(types) => {
+                for type_ in types{
+                    self.register(type_, namespace)?;
+                }
+            },
+            Value::Object(properties) => {
+                let type_ = properties.get("type").ok_or("schemas must define a 'type'")?;
+                match type_.as_str(){
+                    Some("record") | Some("error") | Some("enum") | Some("fixed") => {
+                        let name = properties.get("name").and_then(Value::as_str).ok_or("named types must define a 'name'")?;
+                        let (full_name, namespace) = full_name(name, properties.get("namespace").and_then(Value::as_str).or(namespace));
+                        if self.types.insert(full_name.clone(), (schema, namespace.clone())).is_some(){
+                            return Err(format!("the type '{}' is defined more than once", full_name));
+                        }
+                        if type_.as_str() != Some("enum") && type_.as_str() != Some("fixed"){
+                            let fields = properties.get("fields").and_then(Value::as_array).ok_or("records must define 'fields'")?;
+                            for field in fields{
+                                self.register(field.get("type").ok_or("fields must define a 'type'")?, namespace.as_deref())?;
+                            }
+                        }
+                    },
+                    Some("array") => self.register(properties.get("items").ok_or("arrays must define 'items'")?, namespace)?,
+                    Some("map") => self.register(properties.get("values").ok_or("maps must define 'values'")?, namespace)?,
+                    Some(_) => {},
+                    None => self.register(type_, namespace)?
+                }
+            },
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // Validates the specified value against the specified schema, and returns the violations found
+    fn validate(&self, schema: &Value, value: &Value, namespace: Option<&str>, instance_path: &str, schema_path: &str) -> Result<Vec<SchemaViolation>, String>{
+        let violation = |message: String| vec![SchemaViolation {
+            instance_path: instance_path.to_string(),
+            schema_path: schema_path.to_string(),
+            message
+        }];
+        match schema{
+            Value::String(name) => self.validate_type(name, None, value, namespace, instance_path, schema_path),
+            Value::Array(types) => {
+                for (index, type_) in types.iter().enumerate(){
+                    if self.validate(type_, value, namespace, instance_path, &format!("{}/{}", schema_path, index))?.is_empty(){
+                        return Ok(Vec::new());
+                    }
+                }
+                Ok(violation(format!("{} does not match any of the types of the union", value)))
+            },
+            Value::Object(properties) => match properties.get("type"){
+                Some(Value::String(name)) => self.validate_type(name, Some(schema), value, namespace, instance_path, schema_path),
+                Some(type_) => self.validate(type_, value, namespace, instance_path, &format!("{}/type", schema_path)),
+                None => Err("schemas must define a 'type'".to_string())
+            },
+            _ => Err(format!("{} is not a valid schema", schema))
+        }
+    }
+
+    // Validates the specified value against the type with the specified name, whose definition is the specified schema if it is not a reference
+    fn validate_type(&self, name: &str, schema: Option<&Value>, value: &Value, namespace: Option<&str>, instance_path: &str, schema_path: &str) -> Result<Vec<SchemaViolation>, String>{
+        let violation = |message: String| Ok(vec![SchemaViolation {
+            instance_path: instance_path.to_string(),
+            schema_path: schema_path.to_string(),
+            message
+        }]);
+        let valid = match name{
+            "null" => value.is_null(),
+            "boolean" => value.is_boolean(),
+            "int" => value.as_i64().is_some_and(|number| i32::try_from(number).is_ok()),
+            "long" => value.is_i64(),
+            "float" | "double" => value.is_number(),
+            "bytes" | "string" => value.is_string(),
+            "record" | "error" | "enum" | "fixed" | "array" | "map" => {
+                let Some(schema) = schema else { return Err(format!("'{}' types must be defined by an object", name)) };
+                let namespace = match name{
+                    "record" | "error" | "enum" | "fixed" => {
+                        let type_name = schema.get("name").and_then(Value::as_str).unwrap_or_default();
+                        full_name(type_name, schema.get("namespace").and_then(Value::as_str).or(namespace)).1
+                    },
+                    _ => namespace.map(str::to_string)
+                };
+                return self.validate_complex(name, schema, value, namespace.as_deref(), instance_path, schema_path);
+            },
+            _ => {
+                let candidates = [full_name(name, namespace).0, name.to_string()];
+                let Some((definition, definition_namespace)) = candidates.iter().find_map(|candidate| self.types.get(candidate)) else {
+                    return Err(format!("the type '{}' is not defined", name));
+                };
+                let type_ = definition.get("type").and_then(Value::as_str).unwrap_or_default();
+                return self.validate_complex(type_, definition, value, definition_namespace.as_deref(), instance_path, schema_path);
+            }
+        };
+        if valid{
+            Ok(Vec::new())
+        }
+        else{
+            violation(format!("{} is not a valid '{}'", value, name))
+        }
+    }
+
+    // Validates the specified value against the specified complex type definition
+    fn validate_complex(&self, type_: &str, schema: &Value, value: &Value, namespace: Option<&str>, instance_path: &str, schema_path: &str) -> Result<Vec<SchemaViolation>, String>{
+        let violation = |message: String| Ok(vec![SchemaViolation {
+            instance_path: instance_path.to_string(),
+            schema_path: schema_path.to_string(),
+            message
+        }]);
+        match (type_, value){
+            ("record" | "error", Value::Object(properties)) => {
+                let mut violations = Vec::new();
+                let fields = schema.get("fields").and_then(Value::as_array).ok_or("records must define 'fields'")?;
+                for (index, field) in fields.iter().enumerate(){
+                    let name = field.get("name").and_then(Value::as_str).ok_or("fields must define a 'name'")?;
+                    let field_type = field.get("type").ok_or("fields must define a 'type'")?;
+                    let field_path = format!("{}/fields/{}/type", schema_path, index);
+                    match properties.get(name){
+                        Some(property) => violations.extend(self.validate(field_type, property, namespace, &format!("{}/{}", instance_path, escape_json_pointer_segment(name)), &field_path)?),
+                        None if field.get("default").is_some() || self.validate(field_type, &Value::Null, namespace, instance_path, &field_path)?.is_empty() => {},
+                        None => violations.push(SchemaViolation {
+                            instance_path: instance_path.to_string(),
+                            schema_path: field_path,
+                            message: format!("the required field '{}' is missing", name)
+                        })
+                    }
+                }
+                Ok(violations)
+            },
+            ("enum", Value::String(symbol)) => {
+                let symbols = schema.get("symbols").and_then(Value::as_array).ok_or("enums must define 'symbols'")?;
+                if symbols.iter().any(|candidate| candidate.as_str() == Some(symbol)){
+                    Ok(Vec::new())
+                }
+                else{
+                    violation(format!("'{}' is not one of the symbols of the enum", symbol))
+                }
+            },
+            ("fixed", Value::String(bytes)) => {
+                let size = schema.get("size").and_then(Value::as_u64).ok_or("fixed types must define a 'size'")?;
+                if bytes.chars().count() as u64 == size{
+                    Ok(Vec::new())
+                }
+                else{
+                    violation(format!("the value must be exactly {} bytes long", size))
+                }
+            },
+            ("array", Value::Array(items)) => {
+                let item_type = schema.get("items").ok_or("arrays must define 'items'")?;
+                let mut violations = Vec::new();
+                for (index, item) in items.iter().enumerate(){
+                    violations.extend(self.validate(item_type, item, namespace, &format!("{}/{}", instance_path, index), &format!("{}/items", schema_path))?);
+                }
+                Ok(violations)
+            },
+            ("map", Value::Object(properties)) => {
+                let value_type = schema.get("values").ok_or("maps must define 'values'")?;
+                let mut violations = Vec::new();
+                for (name, property) in properties{
+                    violations.extend(self.validate(value_type, property, namespace, &format!("{}/{}", instance_path, escape_json_pointer_segment(name)), &format!("{}/values", schema_path))?);
+                }
+                Ok(violations)
+            },
+            ("record" | "error" | "enum" | "fixed" | "array" | "map", _) => violation(format!("{} is not a valid '{}'", value, type_)),
+            _ => self.validate_type(type_, None, value, namespace, instance_path, schema_path)
+        }
+    }
+
+}
+
+// Gets the full name of the named type with the specified name, defined in the specified namespace, and the namespace of the types it encloses
+fn full_name(name: &str, namespace: Option<&str>) -> (String, Option<String>){
+    match name.rsplit_once('.'){
+        Some((namespace, _)) => (name.to_string(), Some(namespace.to_string())),
+        None => match namespace.filter(|namespace| !namespace.is_empty()){
+            Some(namespace) => (format!("{}.{}", namespace, name), Some(namespace.to_string())),
+            None => (name.to_string(), None)
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use serde_json::Value;
+use crate::models::error::*;
+use crate::models::task::*;
+use crate::services::expression::*;
+
+/// Gets the default name of the runtime expression variable the caught error is saved as
+pub const DEFAULT_ERROR_VARIABLE: &str = "error";
+
+/// Determines whether or not the specified error matches the specified filter
+///
+/// An error matches if, for each property defined by the filter's 'with', the error's property of the same name ('type', 'title', 'status', 'detail' or 'instance') has the same value.
+/// Status codes are compared regardless of whether they are set as numbers or strings, and a null value matches an undefined property.
+pub fn error_matches_filter(filter: &ErrorFilterDefinition, error: &ErrorDefinition) -> bool{
+    let Some(with) = &filter.with else { return true };
+    with.iter().all(|(name, expected)| {
+        let actual = match name.as_str(){
+            "type" => Value::String(error.type_.clone()),
+            "title" => Value::String(error.title.clone()),
+            "status" => error.status.clone(),
+            "detail" => error.detail.clone().map(Value::String).unwrap_or_default(),
+            "instance" => error.instance.clone().map(Value::String).unwrap_or_default(),
+            _ => Value::Null
+        };
+        match (expected, &actual){
+            (Value::Number(expected), Value::String(actual)) | (Value::String(actual), Value::Number(expected)) => expected.to_string() == *actual,
+            _ => *expected == actual
+        }
+    })
+}
+
+/// Determines whether or not the specified catcher catches the specified error, raised while processing the specified data
+///
+/// The error is caught if it matches the catcher's 'errors' filter, if any, then if its 'when' expression, if any, evaluates to true, and finally if its 'exceptWhen' expression, if any, evaluates to false.
+/// Both expressions are evaluated with the error bound to the variable named by the catcher's 'as', which defaults to 'error', and must evaluate to a boolean.
+///
+/// Returns the arguments to evaluate the catcher's retry policy and tasks with, that is the specified arguments with the error bound, if the error is caught, or None otherwise.
+pub fn catches(catcher: &ErrorCatcherDefinition, error: &ErrorDefinition, data: &Value, arguments: &RuntimeExpressionArguments, evaluator: &dyn RuntimeExpressionEvaluator) -> Result<Option<RuntimeExpressionArguments>, RuntimeExpressionError>{
+    if !catcher.errors.as_ref().is_none_or(|filter| error_matches_filter(filter, error)){
+        return Ok(None);
+    }
+    let mut arguments = arguments.clone();
+    let variable = catcher.as_.as_deref().unwrap_or(DEFAULT_ERROR_VARIABLE);
+    arguments.variables.insert(variable.to_string(), serde_json::to_value(error).unwrap_or_default());
+    if let Some(when) = &catcher.when{
+        if !evaluate_condition(when, data, &arguments, evaluator)?{
+            return Ok(None);
+        }
+    }
+    if let Some(except_when) = &catcher.except_when{
+        if evaluate_condition(except_when, data, &arguments, evaluator)?{
+            return Ok(None);
+        }
+    }
+    Ok(Some(arguments))
+}
+
+// Evaluates the specified condition, which must produce a boolean
+fn evaluate_condition(expression: &str, data: &Value, arguments: &RuntimeExpressionArguments, evaluator: &dyn RuntimeExpressionEvaluator) -> Result<bool, RuntimeExpressionError>{
+    match evaluator.evaluate(expression, data, arguments)?{
+        Value::Bool(result) => Ok(result),
+        other => Err(RuntimeExpressionError::new(expression, &format!("the condition must evaluate to a boolean, but evaluated to {}", other)))
+    }
+}
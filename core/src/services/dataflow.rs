@@ -0,0 +1,130 @@
+use serde_json::Value;
+use crate::models::input::*;
+use crate::models::output::*;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::expression::*;
+
+/// Represents the data produced by applying the data-flow transformations of a task
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TaskDataFlow{
+
+    /// Gets/sets the task's transformed input, that is the result of its 'input.from'
+    pub input: Value,
+
+    /// Gets/sets the task's transformed output, that is the result of its 'output.as'
+    pub output: Value,
+
+    /// Gets/sets the workflow's context after the task ran, that is the result of its 'export.as'
+    pub context: Value
+
+}
+
+/// Represents a service used to apply the data-flow transformations defined by workflows and tasks, as defined by the specification
+///
+/// Transformations are applied in the following order:
+/// 1. 'input.from' is evaluated against the raw input, and produces the transformed input, exposed as '$input' to the expressions that follow.
+/// 2. 'output.as' is evaluated against the raw output, and produces the transformed output.
+/// 3. 'export.as' is evaluated against the transformed output, and produces the new context, exposed as '$context' to the tasks that follow.
+///
+/// A transformation that is not defined leaves its data unchanged. Transformations can be runtime expressions or objects and arrays containing runtime expressions, which are evaluated recursively.
+pub struct DataFlowProcessor<'a>{
+    evaluator: &'a dyn RuntimeExpressionEvaluator,
+    mode: RuntimeExpressionEvaluationMode
+}
+impl<'a> DataFlowProcessor<'a>{
+
+    /// Initializes a new DataFlowProcessor
+    pub fn new(evaluator: &'a dyn RuntimeExpressionEvaluator, mode: RuntimeExpressionEvaluationMode) -> Self{
+        Self {
+            evaluator,
+            mode
+        }
+    }
+
+    /// Initializes a new DataFlowProcessor that evaluates runtime expressions in the mode configured by the specified workflow
+    pub fn for_workflow(evaluator: &'a dyn RuntimeExpressionEvaluator, workflow: &WorkflowDefinition) -> Self{
+        Self::new(evaluator, workflow.evaluate.as_ref().and_then(|evaluate| evaluate.mode.clone()).unwrap_or_default())
+    }
+
+    /// Applies the specified input definition, if any, to the specified raw input
+    ///
+    /// '$input' is set to the raw input, and the other arguments are passed as is.
+    pub fn transform_input(&self, definition: Option<&InputDataModelDefinition>, raw_input: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        match definition.and_then(|definition| definition.from.as_ref()){
+            Some(from) => {
+                let mut arguments = arguments.clone();
+                arguments.input = raw_input.clone();
+                self.evaluate(from, raw_input, &arguments)
+            },
+            None => Ok(raw_input.clone())
+        }
+    }
+
+    /// Applies the specified output definition, if any, to the specified raw output
+    ///
+    /// The arguments are passed as is, and must therefore define the transformed input as '$input'.
+    pub fn transform_output(&self, definition: Option<&OutputDataModelDefinition>, raw_output: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        match definition.and_then(|definition| definition.as_.as_ref()){
+            Some(as_) => self.evaluate(as_, raw_output, arguments),
+            None => Ok(raw_output.clone())
+        }
+    }
+
+    /// Applies the specified export definition, if any, to the specified transformed output, and returns the new context
+    ///
+    /// The arguments are passed as is, and must therefore define the transformed input as '$input' and the current context as '$context', which is returned unchanged if no export is defined.
+    pub fn export(&self, definition: Option<&OutputDataModelDefinition>, output: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        match definition.and_then(|definition| definition.as_.as_ref()){
+            Some(as_) => self.evaluate(as_, output, arguments),
+            None => Ok(arguments.context.clone())
+        }
+    }
+
+    /// Applies the 'input.from' of the specified task to the specified raw input
+    pub fn transform_task_input(&self, task: &TaskDefinition, raw_input: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        self.transform_input(task.common().input.as_ref(), raw_input, arguments)
+    }
+
+    /// Applies the 'output.as' and then the 'export.as' of the specified task to the specified raw output, given the task's transformed input and the current context
+    pub fn transform_task_output(&self, task: &TaskDefinition, input: &Value, raw_output: &Value, context: &Value, arguments: &RuntimeExpressionArguments) -> Result<TaskDataFlow, RuntimeExpressionError>{
+        let mut arguments = arguments.clone();
+        arguments.input = input.clone();
+        arguments.context = context.clone();
+        let common = task.common();
+        let output = self.transform_output(common.output.as_ref(), raw_output, &arguments)?;
+        let context = self.export(common.export.as_ref(), &output, &arguments)?;
+        Ok(TaskDataFlow {
+            input: input.clone(),
+            output,
+            context
+        })
+    }
+
+    /// Applies all the data-flow transformations of the specified task, given its raw input, its raw output and the current context
+    pub fn process_task(&self, task: &TaskDefinition, raw_input: &Value, raw_output: &Value, context: &Value, arguments: &RuntimeExpressionArguments) -> Result<TaskDataFlow, RuntimeExpressionError>{
+        let mut arguments = arguments.clone();
+        arguments.context = context.clone();
+        let input = self.transform_task_input(task, raw_input, &arguments)?;
+        self.transform_task_output(task, &input, raw_output, context, &arguments)
+    }
+
+    /// Applies the 'input.from' of the specified workflow to the specified raw input
+    pub fn transform_workflow_input(&self, workflow: &WorkflowDefinition, raw_input: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        self.transform_input(workflow.input.as_ref(), raw_input, arguments)
+    }
+
+    /// Applies the 'output.as' of the specified workflow to the specified raw output
+    pub fn transform_workflow_output(&self, workflow: &WorkflowDefinition, raw_output: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        self.transform_output(workflow.output.as_ref(), raw_output, arguments)
+    }
+
+    // Evaluates the specified transformation against the specified data
+    fn evaluate(&self, transformation: &Value, data: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        match transformation{
+            Value::String(expression) if self.mode == RuntimeExpressionEvaluationMode::Loose && !is_runtime_expression(expression) => self.evaluator.evaluate(expression, data, arguments),
+            _ => self.evaluator.evaluate_value(transformation, data, arguments)
+        }
+    }
+
+}
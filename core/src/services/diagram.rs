@@ -0,0 +1,185 @@
+use std::fmt::Write;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::graph::*;
+
+/// Renders the specified workflow definition as a Mermaid flowchart
+///
+/// The output only depends on the workflow definition, which makes it suitable for snapshot testing.
+pub fn to_mermaid(workflow: &WorkflowDefinition) -> String{
+    let graph = ControlFlowGraph::new(workflow);
+    let mut output = String::from("flowchart TD\n");
+    write_mermaid_scope(&mut output, &graph, None, 1);
+    for edge in sorted_edges(&graph){
+        let arrow = match (edge.kind, &edge.label){
+            (ControlFlowEdgeKind::Loop, _) => "-.->".to_string(),
+            (ControlFlowEdgeKind::Catch, _) => "-.->|\"catch\"|".to_string(),
+            (ControlFlowEdgeKind::Raise, _) => "==>|\"raise\"|".to_string(),
+            (_, Some(label)) => format!("-->|\"{}\"|", escape_mermaid(label)),
+            (_, None) => "-->".to_string()
+        };
+        let _ = writeln!(output, "    n{} {} n{}", edge.source, arrow, edge.target);
+    }
+    output
+}
+
+/// Renders the specified workflow definition as a Graphviz DOT digraph
+///
+/// The output only depends on the workflow definition, which makes it suitable for snapshot testing.
+pub fn to_dot(workflow: &WorkflowDefinition) -> String{
+    let graph = ControlFlowGraph::new(workflow);
+    let mut output = String::new();
+    let _ = writeln!(output, "digraph \"{}\" {{", escape_dot(&workflow.document.name));
+    output.push_str("    rankdir=TB;\n");
+    output.push_str("    node [fontname=\"Helvetica\"];\n");
+    output.push_str("    edge [fontname=\"Helvetica\"];\n");
+    write_dot_scope(&mut output, &graph, None, 1);
+    for edge in sorted_edges(&graph){
+        let mut attributes = Vec::new();
+        match edge.kind{
+            ControlFlowEdgeKind::Loop => attributes.push("style=dashed".to_string()),
+            ControlFlowEdgeKind::Catch => attributes.extend(["style=dashed".to_string(), "label=\"catch\"".to_string()]),
+            ControlFlowEdgeKind::Raise => attributes.extend(["style=bold".to_string(), "label=\"raise\"".to_string()]),
+            _ => {}
+        }
+        if let Some(label) = &edge.label{
+            attributes.push(format!("label=\"{}\"", escape_dot(label)));
+        }
+        if attributes.is_empty(){
+            let _ = writeln!(output, "    n{} -> n{};", edge.source, edge.target);
+        }
+        else{
+            let _ = writeln!(output, "    n{} -> n{} [{}];", edge.source, edge.target, attributes.join(", "));
+        }
+    }
+    output.push_str("}\n");
+    output
+}
+
+// Enumerates the sections of a try task that are rendered as clusters
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ClusterSection{
+    Try,
+    Catch
+}
+
+// Represents a cluster, identified by the try task node it belongs to and by the section of the task it renders
+type Cluster = (usize, ClusterSection);
+
+// Gets the cluster, if any, the specified node must be rendered in
+fn cluster_of(graph: &ControlFlowGraph, node: &ControlFlowNode) -> Option<Cluster>{
+    let parent = graph.node(node.parent?)?;
+    if node.kind == ControlFlowNodeKind::Task && parent.task_type == Some(TaskType::Try){
+        let reference = node.reference.as_ref()?;
+        let parent_depth = parent.reference.as_ref()?.segments().len();
+        let section = match reference.segments().get(parent_depth).map(String::as_str){
+            Some("catch") => ClusterSection::Catch,
+            _ => ClusterSection::Try
+        };
+        return Some((parent.id, section));
+    }
+    cluster_of(graph, parent)
+}
+
+// Gets the clusters, sorted, that are directly nested in the specified scope
+fn clusters_in(graph: &ControlFlowGraph, scope: Option<Cluster>) -> Vec<Cluster>{
+    let mut clusters: Vec<Cluster> = graph.nodes().iter()
+        .filter_map(|node| cluster_of(graph, node))
+        .filter(|(task, _)| graph.node(*task).and_then(|task| cluster_of(graph, task)) == scope)
+        .collect();
+    clusters.sort();
+    clusters.dedup();
+    clusters
+}
+
+// Gets the title of the specified cluster
+fn cluster_title(graph: &ControlFlowGraph, cluster: Cluster) -> String{
+    let name = graph.node(cluster.0).map(|node| node.name.as_str()).unwrap_or_default();
+    match cluster.1{
+        ClusterSection::Try => format!("{} (try)", name),
+        ClusterSection::Catch => format!("{} (catch)", name)
+    }
+}
+
+// Gets the identifier of the specified cluster
+fn cluster_id(cluster: Cluster) -> String{
+    match cluster.1{
+        ClusterSection::Try => format!("n{}_try", cluster.0),
+        ClusterSection::Catch => format!("n{}_catch", cluster.0)
+    }
+}
+
+// Writes the Mermaid declarations of the nodes and clusters of the specified scope
+fn write_mermaid_scope(output: &mut String, graph: &ControlFlowGraph, scope: Option<Cluster>, depth: usize){
+    let indent = "    ".repeat(depth);
+    for node in graph.nodes().iter().filter(|node| cluster_of(graph, node) == scope){
+        let label = escape_mermaid(&node.name);
+        let declaration = match (node.kind, &node.task_type){
+            (ControlFlowNodeKind::Start, _) => format!("(({}))", label),
+            (ControlFlowNodeKind::End, _) => format!("((({})))", label),
+            (ControlFlowNodeKind::Join, _) => "@{ shape: fork, label: \" \" }".to_string(),
+            (_, Some(TaskType::Fork)) => format!("@{{ shape: fork, label: \"{}\" }}", label),
+            (_, Some(TaskType::Call)) => format!("[[\"{}\"]]", label),
+            (_, Some(TaskType::Run)) => format!("[/\"{}\"/]", label),
+            (_, Some(TaskType::Wait)) => format!("{{{{\"{}\"}}}}", label),
+            (_, Some(TaskType::Listen)) => format!(">\"{}\"]", label),
+            (_, Some(TaskType::Emit)) => format!("[\\\"{}\"\\]", label),
+            (_, Some(TaskType::Switch)) => format!("{{\"{}\"}}", label),
+            (_, Some(TaskType::Raise)) => format!("[/\"{}\"\\]", label),
+            _ => format!("[\"{}\"]", label)
+        };
+        let _ = writeln!(output, "{}n{}{}", indent, node.id, declaration);
+    }
+    for cluster in clusters_in(graph, scope){
+        let _ = writeln!(output, "{}subgraph {} [\"{}\"]", indent, cluster_id(cluster), escape_mermaid(&cluster_title(graph, cluster)));
+        write_mermaid_scope(output, graph, Some(cluster), depth + 1);
+        let _ = writeln!(output, "{}end", indent);
+    }
+}
+
+// Writes the Graphviz declarations of the nodes and clusters of the specified scope
+fn write_dot_scope(output: &mut String, graph: &ControlFlowGraph, scope: Option<Cluster>, depth: usize){
+    let indent = "    ".repeat(depth);
+    for node in graph.nodes().iter().filter(|node| cluster_of(graph, node) == scope){
+        let label = escape_dot(&node.name);
+        let attributes = match (node.kind, &node.task_type){
+            (ControlFlowNodeKind::Start, _) => format!("label=\"{}\", shape=circle", label),
+            (ControlFlowNodeKind::End, _) => format!("label=\"{}\", shape=doublecircle", label),
+            (ControlFlowNodeKind::Join, _) => "label=\"\", shape=box, style=filled, fillcolor=black, height=0.08".to_string(),
+            (_, Some(TaskType::Fork)) => format!("label=\"\", xlabel=\"{}\", shape=box, style=filled, fillcolor=black, height=0.08", label),
+            (_, Some(TaskType::Call)) => format!("label=\"{}\", shape=component", label),
+            (_, Some(TaskType::Run)) => format!("label=\"{}\", shape=parallelogram", label),
+            (_, Some(TaskType::Wait)) => format!("label=\"{}\", shape=hexagon", label),
+            (_, Some(TaskType::Listen)) => format!("label=\"{}\", shape=invhouse", label),
+            (_, Some(TaskType::Emit)) => format!("label=\"{}\", shape=house", label),
+            (_, Some(TaskType::Switch)) => format!("label=\"{}\", shape=diamond", label),
+            (_, Some(TaskType::Raise)) => format!("label=\"{}\", shape=octagon", label),
+            _ => format!("label=\"{}\", shape=box", label)
+        };
+        let _ = writeln!(output, "{}n{} [{}];", indent, node.id, attributes);
+    }
+    for cluster in clusters_in(graph, scope){
+        let _ = writeln!(output, "{}subgraph cluster_{} {{", indent, cluster_id(cluster));
+        let _ = writeln!(output, "{}    label=\"{}\";", indent, escape_dot(&cluster_title(graph, cluster)));
+        let _ = writeln!(output, "{}    style=dashed;", indent);
+        write_dot_scope(output, graph, Some(cluster), depth + 1);
+        let _ = writeln!(output, "{}}}", indent);
+    }
+}
+
+// Gets the edges of the specified graph, sorted by source node and then by creation order
+fn sorted_edges(graph: &ControlFlowGraph) -> Vec<&ControlFlowEdge>{
+    let mut edges: Vec<&ControlFlowEdge> = graph.edges().iter().collect();
+    edges.sort_by_key(|edge| edge.source);
+    edges
+}
+
+// Escapes the specified text for use in a quoted Mermaid label
+fn escape_mermaid(text: &str) -> String{
+    text.replace('"', "#quot;")
+}
+
+// Escapes the specified text for use in a quoted Graphviz identifier
+fn escape_dot(text: &str) -> String{
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,362 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use crate::models::input::*;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::pointer::escape_json_pointer_segment;
+use crate::services::visitor::*;
+
+/// Enumerates all the kinds of changes that can be made between two versions of a workflow definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WorkflowChangeKind{
+    /// Indicates that a task has been added
+    #[serde(rename = "taskAdded")]
+    TaskAdded,
+    /// Indicates that a task has been removed
+    #[serde(rename = "taskRemoved")]
+    TaskRemoved,
+    /// Indicates that a task has been moved before or after some of the other tasks of its list
+    #[serde(rename = "taskMoved")]
+    TaskMoved,
+    /// Indicates that the type of a task has changed
+    #[serde(rename = "taskTypeChanged")]
+    TaskTypeChanged,
+    /// Indicates that the transitions of a task, defined by its 'then' directive or by its switch cases, have changed
+    #[serde(rename = "transitionChanged")]
+    TransitionChanged,
+    /// Indicates that the function or the arguments of a call task have changed
+    #[serde(rename = "callArgumentsChanged")]
+    CallArgumentsChanged,
+    /// Indicates that the schema of the input of a workflow or task has changed
+    #[serde(rename = "inputSchemaChanged")]
+    InputSchemaChanged,
+    /// Indicates that the schema of the output of a workflow has changed
+    #[serde(rename = "outputSchemaChanged")]
+    OutputSchemaChanged,
+    /// Indicates that an authentication policy has changed
+    #[serde(rename = "authenticationChanged")]
+    AuthenticationChanged,
+    /// Indicates that any other property of a task has changed
+    #[serde(rename = "taskModified")]
+    TaskModified
+}
+impl fmt::Display for WorkflowChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkflowChangeKind::TaskAdded => write!(f, "task added"),
+            WorkflowChangeKind::TaskRemoved => write!(f, "task removed"),
+            WorkflowChangeKind::TaskMoved => write!(f, "task moved"),
+            WorkflowChangeKind::TaskTypeChanged => write!(f, "task type changed"),
+            WorkflowChangeKind::TransitionChanged => write!(f, "transition changed"),
+            WorkflowChangeKind::CallArgumentsChanged => write!(f, "call arguments changed"),
+            WorkflowChangeKind::InputSchemaChanged => write!(f, "input schema changed"),
+            WorkflowChangeKind::OutputSchemaChanged => write!(f, "output schema changed"),
+            WorkflowChangeKind::AuthenticationChanged => write!(f, "authentication changed"),
+            WorkflowChangeKind::TaskModified => write!(f, "task modified")
+        }
+    }
+}
+
+/// Represents a change made between two versions of a workflow definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkflowChange{
+
+    /// Gets/sets the JSON pointer to the changed node. Points into the newer definition, except for removals, which point into the older one
+    #[serde(rename = "path")]
+    pub path: String,
+
+    /// Gets/sets the JSON pointer, if any, to the changed node in the older definition, when it differs from the path
+    #[serde(rename = "previousPath", skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+
+    /// Gets/sets the kind of change
+    #[serde(rename = "kind")]
+    pub kind: WorkflowChangeKind,
+
+    /// Gets/sets a boolean indicating whether or not the change can break the consumers of the workflow
+    #[serde(rename = "breaking")]
+    pub breaking: bool,
+
+    /// Gets/sets the value, if any, of the changed node in the older definition
+    #[serde(rename = "before", skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+
+    /// Gets/sets the value, if any, of the changed node in the newer definition
+    #[serde(rename = "after", skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>
+
+}
+impl WorkflowChange{
+
+    /// Initializes a new WorkflowChange
+    pub fn new(path: &str, kind: WorkflowChangeKind, breaking: bool, before: Option<Value>, after: Option<Value>) -> Self{
+        Self {
+            path: path.to_string(),
+            previous_path: None,
+            kind,
+            breaking,
+            before,
+            after
+        }
+    }
+
+}
+impl fmt::Display for WorkflowChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let breaking = if self.breaking { "breaking" } else { "non-breaking" };
+        match &self.previous_path {
+            Some(previous_path) => write!(f, "{} [{}] {} (was {})", breaking, self.kind, self.path, previous_path),
+            None => write!(f, "{} [{}] {}", breaking, self.kind, self.path)
+        }
+    }
+}
+
+/// Computes the structural changes made between the specified versions of a workflow definition
+///
+/// Tasks are matched by their name and by the names of the tasks they are nested in, so that moving a task within its list is reported as such rather than as a removal and an addition.
+/// Changes are classified as breaking when they can affect the consumers of the workflow:
+/// - removing a task, or changing its type
+/// - adding or modifying an input schema, or removing or modifying the workflow's output schema
+/// - adding, removing or modifying an authentication policy
+///
+/// All other changes, such as adding or moving tasks or changing transitions and call arguments, are reported as non-breaking.
+pub fn diff(old: &WorkflowDefinition, new: &WorkflowDefinition) -> Vec<WorkflowChange>{
+    let mut changes = Vec::new();
+    compare_value("/input/schema", WorkflowChangeKind::InputSchemaChanged, input_schema(old.input.as_ref()), input_schema(new.input.as_ref()), |_, after| after.is_some(), &mut changes);
+    compare_value("/output/schema", WorkflowChangeKind::OutputSchemaChanged, output_schema(old), output_schema(new), |before, _| before.is_some(), &mut changes);
+    compare_authentications(old, new, &mut changes);
+    let old_tasks = collect_tasks(old);
+    let new_tasks = collect_tasks(new);
+    let old_index: HashMap<&str, &TaskEntry> = old_tasks.iter().map(|entry| (entry.key.as_str(), entry)).collect();
+    let new_index: HashMap<&str, &TaskEntry> = new_tasks.iter().map(|entry| (entry.key.as_str(), entry)).collect();
+    let moved = moved_tasks(&old_tasks, &new_tasks);
+    let mut added: Vec<&str> = Vec::new();
+    for entry in &new_tasks{
+        match old_index.get(entry.key.as_str()){
+            Some(previous) => compare_tasks(previous, entry, moved.contains(entry.key.as_str()), &mut changes),
+            None => {
+                if !is_nested_in_any(&entry.key, &added){
+                    changes.push(WorkflowChange::new(&entry.path, WorkflowChangeKind::TaskAdded, false, None, to_value(&entry.task)));
+                }
+                added.push(&entry.key);
+            }
+        }
+    }
+    let mut removed: Vec<&str> = Vec::new();
+    for entry in &old_tasks{
+        if !new_index.contains_key(entry.key.as_str()){
+            if !is_nested_in_any(&entry.key, &removed){
+                changes.push(WorkflowChange::new(&entry.path, WorkflowChangeKind::TaskRemoved, true, to_value(&entry.task), None));
+            }
+            removed.push(&entry.key);
+        }
+    }
+    changes
+}
+
+// Determines whether or not the task with the specified key is nested in any of the tasks with the specified keys, in which case the change of the outermost task already describes it
+fn is_nested_in_any(key: &str, keys: &[&str]) -> bool{
+    keys.iter().any(|candidate| key.strip_prefix(candidate).is_some_and(|rest| rest.starts_with('/')))
+}
+
+// Represents a task collected from a workflow definition
+//
+// The task is owned, as the tasks defined by the arguments of a call, such as the subscription tasks of AsyncAPI calls, are deserialized from them while visiting.
+struct TaskEntry{
+    key: String,
+    path: String,
+    index: Option<usize>,
+    task: TaskDefinition
+}
+
+// Represents the visitor used to collect the tasks of a workflow definition, keyed by their index-independent path
+#[derive(Default)]
+struct TaskCollector{
+    keys: HashMap<String, String>,
+    entries: Vec<TaskEntry>
+}
+impl TaskCollector{
+
+    // Gets the index-independent key of the specified task, made of the names of the task and of the tasks it is nested in
+    fn key_of(&self, context: &TaskContext) -> String{
+        let Some(index) = context.index else {
+            return context.path.clone();
+        };
+        let suffix = format!("/{}/{}", index, escape_json_pointer_segment(&context.name));
+        let list_path = context.path.strip_suffix(&suffix).unwrap_or(&context.path);
+        let list_key = match context.parent.as_ref().and_then(|parent| self.keys.get(&parent.path).map(|key| (parent, key))){
+            Some((parent, parent_key)) => format!("{}{}", parent_key, &list_path[parent.path.len()..]),
+            None => list_path.to_string()
+        };
+        format!("{}/{}", list_key, escape_json_pointer_segment(&context.name))
+    }
+
+}
+impl Visitor for TaskCollector{
+
+    fn visit_task(&mut self, context: &TaskContext, task: &TaskDefinition) -> bool{
+        let key = self.key_of(context);
+        self.keys.insert(context.path.clone(), key.clone());
+        self.entries.push(TaskEntry { key, path: context.path.clone(), index: context.index, task: task.clone() });
+        true
+    }
+
+}
+
+// Collects all the tasks of the specified workflow definition
+fn collect_tasks(workflow: &WorkflowDefinition) -> Vec<TaskEntry>{
+    let mut collector = TaskCollector::default();
+    walk(workflow, &mut collector);
+    collector.entries
+}
+
+// Gets the keys of the tasks whose order, relative to the other tasks of their list that are defined by both versions, has changed
+//
+// The tasks that keep their relative order are those of the longest common subsequence of both orderings, so that adding or removing a task does not move its siblings.
+fn moved_tasks<'a>(old: &[TaskEntry], new: &'a [TaskEntry]) -> HashSet<&'a str>{
+    let list_of = |entry: &TaskEntry| entry.index.and(entry.key.rsplit_once('/')).map(|(list, _)| list.to_string());
+    let old_keys: HashSet<&str> = old.iter().map(|entry| entry.key.as_str()).collect();
+    let new_keys: HashSet<&str> = new.iter().map(|entry| entry.key.as_str()).collect();
+    let mut lists: HashMap<String, (Vec<&str>, Vec<&'a str>)> = HashMap::new();
+    for entry in old.iter().filter(|entry| new_keys.contains(entry.key.as_str())){
+        if let Some(list) = list_of(entry){
+            lists.entry(list).or_default().0.push(entry.key.as_str());
+        }
+    }
+    for entry in new.iter().filter(|entry| old_keys.contains(entry.key.as_str())){
+        if let Some(list) = list_of(entry){
+            lists.entry(list).or_default().1.push(entry.key.as_str());
+        }
+    }
+    let mut moved = HashSet::new();
+    for (old_order, new_order) in lists.values(){
+        let mut lengths = vec![vec![0usize; new_order.len() + 1]; old_order.len() + 1];
+        for i in (0..old_order.len()).rev(){
+            for j in (0..new_order.len()).rev(){
+                lengths[i][j] = if old_order[i] == new_order[j] { lengths[i + 1][j + 1] + 1 } else { lengths[i + 1][j].max(lengths[i][j + 1]) };
+            }
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < old_order.len() && j < new_order.len(){
+            if old_order[i] == new_order[j]{
+                i += 1;
+                j += 1;
+            }
+            else if lengths[i + 1][j] >= lengths[i][j + 1]{
+                i += 1;
+            }
+            else{
+                moved.insert(new_order[j]);
+                j += 1;
+            }
+        }
+        moved.extend(&new_order[j..]);
+    }
+    moved
+}
+
+// Compares the specified versions of a task
+fn compare_tasks(old: &TaskEntry, new: &TaskEntry, moved: bool, changes: &mut Vec<WorkflowChange>){
+    if moved{
+        let mut change = WorkflowChange::new(&new.path, WorkflowChangeKind::TaskMoved, false, old.index.map(Value::from), new.index.map(Value::from));
+        change.previous_path = Some(old.path.clone());
+        changes.push(change);
+    }
+    if old.task.task_type() != new.task.task_type(){
+        changes.push(WorkflowChange::new(&new.path, WorkflowChangeKind::TaskTypeChanged, true, Some(Value::from(old.task.task_type().as_str())), Some(Value::from(new.task.task_type().as_str()))));
+        return;
+    }
+    let path = |suffix: &str| format!("{}/{}", new.path, suffix);
+    compare_value(&path("then"), WorkflowChangeKind::TransitionChanged, old.task.common().then.as_ref().and_then(to_value), new.task.common().then.as_ref().and_then(to_value), |_, _| false, changes);
+    if let (TaskDefinition::Switch(old_switch), TaskDefinition::Switch(new_switch)) = (&old.task, &new.task){
+        compare_value(&path("switch"), WorkflowChangeKind::TransitionChanged, to_value(&old_switch.switch), to_value(&new_switch.switch), |_, _| false, changes);
+    }
+    if let (TaskDefinition::Call(old_call), TaskDefinition::Call(new_call)) = (&old.task, &new.task){
+        let (old_arguments, mut old_authentications) = split_call(old_call);
+        let (new_arguments, mut new_authentications) = split_call(new_call);
+        compare_value(&path("with"), WorkflowChangeKind::CallArgumentsChanged, Some(old_arguments), Some(new_arguments), |_, _| false, changes);
+        for authentication_path in CallTaskDefinition::AUTHENTICATION_PATHS{
+            compare_value(&format!("{}/with{}", new.path, authentication_path), WorkflowChangeKind::AuthenticationChanged, old_authentications.remove(authentication_path), new_authentications.remove(authentication_path), |_, _| true, changes);
+        }
+    }
+    compare_value(&path("input/schema"), WorkflowChangeKind::InputSchemaChanged, input_schema(old.task.common().input.as_ref()), input_schema(new.task.common().input.as_ref()), |_, after| after.is_some(), changes);
+    compare_value(&new.path, WorkflowChangeKind::TaskModified, Some(own_fields(&old.task)), Some(own_fields(&new.task)), |_, _| false, changes);
+}
+
+// Compares the authentication policies defined by the specified versions of a workflow definition
+fn compare_authentications(old: &WorkflowDefinition, new: &WorkflowDefinition, changes: &mut Vec<WorkflowChange>){
+    let policies = |workflow: &WorkflowDefinition| -> Vec<(String, Option<Value>)>{
+        let mut policies: Vec<(String, Option<Value>)> = workflow.use_.as_ref()
+            .and_then(|components| components.authentications.as_ref())
+            .map(|authentications| authentications.iter().map(|(name, policy)| (name.clone(), to_value(policy))).collect())
+            .unwrap_or_default();
+        policies.sort_by(|a, b| a.0.cmp(&b.0));
+        policies
+    };
+    let old_policies = policies(old);
+    let new_policies = policies(new);
+    let mut names: Vec<&String> = old_policies.iter().chain(new_policies.iter()).map(|(name, _)| name).collect();
+    names.sort();
+    names.dedup();
+    for name in names{
+        let find = |policies: &[(String, Option<Value>)]| policies.iter().find(|(candidate, _)| candidate == name).and_then(|(_, policy)| policy.clone());
+        let path = format!("/use/authentications/{}", escape_json_pointer_segment(name));
+        compare_value(&path, WorkflowChangeKind::AuthenticationChanged, find(&old_policies), find(&new_policies), |_, _| true, changes);
+    }
+}
+
+// Compares the specified values, and records a change if they differ. The classifier determines whether or not the change is breaking, based on the old and new values
+fn compare_value<F: Fn(&Option<Value>, &Option<Value>) -> bool>(path: &str, kind: WorkflowChangeKind, before: Option<Value>, after: Option<Value>, is_breaking: F, changes: &mut Vec<WorkflowChange>){
+    if before == after{
+        return;
+    }
+    let breaking = is_breaking(&before, &after);
+    changes.push(WorkflowChange::new(path, kind, breaking, before, after));
+}
+
+// Serializes the specified value
+fn to_value<T: serde::Serialize>(value: &T) -> Option<Value>{
+    serde_json::to_value(value).ok()
+}
+
+// Gets the schema, if any, of the specified input
+fn input_schema(input: Option<&InputDataModelDefinition>) -> Option<Value>{
+    input.and_then(|input| input.schema.as_ref()).and_then(to_value)
+}
+
+// Gets the schema, if any, of the output of the specified workflow
+fn output_schema(workflow: &WorkflowDefinition) -> Option<Value>{
+    workflow.output.as_ref().and_then(|output| output.schema.as_ref()).and_then(to_value)
+}
+
+// Splits the arguments of the specified call task into the arguments, including the called function, and the authentication policies they define, keyed by their JSON pointer relative to the arguments
+fn split_call(call: &CallTaskDefinition) -> (Value, HashMap<&'static str, Value>){
+    let mut arguments = to_value(&call.with).unwrap_or(Value::Null);
+    let mut authentications = HashMap::new();
+    for authentication_path in CallTaskDefinition::AUTHENTICATION_PATHS{
+        let Some((parent, name)) = authentication_path.rsplit_once('/') else { continue };
+        let parent = if parent.is_empty() { Some(&mut arguments) } else { arguments.pointer_mut(parent) };
+        if let Some(policy) = parent.and_then(|parent| parent.as_object_mut()).and_then(|parent| parent.remove(name)){
+            authentications.insert(authentication_path, policy);
+        }
+    }
+    (serde_json::json!({ "call": call.call, "with": arguments }), authentications)
+}
+
+// Gets the fields of the specified task that are not compared individually, excluding its subtasks
+fn own_fields(task: &TaskDefinition) -> Value{
+    let mut value = to_value(task).unwrap_or(Value::Null);
+    if let Some(fields) = value.as_object_mut(){
+        for key in ["then", "switch", "call", "with", "do", "try"]{
+            fields.remove(key);
+        }
+        for (key, nested) in [("input", "schema"), ("catch", "do"), ("fork", "branches"), ("foreach", "do")]{
+            if let Some(object) = fields.get_mut(key).and_then(|value| value.as_object_mut()){
+                object.remove(nested);
+            }
+        }
+    }
+    value
+}
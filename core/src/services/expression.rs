@@ -0,0 +1,383 @@
+use serde_json::{Map as JsonMap, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use crate::models::call::CallFunction;
+use crate::models::event::*;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::catch::DEFAULT_ERROR_VARIABLE;
+use crate::services::pointer::escape_json_pointer_segment;
+use crate::services::validation::{ValidationCode, ValidationDiagnostic};
+use crate::services::visitor::*;
+
+/// Gets the prefix that opens a runtime expression
+pub const RUNTIME_EXPRESSION_PREFIX: &str = "${";
+
+/// Gets the suffix that closes a runtime expression
+pub const RUNTIME_EXPRESSION_SUFFIX: &str = "}";
+
+/// Determines whether or not the specified string is a runtime expression, that is whether it is enclosed in '${' and '}'
+pub fn is_runtime_expression(value: &str) -> bool{
+    let value = value.trim();
+    value.len() >= RUNTIME_EXPRESSION_PREFIX.len() + RUNTIME_EXPRESSION_SUFFIX.len()
+        && value.starts_with(RUNTIME_EXPRESSION_PREFIX)
+        && value.ends_with(RUNTIME_EXPRESSION_SUFFIX)
+}
+
+/// Gets the body of the specified runtime expression, stripped of its enclosing '${' and '}'. Returns the trimmed string as is if it is not enclosed
+pub fn runtime_expression_body(value: &str) -> &str{
+    let value = value.trim();
+    if is_runtime_expression(value){
+        value[RUNTIME_EXPRESSION_PREFIX.len()..value.len() - RUNTIME_EXPRESSION_SUFFIX.len()].trim()
+    }
+    else{
+        value
+    }
+}
+
+/// Represents the arguments made available to runtime expressions, as defined by the specification
+///
+/// Each argument is exposed to expressions as a variable of the same name, prefixed with '$'. Arguments that are not set evaluate to null.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RuntimeExpressionArguments{
+
+    /// Gets/sets the workflow's context data, exposed as '$context'
+    pub context: Value,
+
+    /// Gets/sets the task's transformed input, exposed as '$input'
+    pub input: Value,
+
+    /// Gets/sets the secrets available to the workflow, exposed as '$secrets'
+    pub secrets: Value,
+
+    /// Gets/sets the descriptor of the task being executed, exposed as '$task'
+    pub task: Value,
+
+    /// Gets/sets the descriptor of the workflow being executed, exposed as '$workflow'
+    pub workflow: Value,
+
+    /// Gets/sets the descriptor of the runtime executing the workflow, exposed as '$runtime'
+    pub runtime: Value,
+
+    /// Gets/sets a name/value mapping of the additional variables in scope, such as the item of a 'for' loop or the error caught by a 'try' task. Names do not include the '$' prefix
+    pub variables: BTreeMap<String, Value>
+
+}
+impl RuntimeExpressionArguments{
+
+    /// Gets the names, without their '$' prefix, of the arguments made available to runtime expressions
+    pub const NAMES: &'static [&'static str] = &["context", "input", "secrets", "task", "workflow", "runtime"];
+
+    /// Gets the values of the arguments, in the order defined by NAMES
+    pub fn values(&self) -> [&Value; 6]{
+        [&self.context, &self.input, &self.secrets, &self.task, &self.workflow, &self.runtime]
+    }
+
+    /// Gets the name/value pairs, without the '$' prefix, of all the variables made available to runtime expressions: the arguments defined by NAMES, followed by the additional variables
+    pub fn all_variables(&self) -> Vec<(&str, &Value)>{
+        Self::NAMES.iter().copied().zip(self.values())
+            .chain(self.variables.iter().filter(|(name, _)| !Self::NAMES.contains(&name.as_str())).map(|(name, value)| (name.as_str(), value)))
+            .collect()
+    }
+
+}
+
+/// Represents the error returned when a runtime expression cannot be compiled or evaluated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeExpressionError{
+
+    /// Gets/sets the expression that could not be compiled or evaluated
+    pub expression: String,
+
+    /// Gets/sets a human-readable message that describes the error
+    pub message: String
+
+}
+impl RuntimeExpressionError{
+
+    /// Initializes a new RuntimeExpressionError
+    pub fn new(expression: &str, message: &str) -> Self{
+        Self {
+            expression: expression.to_string(),
+            message: message.to_string()
+        }
+    }
+
+}
+impl fmt::Display for RuntimeExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid runtime expression '{}': {}", self.expression, self.message)
+    }
+}
+impl std::error::Error for RuntimeExpressionError {}
+
+/// Defines the fundamentals of a service used to compile and evaluate runtime expressions
+///
+/// Expressions may be passed either enclosed in '${' and '}' or as their bare body.
+pub trait RuntimeExpressionEvaluator{
+
+    /// Gets the language of the runtime expressions the evaluator supports
+    fn language(&self) -> RuntimeExpressionLanguage;
+
+    /// Checks the syntax of the specified runtime expression, without evaluating it
+    ///
+    /// The expression may reference the arguments defined by the specification and the specified additional variables, whose names do not include the '$' prefix.
+    fn check(&self, expression: &str, variables: &[&str]) -> Result<(), RuntimeExpressionError>;
+
+    /// Evaluates the specified runtime expression against the specified data
+    fn evaluate(&self, expression: &str, data: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>;
+
+    /// Evaluates all the runtime expressions contained by the specified value against the specified data
+    ///
+    /// Strings enclosed in '${' and '}' are replaced by the result of their evaluation, and objects and arrays are evaluated recursively. Other values are returned as is.
+    fn evaluate_value(&self, value: &Value, data: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        match value{
+            Value::String(expression) if is_runtime_expression(expression) => self.evaluate(expression, data, arguments),
+            Value::Array(items) => items.iter()
+                .map(|item| self.evaluate_value(item, data, arguments))
+                .collect::<Result<Vec<Value>, _>>()
+                .map(Value::Array),
+            Value::Object(properties) => properties.iter()
+                .map(|(name, property)| self.evaluate_value(property, data, arguments).map(|property| (name.clone(), property)))
+                .collect::<Result<JsonMap<String, Value>, _>>()
+                .map(Value::Object),
+            _ => Ok(value.clone())
+        }
+    }
+
+}
+
+/// Represents a runtime expression found in a workflow definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeExpressionLocation{
+
+    /// Gets/sets the JSON pointer to the node of the workflow definition that holds the expression
+    pub path: String,
+
+    /// Gets/sets the expression, as written in the workflow definition
+    pub expression: String,
+
+    /// Gets/sets the names, without the '$' prefix, of the additional variables in scope at the expression's location, such as the item of an enclosing 'for' loop
+    pub variables: Vec<String>
+
+}
+
+/// Finds all the runtime expressions of the specified workflow definition, in document order
+///
+/// Strings enclosed in '${' and '}' are runtime expressions wherever they appear, except in the workflow's 'document'.
+/// When the workflow's evaluation mode is 'loose', strings set in fields that only accept runtime expressions, such as 'if', 'when' or 'for.in', are runtime expressions too, even if they are not enclosed.
+pub fn find_expressions(workflow: &WorkflowDefinition) -> Vec<RuntimeExpressionLocation>{
+    let strict = is_strict(workflow);
+    find_all_expressions(workflow).into_iter()
+        .filter(|location| !strict || is_runtime_expression(&location.expression))
+        .collect()
+}
+
+/// Checks the syntax of all the runtime expressions of the specified workflow definition, using the specified evaluator
+///
+/// Returns an error diagnostic for each invalid expression, or a single one if the evaluator does not support the language configured by the workflow's 'evaluate.language'.
+/// When the workflow's evaluation mode is 'strict', strings that are not enclosed in '${' and '}' but are set in fields that only accept runtime expressions are reported as invalid expressions.
+pub fn check_expressions(workflow: &WorkflowDefinition, evaluator: &dyn RuntimeExpressionEvaluator) -> Vec<ValidationDiagnostic>{
+    let language = workflow.evaluate.as_ref().map(|evaluate| evaluate.language.clone()).unwrap_or_default();
+    if language != evaluator.language(){
+        return vec![ValidationDiagnostic::error("/evaluate/language", ValidationCode::UnsupportedExpressionLanguage, format!("runtime expressions are written in '{}', but the evaluator supports '{}'", language.as_str(), evaluator.language().as_str()))];
+    }
+    let strict = is_strict(workflow);
+    find_all_expressions(workflow).into_iter()
+        .filter_map(|location| {
+            if strict && !is_runtime_expression(&location.expression){
+                return Some(ValidationDiagnostic::error(&location.path, ValidationCode::InvalidExpression, format!("runtime expressions must be enclosed in '{}' and '{}' when the evaluation mode is 'strict'", RUNTIME_EXPRESSION_PREFIX, RUNTIME_EXPRESSION_SUFFIX)));
+            }
+            let variables: Vec<&str> = location.variables.iter().map(String::as_str).collect();
+            evaluator.check(&location.expression, &variables).err()
+                .map(|error| ValidationDiagnostic::error(&location.path, ValidationCode::InvalidExpression, error.message))
+        })
+        .collect()
+}
+
+// Determines whether or not the runtime expressions of the specified workflow definition must be enclosed in '${' and '}'
+fn is_strict(workflow: &WorkflowDefinition) -> bool{
+    workflow.evaluate.as_ref().and_then(|evaluate| evaluate.mode.clone()).unwrap_or_default() == RuntimeExpressionEvaluationMode::Strict
+}
+
+// Finds all the runtime expressions of the specified workflow definition, in document order, including the strings set in fields that only accept runtime expressions, regardless of the evaluation mode
+fn find_all_expressions(workflow: &WorkflowDefinition) -> Vec<RuntimeExpressionLocation>{
+    let mut collector = ExpressionFieldCollector::default();
+    collector.collect(workflow);
+    let mut expressions = Vec::new();
+    if let Ok(Value::Object(properties)) = serde_json::to_value(workflow){
+        for (name, property) in properties.iter().filter(|(name, _)| name.as_str() != "document"){
+            collect_expressions(&format!("/{}", escape_json_pointer_segment(name)), property, &collector, &mut expressions);
+        }
+    }
+    expressions
+}
+
+// Collects the runtime expressions contained by the specified value
+fn collect_expressions(path: &str, value: &Value, collector: &ExpressionFieldCollector, expressions: &mut Vec<RuntimeExpressionLocation>){
+    match value{
+        Value::String(expression) if is_runtime_expression(expression) || (collector.fields.contains(path) && !expression.trim().is_empty()) => expressions.push(RuntimeExpressionLocation {
+            path: path.to_string(),
+            expression: expression.clone(),
+            variables: collector.variables_at(path)
+        }),
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate(){
+                collect_expressions(&format!("{}/{}", path, index), item, collector, expressions);
+            }
+        },
+        Value::Object(properties) => {
+            for (name, property) in properties{
+                collect_expressions(&format!("{}/{}", path, escape_json_pointer_segment(name)), property, collector, expressions);
+            }
+        },
+        _ => {}
+    }
+}
+
+// Represents the visitor used to collect the JSON pointers to the fields that only accept runtime expressions, and the additional variables each task brings into scope
+#[derive(Default)]
+struct ExpressionFieldCollector{
+    fields: HashSet<String>,
+    scopes: Vec<(String, Vec<String>)>
+}
+impl ExpressionFieldCollector{
+
+    // Collects the fields and scopes of the specified workflow definition
+    fn collect(&mut self, workflow: &WorkflowDefinition){
+        self.add_data_fields("");
+        if let Some(on) = workflow.schedule.as_ref().and_then(|schedule| schedule.on.as_ref()){
+            self.add_strategy_fields("/schedule/on", on);
+        }
+        if let Some(components) = &workflow.use_{
+            if let Some(retries) = &components.retries{
+                for name in retries.keys(){
+                    self.add_retry_fields(&format!("/use/retries/{}", escape_json_pointer_segment(name)));
+                }
+            }
+            if let Some(extensions) = &components.extensions{
+                for (index, entry) in extensions.entries.iter().enumerate(){
+                    for name in entry.keys(){
+                        self.add(format!("/use/extensions/{}/{}/when", index, escape_json_pointer_segment(name)));
+                    }
+                }
+            }
+        }
+        workflow.walk(self);
+    }
+
+    // Gets the names of the additional variables in scope at the specified path
+    fn variables_at(&self, path: &str) -> Vec<String>{
+        let mut variables = Vec::new();
+        for (scope, names) in &self.scopes{
+            if path.strip_prefix(scope.as_str()).is_some_and(|rest| rest.is_empty() || rest.starts_with('/')){
+                for name in names{
+                    if !variables.contains(name){
+                        variables.push(name.clone());
+                    }
+                }
+            }
+        }
+        variables
+    }
+
+    // Adds the specified field
+    fn add(&mut self, path: String){
+        self.fields.insert(path);
+    }
+
+    // Adds the specified variables to the scope of the nodes at the specified paths
+    fn add_scope(&mut self, paths: &[String], variables: &[&str]){
+        let variables: Vec<String> = variables.iter().map(|name| name.to_string()).collect();
+        for path in paths{
+            self.scopes.push((path.clone(), variables.clone()));
+        }
+    }
+
+    // Adds the data filtering fields of the object at the specified path
+    fn add_data_fields(&mut self, path: &str){
+        self.add(format!("{}/input/from", path));
+        self.add(format!("{}/output/as", path));
+        self.add(format!("{}/export/as", path));
+    }
+
+    // Adds the fields of the retry policy at the specified path
+    fn add_retry_fields(&mut self, path: &str){
+        self.add(format!("{}/when", path));
+        self.add(format!("{}/exceptWhen", path));
+    }
+
+    // Adds the fields of the specified event consumption strategy
+    fn add_strategy_fields(&mut self, path: &str, strategy: &EventConsumptionStrategyDefinition){
+        let mut filters: Vec<(String, &EventFilterDefinition)> = Vec::new();
+        for (key, list) in [("all", &strategy.all), ("any", &strategy.any)]{
+            for (index, filter) in list.iter().flatten().enumerate(){
+                filters.push((format!("{}/{}/{}", path, key, index), filter));
+            }
+        }
+        if let Some(filter) = &strategy.one{
+            filters.push((format!("{}/one", path), filter));
+        }
+        for (filter_path, filter) in filters{
+            for key in filter.correlate.iter().flat_map(|correlate| correlate.keys()){
+                self.add(format!("{}/correlate/{}/from", filter_path, escape_json_pointer_segment(key)));
+            }
+        }
+        match strategy.until.as_deref(){
+            Some(OneOfEventConsumptionStrategyDefinitionOrExpression::Strategy(until)) => self.add_strategy_fields(&format!("{}/until", path), until),
+            Some(OneOfEventConsumptionStrategyDefinitionOrExpression::Expression(_)) => self.add(format!("{}/until", path)),
+            None => {}
+        }
+    }
+
+}
+impl Visitor for ExpressionFieldCollector{
+
+    fn visit_task(&mut self, context: &TaskContext, task: &TaskDefinition) -> bool{
+        let path = &context.path;
+        self.add(format!("{}/if", path));
+        self.add_data_fields(path);
+        match task{
+            TaskDefinition::Call(call) if CallFunction::AsyncApi == *call.call => {
+                self.add(format!("{}/with/subscription/filter", path));
+                self.add(format!("{}/with/subscription/consume/while", path));
+                self.add(format!("{}/with/subscription/consume/until", path));
+                self.add_data_fields(&format!("{}/with/subscription/foreach", path));
+                if let Some(foreach) = call.with.as_ref().and_then(|with| with.get("subscription")).and_then(|subscription| subscription.get("foreach")){
+                    let variable = |name: &str, default: &'static str| foreach.get(name).and_then(Value::as_str).unwrap_or(default).to_string();
+                    self.add_scope(&[format!("{}/with/subscription/foreach", path)], &[&variable("item", "item"), &variable("at", "index")]);
+                }
+            },
+            TaskDefinition::For(for_) => {
+                self.add(format!("{}/for/in", path));
+                self.add(format!("{}/while", path));
+                self.add_data_fields(&format!("{}/for", path));
+                self.add_scope(&[format!("{}/for/input", path), format!("{}/while", path), format!("{}/do", path)], &[&for_.for_.each, for_.for_.at.as_deref().unwrap_or("index")]);
+            },
+            TaskDefinition::Listen(listen) => {
+                self.add_strategy_fields(&format!("{}/listen/to", path), &listen.listen.to);
+                self.add_data_fields(&format!("{}/foreach", path));
+                if let Some(foreach) = &listen.foreach{
+                    self.add_scope(&[format!("{}/foreach", path)], &[foreach.item.as_deref().unwrap_or("item"), foreach.at.as_deref().unwrap_or("index")]);
+                }
+            },
+            TaskDefinition::Set(_) => self.add(format!("{}/set", path)),
+            TaskDefinition::Switch(switch) => {
+                for (index, entry) in switch.switch.entries.iter().enumerate(){
+                    for name in entry.keys(){
+                        self.add(format!("{}/switch/{}/{}/when", path, index, escape_json_pointer_segment(name)));
+                    }
+                }
+            },
+            TaskDefinition::Try(try_) => {
+                self.add_retry_fields(&format!("{}/catch", path));
+                self.add_retry_fields(&format!("{}/catch/retry", path));
+                let catch = format!("{}/catch", path);
+                self.add_scope(&[format!("{}/when", catch), format!("{}/exceptWhen", catch), format!("{}/retry", catch), format!("{}/do", catch)], &[try_.catch.as_.as_deref().unwrap_or(DEFAULT_ERROR_VARIABLE)]);
+            },
+            _ => {}
+        }
+        true
+    }
+
+}
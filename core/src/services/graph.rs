@@ -0,0 +1,342 @@
+use std::collections::{BTreeSet, VecDeque};
+use crate::models::map::*;
+use crate::models::reference::*;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::pointer::escape_json_pointer_segment;
+
+/// Enumerates all the kinds of nodes of a control-flow graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlFlowNodeKind{
+    /// Indicates the node the workflow starts at
+    Start,
+    /// Indicates the node the workflow ends at
+    End,
+    /// Indicates a node that represents a task
+    Task,
+    /// Indicates a node that represents the point where the branches of a fork task join
+    Join
+}
+
+/// Enumerates all the kinds of edges of a control-flow graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ControlFlowEdgeKind{
+    /// Indicates a transition to the next task in document order
+    Sequence,
+    /// Indicates a transition performed by an explicit 'then' directive, such as 'exit', 'end' or the name of a task
+    Directive,
+    /// Indicates a transition performed by a case of a switch task
+    Case,
+    /// Indicates a transition from a composite task to its first subtask
+    Enter,
+    /// Indicates a transition from the last subtask of a loop back to the loop
+    Loop,
+    /// Indicates a transition from a fork task to one of its branches
+    Branch,
+    /// Indicates a transition from a fork's join node to the fork's successor
+    Join,
+    /// Indicates a transition from a try task to the tasks that handle the errors it caught
+    Catch,
+    /// Indicates a transition performed when a raise task raises its error
+    Raise
+}
+
+/// Represents a node of a control-flow graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlFlowNode{
+
+    /// Gets/sets the node's identifier, which is its index in the graph's nodes
+    pub id: usize,
+
+    /// Gets/sets the node's kind
+    pub kind: ControlFlowNodeKind,
+
+    /// Gets/sets the node's name. For join nodes, this is the name of the fork task they belong to
+    pub name: String,
+
+    /// Gets/sets the reference to the task represented by the node, if any. For join nodes, this is the reference to the fork task they belong to
+    pub reference: Option<TaskReference>,
+
+    /// Gets/sets the type of the task represented by the node, if any
+    pub task_type: Option<TaskType>,
+
+    /// Gets/sets the identifier of the composite task node, if any, the node is nested in
+    pub parent: Option<usize>
+
+}
+
+/// Represents an edge of a control-flow graph
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlFlowEdge{
+
+    /// Gets/sets the identifier of the node the edge starts at
+    pub source: usize,
+
+    /// Gets/sets the identifier of the node the edge ends at
+    pub target: usize,
+
+    /// Gets/sets the edge's kind
+    pub kind: ControlFlowEdgeKind,
+
+    /// Gets/sets the edge's label, if any, such as the name of a switch case
+    pub label: Option<String>
+
+}
+
+// Represents the nodes the tasks of a list transition to when they complete, when they exit their list, and when they raise an error
+#[derive(Clone, Copy)]
+struct ListTargets{
+    continuation: (usize, ControlFlowEdgeKind),
+    exit: usize,
+    handler: Option<usize>
+}
+
+/// Represents the control-flow graph of a workflow definition, whose nodes are tasks and whose edges are the transitions between them
+#[derive(Debug, Clone, PartialEq)]
+pub struct ControlFlowGraph{
+    nodes: Vec<ControlFlowNode>,
+    edges: Vec<ControlFlowEdge>
+}
+impl ControlFlowGraph{
+
+    /// Gets the identifier of the graph's start node
+    pub const START: usize = 0;
+
+    /// Gets the identifier of the graph's end node
+    pub const END: usize = 1;
+
+    /// Builds the control-flow graph of the tasks defined by the specified workflow's 'do' list
+    pub fn new(workflow: &WorkflowDefinition) -> Self{
+        let mut graph = Self {
+            nodes: Vec::new(),
+            edges: Vec::new()
+        };
+        graph.add_node(ControlFlowNodeKind::Start, "start", None, None, None);
+        graph.add_node(ControlFlowNodeKind::End, "end", None, None, None);
+        let entries = graph.add_task_list(&workflow.do_, "/do", None, false, ListTargets { continuation: (Self::END, ControlFlowEdgeKind::Sequence), exit: Self::END, handler: None });
+        let entry = entries.first().copied().unwrap_or(Self::END);
+        graph.add_edge(Self::START, entry, ControlFlowEdgeKind::Sequence, None);
+        graph
+    }
+
+    /// Gets the identifier of the node the workflow starts at
+    pub fn entry(&self) -> usize{
+        Self::START
+    }
+
+    /// Gets the identifier of the node the workflow ends at
+    pub fn exit(&self) -> usize{
+        Self::END
+    }
+
+    /// Gets the graph's nodes, in creation order
+    pub fn nodes(&self) -> &[ControlFlowNode]{
+        &self.nodes
+    }
+
+    /// Gets the graph's edges, in creation order
+    pub fn edges(&self) -> &[ControlFlowEdge]{
+        &self.edges
+    }
+
+    /// Gets the node with the specified identifier, if any
+    pub fn node(&self, id: usize) -> Option<&ControlFlowNode>{
+        self.nodes.get(id)
+    }
+
+    /// Gets the identifier of the node that represents the referenced task, if any
+    pub fn node_of(&self, reference: &TaskReference) -> Option<usize>{
+        self.nodes.iter().find(|node| node.kind == ControlFlowNodeKind::Task && node.reference.as_ref() == Some(reference)).map(|node| node.id)
+    }
+
+    /// Gets the edges that start at the specified node
+    pub fn outgoing_edges(&self, id: usize) -> impl Iterator<Item = &ControlFlowEdge>{
+        self.edges.iter().filter(move |edge| edge.source == id)
+    }
+
+    /// Gets the edges that end at the specified node
+    pub fn incoming_edges(&self, id: usize) -> impl Iterator<Item = &ControlFlowEdge>{
+        self.edges.iter().filter(move |edge| edge.target == id)
+    }
+
+    /// Gets the identifiers of the nodes that can directly be transitioned to from the specified node, in edge order
+    pub fn successors(&self, id: usize) -> Vec<usize>{
+        let mut successors = Vec::new();
+        for edge in self.outgoing_edges(id){
+            if !successors.contains(&edge.target){
+                successors.push(edge.target);
+            }
+        }
+        successors
+    }
+
+    /// Gets the identifiers of the nodes that can directly transition to the specified node, in edge order
+    pub fn predecessors(&self, id: usize) -> Vec<usize>{
+        let mut predecessors = Vec::new();
+        for edge in self.incoming_edges(id){
+            if !predecessors.contains(&edge.source){
+                predecessors.push(edge.source);
+            }
+        }
+        predecessors
+    }
+
+    /// Gets the identifiers, sorted, of all the nodes that can be reached from the specified node, including itself
+    pub fn reachable_from(&self, id: usize) -> BTreeSet<usize>{
+        let mut reachable = BTreeSet::new();
+        let mut queue = VecDeque::from([id]);
+        while let Some(current) = queue.pop_front(){
+            if current >= self.nodes.len() || !reachable.insert(current){
+                continue;
+            }
+            queue.extend(self.successors(current));
+        }
+        reachable
+    }
+
+    /// Determines whether or not the specified target node can be reached from the specified source node
+    pub fn is_reachable(&self, source: usize, target: usize) -> bool{
+        self.reachable_from(source).contains(&target)
+    }
+
+    /// Gets the task nodes that cannot be reached from the start node
+    pub fn unreachable_tasks(&self) -> Vec<&ControlFlowNode>{
+        let reachable = self.reachable_from(Self::START);
+        self.nodes.iter().filter(|node| node.kind == ControlFlowNodeKind::Task && !reachable.contains(&node.id)).collect()
+    }
+
+    // Adds a new node to the graph, and returns its identifier
+    fn add_node(&mut self, kind: ControlFlowNodeKind, name: &str, reference: Option<TaskReference>, task_type: Option<TaskType>, parent: Option<usize>) -> usize{
+        let id = self.nodes.len();
+        self.nodes.push(ControlFlowNode {
+            id,
+            kind,
+            name: name.to_string(),
+            reference,
+            task_type,
+            parent
+        });
+        id
+    }
+
+    // Adds a new edge to the graph
+    fn add_edge(&mut self, source: usize, target: usize, kind: ControlFlowEdgeKind, label: Option<String>){
+        self.edges.push(ControlFlowEdge {
+            source,
+            target,
+            kind,
+            label
+        });
+    }
+
+    // Adds the nodes and edges of the specified task list, and returns the identifiers of its task nodes
+    //
+    // Tasks of a sequential list transition to their next sibling, and the last one to the list's continuation, whereas the tasks of a parallel list, such as the branches of a fork, all transition to the list's continuation.
+    fn add_task_list(&mut self, tasks: &Map<String, TaskDefinition>, path: &str, parent: Option<usize>, parallel: bool, targets: ListTargets) -> Vec<usize>{
+        let mut nodes = Vec::new();
+        for (index, entry) in tasks.entries.iter().enumerate(){
+            for (name, task) in entry{
+                let task_path = format!("{}/{}/{}", path, index, escape_json_pointer_segment(name));
+                let reference = TaskReference::parse(&task_path).ok();
+                let id = self.add_node(ControlFlowNodeKind::Task, name, reference, Some(task.task_type()), parent);
+                nodes.push((id, name.as_str(), task, task_path));
+            }
+        }
+        for (position, (id, _, task, task_path)) in nodes.iter().enumerate(){
+            let next = match nodes.get(position + 1){
+                Some((next, ..)) if !parallel => (*next, ControlFlowEdgeKind::Sequence),
+                _ => targets.continuation
+            };
+            let resolve = |directive: Option<&FlowDirective>, kind: ControlFlowEdgeKind| -> (usize, ControlFlowEdgeKind){
+                match directive{
+                    None | Some(FlowDirective::Continue) => next,
+                    Some(FlowDirective::Exit) => (targets.exit, kind),
+                    Some(FlowDirective::End) => (Self::END, kind),
+                    Some(FlowDirective::Goto(name)) => nodes.iter().find(|(_, candidate, ..)| candidate == name).map(|(target, ..)| (*target, kind)).unwrap_or(next)
+                }
+            };
+            let successor = resolve(task.common().then.as_ref(), ControlFlowEdgeKind::Directive);
+            match task{
+                TaskDefinition::Switch(switch) => {
+                    let mut has_default = false;
+                    for (case_name, case) in switch.switch.iter(){
+                        has_default |= case.when.is_none();
+                        let (target, _) = resolve(case.then.as_ref(), ControlFlowEdgeKind::Case);
+                        self.add_edge(*id, target, ControlFlowEdgeKind::Case, Some(case_name.clone()));
+                    }
+                    if !has_default{
+                        self.add_edge(*id, successor.0, successor.1, None);
+                    }
+                },
+                _ => self.add_task(*id, task, task_path, successor, targets.handler)
+            }
+        }
+        nodes.into_iter().map(|(id, ..)| id).collect()
+    }
+
+    // Adds the edges, and the nodes of the subtasks, if any, of the specified task
+    fn add_task(&mut self, id: usize, task: &TaskDefinition, path: &str, successor: (usize, ControlFlowEdgeKind), handler: Option<usize>){
+        match task{
+            TaskDefinition::Do(do_) => {
+                let entries = self.add_task_list(&do_.do_, &format!("{}/do", path), Some(id), false, ListTargets { continuation: successor, exit: successor.0, handler });
+                self.add_entry_edge(id, entries.first().copied(), successor);
+            },
+            TaskDefinition::For(for_) => {
+                let entries = self.add_task_list(&for_.do_, &format!("{}/do", path), Some(id), false, ListTargets { continuation: (id, ControlFlowEdgeKind::Loop), exit: successor.0, handler });
+                if let Some(entry) = entries.first(){
+                    self.add_edge(id, *entry, ControlFlowEdgeKind::Enter, None);
+                }
+                self.add_edge(id, successor.0, successor.1, None);
+            },
+            TaskDefinition::Fork(fork) => {
+                let join = self.add_node(ControlFlowNodeKind::Join, &self.nodes[id].name.clone(), self.nodes[id].reference.clone(), None, Some(id));
+                let branches = self.add_task_list(&fork.fork.branches, &format!("{}/fork/branches", path), Some(id), true, ListTargets { continuation: (join, ControlFlowEdgeKind::Sequence), exit: join, handler });
+                for branch in &branches{
+                    self.add_edge(id, *branch, ControlFlowEdgeKind::Branch, None);
+                }
+                if branches.is_empty(){
+                    self.add_edge(id, join, ControlFlowEdgeKind::Sequence, None);
+                }
+                self.add_edge(join, successor.0, ControlFlowEdgeKind::Join, None);
+            },
+            TaskDefinition::Listen(listen) => {
+                if let Some(do_) = listen.foreach.as_ref().and_then(|foreach| foreach.do_.as_ref()){
+                    let entries = self.add_task_list(do_, &format!("{}/foreach/do", path), Some(id), false, ListTargets { continuation: (id, ControlFlowEdgeKind::Loop), exit: successor.0, handler });
+                    if let Some(entry) = entries.first(){
+                        self.add_edge(id, *entry, ControlFlowEdgeKind::Enter, None);
+                    }
+                }
+                self.add_edge(id, successor.0, successor.1, None);
+            },
+            TaskDefinition::Raise(_) => self.add_edge(id, handler.unwrap_or(Self::END), ControlFlowEdgeKind::Raise, None),
+            TaskDefinition::Try(try_) => {
+                let first_edge = self.edges.len();
+                let entries = self.add_task_list(&try_.try_, &format!("{}/try", path), Some(id), false, ListTargets { continuation: successor, exit: successor.0, handler: Some(id) });
+                let last_edge = self.edges.len();
+                self.add_entry_edge(id, entries.first().copied(), successor);
+                let catch_entries = match &try_.catch.do_{
+                    Some(do_) => self.add_task_list(do_, &format!("{}/catch/do", path), Some(id), false, ListTargets { continuation: successor, exit: successor.0, handler }),
+                    None => Vec::new()
+                };
+                let catch_target = catch_entries.first().copied().unwrap_or(successor.0);
+                // the errors raised in the try block are handled by the catch block, which did not exist yet when their edges were added
+                for edge in &mut self.edges[first_edge..last_edge]{
+                    if edge.kind == ControlFlowEdgeKind::Raise && edge.target == id{
+                        edge.target = catch_target;
+                    }
+                }
+                self.add_edge(id, catch_target, ControlFlowEdgeKind::Catch, None);
+            },
+            _ => self.add_edge(id, successor.0, successor.1, None)
+        }
+    }
+
+    // Adds the edge from the specified composite task to its first subtask or, if it has none, to its successor
+    fn add_entry_edge(&mut self, id: usize, entry: Option<usize>, successor: (usize, ControlFlowEdgeKind)){
+        match entry{
+            Some(entry) => self.add_edge(id, entry, ControlFlowEdgeKind::Enter, None),
+            None => self.add_edge(id, successor.0, successor.1, None)
+        }
+    }
+
+}
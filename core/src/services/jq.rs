@@ -0,0 +1,93 @@
+use jaq_core::load::{self, Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, Native, RcIter};
+use jaq_json::Val;
+use serde_json::Value;
+use crate::models::workflow::*;
+use crate::services::expression::*;
+
+// Represents a compiled jq filter
+type Filter = jaq_core::Filter<Native<Val>>;
+
+/// Represents a RuntimeExpressionEvaluator that evaluates jq expressions, implemented in pure Rust
+///
+/// Expressions have access to the jq standard library, to the runtime expression arguments, as '$context', '$input', '$secrets', '$task', '$workflow' and '$runtime', and to the additional variables in scope.
+/// An expression that outputs a single value evaluates to that value, one that outputs no value evaluates to null, and one that outputs several values evaluates to an array of them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct JqExpressionEvaluator;
+impl JqExpressionEvaluator{
+
+    /// Initializes a new JqExpressionEvaluator
+    pub fn new() -> Self{
+        Self
+    }
+
+    // Compiles the specified expression, which may reference the variables with the specified names
+    fn compile(&self, expression: &str, variables: &[&str]) -> Result<Filter, RuntimeExpressionError>{
+        let body = runtime_expression_body(expression);
+        let arena = Arena::default();
+        let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+        let modules = loader.load(&arena, File { code: body, path: () }).map_err(|errors| {
+            let messages: Vec<String> = errors.into_iter().flat_map(|(_, error)| load_error_messages(error)).collect();
+            RuntimeExpressionError::new(expression, &messages.join("; "))
+        })?;
+        let variables: Vec<String> = variables.iter().map(|name| format!("${}", name)).collect();
+        Compiler::default()
+            .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+            .with_global_vars(variables.iter().map(String::as_str))
+            .compile(modules)
+            .map_err(|errors| {
+                let messages: Vec<String> = errors.into_iter()
+                    .flat_map(|(_, errors)| errors)
+                    .map(|(name, undefined)| format!("undefined {} '{}'", undefined.as_str(), name))
+                    .collect();
+                RuntimeExpressionError::new(expression, &messages.join("; "))
+            })
+    }
+
+}
+impl RuntimeExpressionEvaluator for JqExpressionEvaluator{
+
+    fn language(&self) -> RuntimeExpressionLanguage{
+        RuntimeExpressionLanguage::Jq
+    }
+
+    fn check(&self, expression: &str, variables: &[&str]) -> Result<(), RuntimeExpressionError>{
+        let mut names = RuntimeExpressionArguments::NAMES.to_vec();
+        names.extend(variables.iter().filter(|name| !RuntimeExpressionArguments::NAMES.contains(name)));
+        self.compile(expression, &names).map(|_| ())
+    }
+
+    fn evaluate(&self, expression: &str, data: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        let variables = arguments.all_variables();
+        let names: Vec<&str> = variables.iter().map(|(name, _)| *name).collect();
+        let filter = self.compile(expression, &names)?;
+        let inputs = RcIter::new(core::iter::empty());
+        let variables = variables.into_iter().map(|(_, value)| Val::from(value.clone()));
+        let mut outputs = filter.run((Ctx::new(variables, &inputs), Val::from(data.clone())))
+            .map(|output| output.map(Value::from).map_err(|error| RuntimeExpressionError::new(expression, &error.to_string())))
+            .collect::<Result<Vec<Value>, _>>()?;
+        match outputs.len(){
+            0 => Ok(Value::Null),
+            1 => Ok(outputs.remove(0)),
+            _ => Ok(Value::Array(outputs))
+        }
+    }
+
+}
+
+// Gets the messages that describe the specified load error
+fn load_error_messages(error: load::Error<&str>) -> Vec<String>{
+    match error{
+        load::Error::Io(errors) => errors.into_iter().map(|(path, message)| format!("failed to load '{}': {}", path, message)).collect(),
+        load::Error::Lex(errors) => errors.into_iter().map(|(expected, found)| unexpected(expected.as_str(), found)).collect(),
+        load::Error::Parse(errors) => errors.into_iter().map(|(expected, found)| unexpected(expected.as_str(), found)).collect()
+    }
+}
+
+// Describes a syntax error, given what was expected and the remainder of the expression at which it was found
+fn unexpected(expected: &str, found: &str) -> String{
+    match found.chars().next(){
+        Some(_) => format!("expected {} but found '{}'", expected, found.chars().take(16).collect::<String>()),
+        None => format!("expected {} but reached the end of the expression", expected)
+    }
+}
@@ -0,0 +1,113 @@
+use rquickjs::function::Args;
+use rquickjs::{CatchResultExt, Context, Ctx, Function, Module, Runtime};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use crate::models::workflow::*;
+use crate::services::expression::*;
+
+/// Represents a RuntimeExpressionEvaluator that evaluates JavaScript expressions, using the embedded QuickJS engine
+///
+/// Expressions are evaluated in strict mode, with the data they are evaluated against bound to 'this' and the runtime expression arguments exposed as '$context', '$input', '$secrets', '$task', '$workflow' and '$runtime', alongside the additional variables in scope.
+/// An expression that evaluates to undefined evaluates to null. Each evaluation runs in a new, isolated context, and is interrupted when it exceeds the evaluator's time or memory limit.
+/// Only single expressions are accepted: bodies that contain statements, such as `1); while(true){} (0`, are rejected without being run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JavaScriptExpressionEvaluator{
+    timeout: Duration,
+    memory_limit: usize
+}
+impl JavaScriptExpressionEvaluator{
+
+    /// Gets the default maximum duration of an evaluation
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// Gets the default maximum amount of memory, in bytes, an evaluation can use
+    pub const DEFAULT_MEMORY_LIMIT: usize = 32 * 1024 * 1024;
+
+    /// Initializes a new JavaScriptExpressionEvaluator
+    pub fn new() -> Self{
+        Self {
+            timeout: Self::DEFAULT_TIMEOUT,
+            memory_limit: Self::DEFAULT_MEMORY_LIMIT
+        }
+    }
+
+    /// Sets the maximum duration of an evaluation
+    pub fn with_timeout(mut self, timeout: Duration) -> Self{
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum amount of memory, in bytes, an evaluation can use
+    pub fn with_memory_limit(mut self, memory_limit: usize) -> Self{
+        self.memory_limit = memory_limit;
+        self
+    }
+
+    // Runs the specified operation in a new context, in which the specified expression has been compiled into a function accepting the variables with the specified names
+    fn with_function<T>(&self, expression: &str, variables: &[&str], operation: impl for<'js> FnOnce(&Ctx<'js>, Function<'js>) -> Result<T, String>) -> Result<T, RuntimeExpressionError>{
+        let body = runtime_expression_body(expression);
+        let runtime = Runtime::new().map_err(|error| RuntimeExpressionError::new(expression, &error.to_string()))?;
+        runtime.set_memory_limit(self.memory_limit);
+        let deadline = Instant::now() + self.timeout;
+        runtime.set_interrupt_handler(Some(Box::new(move || Instant::now() > deadline)));
+        let context = Context::full(&runtime).map_err(|error| RuntimeExpressionError::new(expression, &error.to_string()))?;
+        let parameters: Vec<String> = variables.iter().map(|name| format!("${}", name)).collect();
+        let source = format!("(function({}) {{ return ({}\n); }})", parameters.join(", "), body);
+        context.with(|ctx| {
+            ensure_single_expression(&ctx, body)?;
+            let function = ctx.eval::<Function, _>(source).catch(&ctx).map_err(|error| error.to_string())?;
+            operation(&ctx, function)
+        })
+        .map_err(|message| RuntimeExpressionError::new(expression, &message))
+    }
+
+}
+impl Default for JavaScriptExpressionEvaluator{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+impl RuntimeExpressionEvaluator for JavaScriptExpressionEvaluator{
+
+    fn language(&self) -> RuntimeExpressionLanguage{
+        RuntimeExpressionLanguage::JavaScript
+    }
+
+    fn check(&self, expression: &str, variables: &[&str]) -> Result<(), RuntimeExpressionError>{
+        let mut names = RuntimeExpressionArguments::NAMES.to_vec();
+        names.extend(variables.iter().filter(|name| !RuntimeExpressionArguments::NAMES.contains(name)));
+        self.with_function(expression, &names, |_, _| Ok(()))
+    }
+
+    fn evaluate(&self, expression: &str, data: &Value, arguments: &RuntimeExpressionArguments) -> Result<Value, RuntimeExpressionError>{
+        let variables = arguments.all_variables();
+        let names: Vec<&str> = variables.iter().map(|(name, _)| *name).collect();
+        self.with_function(expression, &names, |ctx, function| {
+            let parse = |value: &Value| ctx.json_parse(value.to_string()).catch(ctx).map_err(|error| error.to_string());
+            let mut args = Args::new(ctx.clone(), variables.len());
+            args.this(parse(data)?).map_err(|error| error.to_string())?;
+            for (_, value) in &variables{
+                args.push_arg(parse(value)?).map_err(|error| error.to_string())?;
+            }
+            let result: rquickjs::Value = function.call_arg(args).catch(ctx).map_err(|error| error.to_string())?;
+            match ctx.json_stringify(result).catch(ctx).map_err(|error| error.to_string())?{
+                Some(json) => {
+                    let json = json.to_string().map_err(|error| error.to_string())?;
+                    serde_json::from_str(&json).map_err(|error| error.to_string())
+                },
+                None => Ok(Value::Null)
+            }
+        })
+    }
+
+}
+
+// Ensures that the specified body is a single expression, without running it
+//
+// The body is compiled, but not evaluated, both between parentheses and between brackets: a body that closes the parentheses to append statements cannot also close the brackets, and conversely.
+fn ensure_single_expression(ctx: &Ctx<'_>, body: &str) -> Result<(), String>{
+    for source in [format!("void ({}\n);", body), format!("void [{}\n];", body)]{
+        Module::declare(ctx.clone(), "expression", source).catch(ctx).map_err(|_| "the expression must be a single JavaScript expression".to_string())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,21 @@
+#[cfg(feature = "avro")]
+pub mod avro;
+pub mod catch;
+pub mod dataflow;
+pub mod diagram;
+pub mod diff;
+pub mod expression;
+pub mod graph;
+#[cfg(feature = "jq")]
+pub mod jq;
+#[cfg(feature = "js")]
+pub mod js;
+pub mod normalization;
+pub mod parsing;
+pub mod pointer;
+pub mod retry;
+pub mod schema;
+pub mod validation;
+pub mod visitor;
+#[cfg(feature = "xml")]
+pub mod xml;
@@ -0,0 +1,455 @@
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use crate::models::authentication::*;
+use crate::models::error::*;
+use crate::models::extension::*;
+use crate::models::input::*;
+use crate::models::map::*;
+use crate::models::output::*;
+use crate::models::resource::*;
+use crate::models::retry::*;
+use crate::models::task::*;
+use crate::models::timeout::*;
+use crate::models::workflow::*;
+use crate::services::pointer::escape_json_pointer_segment;
+use crate::services::validation::*;
+use crate::services::visitor::*;
+
+/// Replaces all the references to the reusable components defined in the workflow's `use` with inline copies of the components they reference
+///
+/// The components remain defined in `use`. References to undefined components are left untouched and reported as error diagnostics.
+pub fn inline_components(workflow: &mut WorkflowDefinition) -> Vec<ValidationDiagnostic>{
+    let components = workflow.use_.clone().unwrap_or_default();
+    let mut diagnostics = Vec::new();
+    for_each_site(workflow, &mut |path, site| {
+        match site{
+            ComponentSite::Timeout(timeout) => {
+                if let OneOfTimeoutDefinitionOrReference::Reference(name) = timeout{
+                    match components.timeouts.as_ref().and_then(|timeouts| timeouts.get(name.as_str())){
+                        Some(definition) => *timeout = OneOfTimeoutDefinitionOrReference::Timeout(definition.clone()),
+                        None => diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedTimeout, format!("The timeout '{}' is not defined in 'use.timeouts'", name)))
+                    }
+                }
+            },
+            ComponentSite::Retry(retry) => {
+                if let OneOfRetryPolicyDefinitionOrReference::Reference(name) = retry{
+                    match components.retries.as_ref().and_then(|retries| retries.get(name.as_str())){
+                        Some(definition) => *retry = OneOfRetryPolicyDefinitionOrReference::Retry(definition.clone()),
+                        None => diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedRetryPolicy, format!("The retry policy '{}' is not defined in 'use.retries'", name)))
+                    }
+                }
+            },
+            ComponentSite::Error(error) => {
+                if let OneOfErrorDefinitionOrReference::Reference(name) = error{
+                    match components.errors.as_ref().and_then(|errors| errors.get(name.as_str())){
+                        Some(definition) => *error = OneOfErrorDefinitionOrReference::Error(definition.clone()),
+                        None => diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedError, format!("The error '{}' is not defined in 'use.errors'", name)))
+                    }
+                }
+            },
+            ComponentSite::Authentication(authentication) => {
+                if let Some(name) = authentication.use_.clone(){
+                    match components.authentications.as_ref().and_then(|authentications| authentications.get(name.as_str())){
+                        Some(definition) => *authentication = definition.clone(),
+                        None => diagnostics.push(undefined_authentication(path, &name))
+                    }
+                }
+            },
+            ComponentSite::AuthenticationValue(authentication) => {
+                if let Some(name) = authentication.get("use").and_then(|name| name.as_str()).map(str::to_string){
+                    match components.authentications.as_ref().and_then(|authentications| authentications.get(name.as_str())).and_then(|definition| serde_json::to_value(definition).ok()){
+                        Some(definition) => *authentication = definition,
+                        None => diagnostics.push(undefined_authentication(path, &name))
+                    }
+                }
+            }
+        }
+    });
+    diagnostics
+}
+
+/// Replaces the inline definitions of timeouts, retry policies, errors and authentication policies with references to reusable components defined in the workflow's `use`
+///
+/// Inline definitions equal to an existing component are replaced with a reference to it. Inline definitions that are defined more than once are hoisted into a new component, named after their kind, such as 'timeout-1'.
+/// Returns the names of the components that have been added to `use`, prefixed by the name of the collection they belong to, such as 'timeouts/timeout-1'.
+pub fn extract_components(workflow: &mut WorkflowDefinition) -> Vec<String>{
+    let mut occurrences: Vec<(ComponentKind, Value)> = Vec::new();
+    for_each_site(workflow, &mut |_, site| {
+        if let Some(occurrence) = site.inline_value(){
+            occurrences.push(occurrence);
+        }
+    });
+    let components = workflow.use_.get_or_insert_with(Default::default);
+    let mut names: HashMap<(ComponentKind, String), String> = HashMap::new();
+    for kind in ComponentKind::ALL{
+        for (name, value) in kind.components(components){
+            names.entry((kind, value.to_string())).or_insert(name);
+        }
+    }
+    let mut counts: Vec<(ComponentKind, Value, usize)> = Vec::new();
+    for (kind, value) in occurrences{
+        match counts.iter_mut().find(|(candidate_kind, candidate, _)| *candidate_kind == kind && *candidate == value){
+            Some((_, _, count)) => *count += 1,
+            None => counts.push((kind, value, 1))
+        }
+    }
+    let mut added = Vec::new();
+    for (kind, value, count) in counts{
+        let key = (kind, value.to_string());
+        if count < 2 || names.contains_key(&key){
+            continue;
+        }
+        let existing = kind.components(components);
+        let name = (1..).map(|index| format!("{}-{}", kind.prefix(), index)).find(|name| !existing.iter().any(|(candidate, _)| candidate == name)).unwrap_or_default();
+        if kind.add_component(components, &name, value){
+            added.push(format!("{}/{}", kind.collection(), name));
+            names.insert(key, name);
+        }
+    }
+    if names.is_empty(){
+        if workflow.use_.as_ref().is_some_and(|components| *components == ComponentDefinitionCollection::default()){
+            workflow.use_ = None;
+        }
+        return added;
+    }
+    for_each_site(workflow, &mut |_, site| {
+        let Some((kind, value)) = site.inline_value() else {
+            return;
+        };
+        if let Some(name) = names.get(&(kind, value.to_string())){
+            site.set_reference(name);
+        }
+    });
+    added
+}
+
+/// Expands the extensions defined in the workflow's `use.extensions` into the tasks they extend, and returns the resulting workflow definition
+///
+/// Each task of the workflow's 'do' list, including nested ones, whose type matches the 'extend' property of one or more extensions is replaced by a 'do' task with the same name, that runs:
+/// - a '{extension}-before' task for each extension that defines 'before' tasks, in declaration order
+/// - the extended task itself, under its own name
+/// - a '{extension}-after' task for each extension that defines 'after' tasks, in reverse declaration order
+///
+/// The 'before' and 'after' tasks only run if the extension's 'when' condition, if any, is met, and output their input unchanged so that they do not alter the data flowing through the extended task.
+/// Their names are suffixed with a number when they are already used by the extended task. The extended task's 'if' and 'then' properties are moved to the wrapping task.
+///
+/// Because the transitions of a switch task can only target its siblings, switch tasks are not wrapped: they are renamed '{task}-switch', and preceded by a 'do' task that takes their name, runs the 'before' and then the 'after' tasks of their extensions, and transitions to them.
+/// The expanded workflow does not define `use.extensions` anymore, so that expanding it again has no effect.
+pub fn expand_extensions(workflow: &WorkflowDefinition) -> WorkflowDefinition{
+    let mut expanded = workflow.clone();
+    let Some(extensions) = expanded.use_.as_mut().and_then(|components| components.extensions.take()) else {
+        return expanded;
+    };
+    let context = TaskListContext {
+        path: "/do".to_string(),
+        container: TaskContainer::Workflow,
+        parent: None,
+        depth: 0
+    };
+    walk_task_list_mut(&context, &mut expanded.do_, &mut ExtensionExpander { extensions, generated: HashSet::new() });
+    expanded
+}
+
+// Represents the visitor used to expand extensions into the tasks they extend
+struct ExtensionExpander{
+    extensions: Map<String, ExtensionDefinition>,
+    // the paths of the tasks added in front of switch tasks, which must neither be walked nor extended
+    generated: HashSet<String>
+}
+impl ExtensionExpander{
+
+    // Gets the extensions that apply to the specified task
+    fn extensions_of(&self, task: &TaskDefinition) -> Vec<(&String, &ExtensionDefinition)>{
+        let task_type = task.task_type();
+        self.extensions.iter().filter(|(_, extension)| extension.extends(&task_type)).collect()
+    }
+
+    // Builds the task that runs the specified 'before' or 'after' tasks of the specified extension, and outputs its input unchanged
+    fn block(tasks: &Map<String, TaskDefinition>, extension: &ExtensionDefinition) -> TaskDefinition{
+        let mut block = DoTaskDefinition::new(tasks.clone());
+        block.common.if_ = extension.when.clone();
+        block.common.output = Some(OutputDataModelDefinition { schema: None, as_: Some(Value::String("${ $input }".to_string())) });
+        TaskDefinition::Do(block)
+    }
+
+    // Builds the task list that runs the 'before' tasks of the specified extensions, the specified task, if any, and their 'after' tasks
+    fn expand(extensions: &[(&String, &ExtensionDefinition)], extended: Option<(&str, TaskDefinition)>) -> Map<String, TaskDefinition>{
+        let name = extended.as_ref().map(|(name, _)| name.to_string());
+        let mut tasks = Map::new();
+        let is_taken = |tasks: &Map<String, TaskDefinition>, candidate: &str| name.as_deref() == Some(candidate) || tasks.contains_key(&candidate.to_string());
+        for (extension_name, extension) in extensions{
+            if let Some(before) = &extension.before{
+                let block_name = unique_name(&format!("{}-before", extension_name), |candidate| is_taken(&tasks, candidate));
+                tasks.add(block_name, Self::block(before, extension));
+            }
+        }
+        if let Some((name, task)) = extended{
+            tasks.add(name.to_string(), task);
+        }
+        for (extension_name, extension) in extensions.iter().rev(){
+            if let Some(after) = &extension.after{
+                let block_name = unique_name(&format!("{}-after", extension_name), |candidate| is_taken(&tasks, candidate));
+                tasks.add(block_name, Self::block(after, extension));
+            }
+        }
+        tasks
+    }
+
+}
+impl VisitorMut for ExtensionExpander{
+
+    fn visit_task_list(&mut self, context: &TaskListContext, tasks: &mut Map<String, TaskDefinition>){
+        let switches: Vec<String> = tasks.iter()
+            .filter(|(_, task)| matches!(task, TaskDefinition::Switch(_)) && !self.extensions_of(task).is_empty())
+            .map(|(name, _)| name.clone())
+            .collect();
+        if switches.is_empty(){
+            return;
+        }
+        for name in &switches{
+            let Some(switch) = tasks.get(name) else { continue };
+            let mut wrapper = DoTaskDefinition::new(Self::expand(&self.extensions_of(switch), None));
+            wrapper.common.if_ = switch.common().if_.clone();
+            let switch_name = unique_name(&format!("{}-switch", name), |candidate| tasks.contains_key(&candidate.to_string()));
+            wrapper.common.then = Some(FlowDirective::Goto(switch_name.clone()));
+            tasks.rename(name, switch_name.clone());
+            tasks.insert_before(&switch_name, name.clone(), TaskDefinition::Do(wrapper));
+        }
+        for (index, name) in tasks.keys().enumerate(){
+            if switches.contains(name){
+                self.generated.insert(format!("{}/{}/{}", context.path, index, escape_json_pointer_segment(name)));
+            }
+        }
+    }
+
+    fn visit_task(&mut self, context: &TaskContext, _task: &mut TaskDefinition) -> bool{
+        !self.generated.contains(&context.path)
+    }
+
+    fn leave_task(&mut self, context: &TaskContext, task: &mut TaskDefinition){
+        if self.generated.contains(&context.path) || matches!(task, TaskDefinition::Switch(_)){
+            return;
+        }
+        let extensions = self.extensions_of(task);
+        if extensions.is_empty(){
+            return;
+        }
+        let mut extended = std::mem::replace(task, TaskDefinition::Do(DoTaskDefinition::new(Map::new())));
+        let if_ = extended.common_mut().if_.take();
+        let then = extended.common_mut().then.take();
+        let mut wrapper = DoTaskDefinition::new(Self::expand(&extensions, Some((&context.name, extended))));
+        wrapper.common.if_ = if_;
+        wrapper.common.then = then;
+        *task = TaskDefinition::Do(wrapper);
+    }
+
+}
+
+// Gets the specified name or, if it is already taken, the first name made of it followed by a number that is not
+fn unique_name<F: Fn(&str) -> bool>(name: &str, is_taken: F) -> String{
+    if !is_taken(name){
+        return name.to_string();
+    }
+    (1..).map(|index| format!("{}-{}", name, index)).find(|candidate| !is_taken(candidate)).unwrap_or_default()
+}
+
+// Enumerates the kinds of reusable components that can be referenced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ComponentKind{
+    Authentication,
+    Error,
+    Retry,
+    Timeout
+}
+impl ComponentKind{
+
+    // Gets all the kinds of reusable components
+    const ALL: [ComponentKind; 4] = [ComponentKind::Authentication, ComponentKind::Error, ComponentKind::Retry, ComponentKind::Timeout];
+
+    // Gets the prefix of the names of the components of this kind hoisted by the extract pass
+    fn prefix(&self) -> &'static str{
+        match self{
+            ComponentKind::Authentication => "authentication",
+            ComponentKind::Error => "error",
+            ComponentKind::Retry => "retry",
+            ComponentKind::Timeout => "timeout"
+        }
+    }
+
+    // Gets the name of the collection, in `use`, of the components of this kind
+    fn collection(&self) -> &'static str{
+        match self{
+            ComponentKind::Authentication => "authentications",
+            ComponentKind::Error => "errors",
+            ComponentKind::Retry => "retries",
+            ComponentKind::Timeout => "timeouts"
+        }
+    }
+
+    // Gets the name and the serialized value of the components of this kind, sorted by name
+    fn components(&self, components: &ComponentDefinitionCollection) -> Vec<(String, Value)>{
+        fn serialize<T: serde::Serialize>(collection: Option<&HashMap<String, T>>) -> Vec<(String, Value)>{
+            let mut values: Vec<(String, Value)> = collection.into_iter()
+                .flatten()
+                .filter_map(|(name, value)| serde_json::to_value(value).ok().map(|value| (name.clone(), value)))
+                .collect();
+            values.sort_by(|a, b| a.0.cmp(&b.0));
+            values
+        }
+        match self{
+            ComponentKind::Authentication => serialize(components.authentications.as_ref()),
+            ComponentKind::Error => serialize(components.errors.as_ref()),
+            ComponentKind::Retry => serialize(components.retries.as_ref()),
+            ComponentKind::Timeout => serialize(components.timeouts.as_ref())
+        }
+    }
+
+    // Adds a new component of this kind. Returns false if the specified value does not describe a valid component
+    fn add_component(&self, components: &mut ComponentDefinitionCollection, name: &str, value: Value) -> bool{
+        fn add<T: serde::de::DeserializeOwned>(collection: &mut Option<HashMap<String, T>>, name: &str, value: Value) -> bool{
+            match serde_json::from_value(value){
+                Ok(component) => {
+                    collection.get_or_insert_with(HashMap::new).insert(name.to_string(), component);
+                    true
+                },
+                Err(_) => false
+            }
+        }
+        match self{
+            ComponentKind::Authentication => add(&mut components.authentications, name, value),
+            ComponentKind::Error => add(&mut components.errors, name, value),
+            ComponentKind::Retry => add(&mut components.retries, name, value),
+            ComponentKind::Timeout => add(&mut components.timeouts, name, value)
+        }
+    }
+
+}
+
+// Represents a node of a workflow definition that can either define a component inline or reference a reusable one
+enum ComponentSite<'a>{
+    Timeout(&'a mut OneOfTimeoutDefinitionOrReference),
+    Retry(&'a mut OneOfRetryPolicyDefinitionOrReference),
+    Error(&'a mut OneOfErrorDefinitionOrReference),
+    Authentication(&'a mut AuthenticationPolicyDefinition),
+    AuthenticationValue(&'a mut Value)
+}
+impl ComponentSite<'_>{
+
+    // Gets the kind and the serialized value of the component, if it is defined inline
+    fn inline_value(&self) -> Option<(ComponentKind, Value)>{
+        match self{
+            ComponentSite::Timeout(OneOfTimeoutDefinitionOrReference::Timeout(timeout)) => Some((ComponentKind::Timeout, serde_json::to_value(timeout).ok()?)),
+            ComponentSite::Retry(OneOfRetryPolicyDefinitionOrReference::Retry(retry)) => Some((ComponentKind::Retry, serde_json::to_value(retry).ok()?)),
+            ComponentSite::Error(OneOfErrorDefinitionOrReference::Error(error)) => Some((ComponentKind::Error, serde_json::to_value(error).ok()?)),
+            ComponentSite::Authentication(authentication) if authentication.use_.is_none() => Some((ComponentKind::Authentication, serde_json::to_value(&**authentication).ok()?)),
+            ComponentSite::AuthenticationValue(authentication) if authentication.get("use").is_none() => Some((ComponentKind::Authentication, (**authentication).clone())),
+            _ => None
+        }
+    }
+
+    // Replaces the component with a reference to the component with the specified name
+    fn set_reference(self, name: &str){
+        match self{
+            ComponentSite::Timeout(timeout) => *timeout = OneOfTimeoutDefinitionOrReference::Reference(name.to_string()),
+            ComponentSite::Retry(retry) => *retry = OneOfRetryPolicyDefinitionOrReference::Reference(name.to_string()),
+            ComponentSite::Error(error) => *error = OneOfErrorDefinitionOrReference::Reference(name.to_string()),
+            ComponentSite::Authentication(authentication) => *authentication = AuthenticationPolicyDefinition { use_: Some(name.to_string()), ..Default::default() },
+            ComponentSite::AuthenticationValue(authentication) => *authentication = serde_json::json!({ "use": name })
+        }
+    }
+
+}
+
+// Invokes the specified callback for each node of the workflow definition that can either define a component inline or reference a reusable one
+fn for_each_site(workflow: &mut WorkflowDefinition, callback: &mut dyn FnMut(&str, ComponentSite<'_>)){
+    if let Some(timeout) = &mut workflow.timeout{
+        callback("/timeout", ComponentSite::Timeout(timeout));
+    }
+    visit_input(callback, "/input", workflow.input.as_mut());
+    visit_output(callback, "/output", workflow.output.as_mut());
+    if let Some(catalogs) = workflow.use_.as_mut().and_then(|components| components.catalogs.as_mut()){
+        let mut names: Vec<String> = catalogs.keys().cloned().collect();
+        names.sort();
+        for name in names{
+            if let Some(catalog) = catalogs.get_mut(&name){
+                visit_endpoint(callback, &format!("/use/catalogs/{}/endpoint", escape_json_pointer_segment(&name)), &mut catalog.endpoint);
+            }
+        }
+    }
+    walk_mut(workflow, &mut SiteVisitor { callback });
+}
+
+// Represents the visitor used to find the component sites of tasks
+struct SiteVisitor<'a>{
+    callback: &'a mut dyn FnMut(&str, ComponentSite<'_>)
+}
+impl VisitorMut for SiteVisitor<'_>{
+
+    fn visit_task(&mut self, context: &TaskContext, task: &mut TaskDefinition) -> bool{
+        let path = &context.path;
+        let callback = &mut *self.callback;
+        let common = task.common_mut();
+        if let Some(timeout) = &mut common.timeout{
+            callback(&format!("{}/timeout", path), ComponentSite::Timeout(timeout));
+        }
+        visit_input(callback, &format!("{}/input", path), common.input.as_mut());
+        visit_output(callback, &format!("{}/output", path), common.output.as_mut());
+        visit_output(callback, &format!("{}/export", path), common.export.as_mut());
+        match task{
+            TaskDefinition::Call(call) => {
+                for (authentication_path, authentication) in call.authentications_mut(){
+                    callback(&format!("{}/with{}", path, authentication_path), ComponentSite::AuthenticationValue(authentication));
+                }
+            },
+            TaskDefinition::For(for_) => visit_input(callback, &format!("{}/for/input", path), for_.for_.input.as_mut()),
+            TaskDefinition::Listen(listen) => {
+                if let Some(foreach) = &mut listen.foreach{
+                    visit_output(callback, &format!("{}/foreach/output", path), foreach.output.as_mut());
+                    visit_output(callback, &format!("{}/foreach/export", path), foreach.export.as_mut());
+                }
+            },
+            TaskDefinition::Raise(raise) => callback(&format!("{}/raise/error", path), ComponentSite::Error(&mut raise.raise.error)),
+            TaskDefinition::Run(run) => {
+                if let Some(source) = run.run.script.as_mut().and_then(|script| script.source.as_mut()){
+                    visit_endpoint(callback, &format!("{}/run/script/source/endpoint", path), &mut source.endpoint);
+                }
+            },
+            TaskDefinition::Try(try_) => {
+                if let Some(retry) = &mut try_.catch.retry{
+                    callback(&format!("{}/catch/retry", path), ComponentSite::Retry(retry));
+                }
+            },
+            _ => {}
+        }
+        true
+    }
+
+}
+
+// Visits the component sites of the specified input data model definition
+fn visit_input(callback: &mut dyn FnMut(&str, ComponentSite<'_>), path: &str, input: Option<&mut InputDataModelDefinition>){
+    if let Some(resource) = input.and_then(|input| input.schema.as_mut()).and_then(|schema| schema.resource.as_mut()){
+        visit_endpoint(callback, &format!("{}/schema/resource/endpoint", path), &mut resource.endpoint);
+    }
+}
+
+// Visits the component sites of the specified output data model definition
+fn visit_output(callback: &mut dyn FnMut(&str, ComponentSite<'_>), path: &str, output: Option<&mut OutputDataModelDefinition>){
+    if let Some(resource) = output.and_then(|output| output.schema.as_mut()).and_then(|schema| schema.resource.as_mut()){
+        visit_endpoint(callback, &format!("{}/schema/resource/endpoint", path), &mut resource.endpoint);
+    }
+}
+
+// Visits the component sites of the specified endpoint
+fn visit_endpoint(callback: &mut dyn FnMut(&str, ComponentSite<'_>), path: &str, endpoint: &mut OneOfEndpointDefinitionOrUri){
+    if let OneOfEndpointDefinitionOrUri::Endpoint(endpoint) = endpoint{
+        if let Some(authentication) = &mut endpoint.authentication{
+            callback(&format!("{}/authentication", path), ComponentSite::Authentication(authentication));
+        }
+    }
+}
+
+// Creates the diagnostic reported when referencing an undefined authentication policy
+fn undefined_authentication(path: &str, name: &str) -> ValidationDiagnostic{
+    ValidationDiagnostic::error(path, ValidationCode::UndefinedAuthentication, format!("The authentication policy '{}' is not defined in 'use.authentications'", name))
+}
@@ -0,0 +1,351 @@
+use serde::de::{self, DeserializeOwned, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde_json::Value;
+use std::fmt;
+use crate::models::extension::*;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::pointer::{escape_json_pointer_segment, unescape_json_pointer_segment};
+
+// The message of the error used to interrupt the deserialization of a document once the node to locate has been reached
+const LOCATED_MARKER: &str = "__serverless_workflow_located__";
+
+/// Enumerates all supported workflow definition formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocumentFormat{
+    /// Indicates a JSON document
+    Json,
+    /// Indicates a YAML document
+    Yaml
+}
+
+/// Represents an error that occurred while parsing a workflow definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError{
+
+    /// Gets/sets the JSON pointer to the node of the document that caused the error. Empty if the error concerns the whole document
+    pub pointer: String,
+
+    /// Gets/sets the 1-based line, if known, of the document at which the error occurred
+    pub line: Option<usize>,
+
+    /// Gets/sets the 1-based column, if known, of the document at which the error occurred
+    pub column: Option<usize>,
+
+    /// Gets/sets the keywords, if any, that were expected at the location of the error, such as the supported task types
+    pub expected: Vec<String>,
+
+    /// Gets/sets a human-readable message that describes the error
+    pub message: String
+
+}
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.pointer.is_empty(){
+            write!(f, "{}", self.message)?;
+        }
+        else{
+            write!(f, "{}: {}", self.pointer, self.message)?;
+        }
+        if !self.expected.is_empty(){
+            write!(f, " (expected one of: {})", self.expected.join(", "))?;
+        }
+        if let (Some(line), Some(column)) = (self.line, self.column){
+            write!(f, " at line {} column {}", line, column)?;
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for ParseError {}
+
+/// Parses the workflow definition contained in the specified document
+pub fn parse_workflow(document: &str, format: DocumentFormat) -> Result<WorkflowDefinition, ParseError>{
+    let value: Value = match format {
+        DocumentFormat::Json => serde_json::from_str(document).map_err(|error| ParseError {
+            pointer: String::new(),
+            line: Some(error.line()),
+            column: Some(error.column()),
+            expected: Vec::new(),
+            message: strip_position(&error.to_string())
+        })?,
+        DocumentFormat::Yaml => serde_yaml::from_str(document).map_err(|error| ParseError {
+            pointer: String::new(),
+            line: error.location().map(|location| location.line()),
+            column: error.location().map(|location| location.column()),
+            expected: Vec::new(),
+            message: strip_position(&error.to_string())
+        })?
+    };
+    let error = match serde_path_to_error::deserialize::<_, WorkflowDefinition>(&value) {
+        Ok(workflow) => return Ok(workflow),
+        Err(error) => error
+    };
+    let mut located = find_task_error(&value).unwrap_or_else(|| LocatedError {
+        pointer: to_json_pointer("", error.path()),
+        expected: Vec::new(),
+        message: error.inner().to_string()
+    });
+    if located.message.is_empty(){
+        located.message = error.inner().to_string();
+    }
+    let position = locate(document, format, &located.pointer);
+    Err(ParseError {
+        pointer: located.pointer,
+        line: position.map(|(line, _)| line),
+        column: position.map(|(_, column)| column),
+        expected: located.expected,
+        message: located.message
+    })
+}
+
+// Represents an error that has been located in a workflow definition document
+struct LocatedError{
+    pointer: String,
+    expected: Vec<String>,
+    message: String
+}
+
+// Removes the position, if any, that serde appends to error messages
+fn strip_position(message: &str) -> String{
+    match message.rfind(" at line ") {
+        Some(index) => message[..index].to_string(),
+        None => message.to_string()
+    }
+}
+
+// Converts the specified serde path into a JSON pointer relative to the specified base
+fn to_json_pointer(base: &str, path: &serde_path_to_error::Path) -> String{
+    let mut pointer = base.to_string();
+    for segment in path{
+        match segment {
+            serde_path_to_error::Segment::Seq { index } => pointer.push_str(&format!("/{}", index)),
+            serde_path_to_error::Segment::Map { key } => pointer.push_str(&format!("/{}", escape_json_pointer_segment(key))),
+            serde_path_to_error::Segment::Enum { .. } => {},
+            serde_path_to_error::Segment::Unknown => break
+        }
+    }
+    pointer
+}
+
+// Finds the first invalid task, if any, of the specified workflow definition
+fn find_task_error(workflow: &Value) -> Option<LocatedError>{
+    if let Some(functions) = workflow.pointer("/use/functions").and_then(|functions| functions.as_object()){
+        for (name, function) in functions{
+            let pointer = format!("/use/functions/{}", escape_json_pointer_segment(name));
+            if let Err(error) = check_task(&pointer, function){
+                return Some(error);
+            }
+        }
+    }
+    if let Some(extensions) = workflow.pointer("/use/extensions").and_then(|extensions| extensions.as_array()){
+        for (index, entry) in extensions.iter().enumerate(){
+            for (name, extension) in entry.as_object().into_iter().flatten(){
+                let pointer = format!("/use/extensions/{}/{}", index, escape_json_pointer_segment(name));
+                for list in ["before", "after"]{
+                    if let Err(error) = check_task_list(&format!("{}/{}", pointer, list), extension.get(list)){
+                        return Some(error);
+                    }
+                }
+                if let Err(error) = check_type::<ExtensionDefinition>(&pointer, extension){
+                    return Some(error);
+                }
+            }
+        }
+    }
+    check_task_list("/do", workflow.get("do")).err()
+}
+
+// Checks the specified task list, if any
+fn check_task_list(pointer: &str, tasks: Option<&Value>) -> Result<(), LocatedError>{
+    let Some(tasks) = tasks.and_then(|tasks| tasks.as_array()) else {
+        return Ok(());
+    };
+    for (index, entry) in tasks.iter().enumerate(){
+        for (name, task) in entry.as_object().into_iter().flatten(){
+            check_task(&format!("{}/{}/{}", pointer, index, escape_json_pointer_segment(name)), task)?;
+        }
+    }
+    Ok(())
+}
+
+// Checks the specified task, starting with its subtasks so that errors are reported at the deepest possible location
+fn check_task(pointer: &str, task: &Value) -> Result<(), LocatedError>{
+    let Some(task_type) = TaskDefinition::detect_type(task) else {
+        return Err(LocatedError {
+            pointer: pointer.to_string(),
+            expected: TaskType::VALUES.iter().map(|task_type| task_type.to_string()).collect(),
+            message: "unknown task type".to_string()
+        });
+    };
+    match task_type {
+        TaskType::Call => check_type::<CallTaskDefinition>(pointer, task),
+        TaskType::Do => {
+            check_task_list(&format!("{}/do", pointer), task.get("do"))?;
+            check_type::<DoTaskDefinition>(pointer, task)
+        },
+        TaskType::Emit => check_type::<EmitTaskDefinition>(pointer, task),
+        TaskType::For => {
+            check_task_list(&format!("{}/do", pointer), task.get("do"))?;
+            check_type::<ForTaskDefinition>(pointer, task)
+        },
+        TaskType::Fork => {
+            check_task_list(&format!("{}/fork/branches", pointer), task.pointer("/fork/branches"))?;
+            check_type::<ForkTaskDefinition>(pointer, task)
+        },
+        TaskType::Listen => {
+            check_task_list(&format!("{}/foreach/do", pointer), task.pointer("/foreach/do"))?;
+            check_type::<ListenTaskDefinition>(pointer, task)
+        },
+        TaskType::Raise => check_type::<RaiseTaskDefinition>(pointer, task),
+        TaskType::Run => check_type::<RunTaskDefinition>(pointer, task),
+        TaskType::Set => check_type::<SetTaskDefinition>(pointer, task),
+        TaskType::Switch => check_type::<SwitchTaskDefinition>(pointer, task),
+        TaskType::Try => {
+            check_task_list(&format!("{}/try", pointer), task.get("try"))?;
+            check_task_list(&format!("{}/catch/do", pointer), task.pointer("/catch/do"))?;
+            check_type::<TryTaskDefinition>(pointer, task)
+        },
+        TaskType::Wait => check_type::<WaitTaskDefinition>(pointer, task)
+    }
+}
+
+// Checks that the specified value can be deserialized into the specified type
+fn check_type<T: DeserializeOwned>(pointer: &str, value: &Value) -> Result<(), LocatedError>{
+    serde_path_to_error::deserialize::<_, T>(value)
+        .map(|_| ())
+        .map_err(|error| LocatedError {
+            pointer: to_json_pointer(pointer, error.path()),
+            expected: Vec::new(),
+            message: error.inner().to_string()
+        })
+}
+
+// Gets the 1-based line and column of the node referenced by the specified JSON pointer
+fn locate(document: &str, format: DocumentFormat, pointer: &str) -> Option<(usize, usize)>{
+    let tokens: Vec<String> = pointer
+        .split('/')
+        .skip(1)
+        .map(unescape_json_pointer_segment)
+        .collect();
+    let locator = NodeLocator { tokens: &tokens };
+    match format {
+        DocumentFormat::Json => {
+            let mut deserializer = serde_json::Deserializer::from_str(document);
+            match locator.deserialize(&mut deserializer) {
+                Err(error) if error.to_string().contains(LOCATED_MARKER) => Some((error.line(), error.column())),
+                _ => None
+            }
+        },
+        DocumentFormat::Yaml => {
+            match locator.deserialize(serde_yaml::Deserializer::from_str(document)) {
+                Err(error) if error.to_string().contains(LOCATED_MARKER) => error.location().map(|location| (location.line(), location.column())),
+                _ => None
+            }
+        }
+    }
+}
+
+// Represents the service used to walk a document until it reaches the node at the specified path, at which point it fails so that the underlying deserializer reports its position
+struct NodeLocator<'a>{
+    tokens: &'a [String]
+}
+impl NodeLocator<'_>{
+
+    // Fails if the locator reached the node to locate
+    fn visit_scalar<E: de::Error>(self) -> Result<(), E>{
+        if self.tokens.is_empty(){
+            Err(E::custom(LOCATED_MARKER))
+        }
+        else{
+            Ok(())
+        }
+    }
+
+}
+impl<'de> DeserializeSeed<'de> for NodeLocator<'_>{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where D: de::Deserializer<'de>{
+        deserializer.deserialize_any(self)
+    }
+}
+impl<'de> Visitor<'de> for NodeLocator<'_>{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E: de::Error>(self, _: bool) -> Result<(), E>{
+        self.visit_scalar()
+    }
+
+    fn visit_i64<E: de::Error>(self, _: i64) -> Result<(), E>{
+        self.visit_scalar()
+    }
+
+    fn visit_u64<E: de::Error>(self, _: u64) -> Result<(), E>{
+        self.visit_scalar()
+    }
+
+    fn visit_f64<E: de::Error>(self, _: f64) -> Result<(), E>{
+        self.visit_scalar()
+    }
+
+    fn visit_str<E: de::Error>(self, _: &str) -> Result<(), E>{
+        self.visit_scalar()
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<(), E>{
+        self.visit_scalar()
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<(), E>{
+        self.visit_scalar()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<(), D::Error>
+    where D: de::Deserializer<'de>{
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<(), A::Error>
+    where A: SeqAccess<'de>{
+        let Some((head, tail)) = self.tokens.split_first() else {
+            return Err(de::Error::custom(LOCATED_MARKER));
+        };
+        let mut index = 0;
+        loop {
+            if head == &index.to_string(){
+                if seq.next_element_seed(NodeLocator { tokens: tail })?.is_none(){
+                    break;
+                }
+            }
+            else if seq.next_element::<IgnoredAny>()?.is_none(){
+                break;
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<(), A::Error>
+    where A: MapAccess<'de>{
+        let Some((head, tail)) = self.tokens.split_first() else {
+            return Err(de::Error::custom(LOCATED_MARKER));
+        };
+        while let Some(key) = map.next_key::<Value>()?{
+            let key = match key {
+                Value::String(key) => key,
+                key => key.to_string()
+            };
+            if &key == head{
+                map.next_value_seed(NodeLocator { tokens: tail })?;
+            }
+            else{
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,9 @@
+/// Escapes the specified value for use as a JSON pointer reference token, as defined by RFC 6901
+pub fn escape_json_pointer_segment(segment: &str) -> String{
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Unescapes the specified JSON pointer reference token, as defined by RFC 6901
+pub fn unescape_json_pointer_segment(segment: &str) -> String{
+    segment.replace("~1", "/").replace("~0", "~")
+}
@@ -0,0 +1,168 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use crate::models::retry::*;
+
+/// Defines the fundamentals of a source of randomness, used to compute the jitter of retry delays
+///
+/// Closures returning an f64 implement this trait, which allows injecting deterministic values, for instance in tests.
+pub trait RandomSource{
+
+    /// Gets the next random number, in the [0, 1) range
+    fn next_f64(&mut self) -> f64;
+
+}
+impl<F> RandomSource for F where F: FnMut() -> f64{
+    fn next_f64(&mut self) -> f64{
+        self()
+    }
+}
+
+/// Represents the default RandomSource, a xorshift generator seeded from the random keys of the standard library's hasher
+#[derive(Debug, Clone)]
+pub struct DefaultRandomSource{
+    state: u64
+}
+impl DefaultRandomSource{
+
+    /// Initializes a new, randomly seeded, DefaultRandomSource
+    pub fn new() -> Self{
+        Self::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    /// Initializes a new DefaultRandomSource with the specified seed
+    pub fn with_seed(seed: u64) -> Self{
+        Self { state: seed.max(1) }
+    }
+
+}
+impl Default for DefaultRandomSource{
+    fn default() -> Self{
+        Self::new()
+    }
+}
+impl RandomSource for DefaultRandomSource{
+    fn next_f64(&mut self) -> f64{
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        (self.state >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+// Gets the factor by which an exponential backoff multiplies the delay after each retry attempt, which the specification does not make configurable
+const EXPONENTIAL_BACKOFF_FACTOR: f64 = 2.0;
+
+/// Represents an iterator over the delays to wait before each retry attempt of a retry policy
+///
+/// The delay before the nth retry attempt is computed from the policy's 'delay' (zero if not set) and backoff strategy:
+/// - constant, or no strategy: 'delay'
+/// - linear: 'delay' + (n - 1) * 'increment', where 'increment' defaults to 'delay'
+/// - exponential: 'delay' * 2 ^ (n - 1), the specification not defining any configurable factor
+///
+/// A random duration in the range of the policy's jitter, if any, is then added to the delay.
+///
+/// The iterator ends once 'limit.attempt.count' retry attempts have been scheduled, or when waiting for the next delay would exceed 'limit.duration'.
+/// 'limit.duration' is compared against the sum of the delays yielded so far and of the running time recorded with 'record_elapsed'. Without limits, the iterator never ends.
+/// 'limit.attempt.duration' bounds each attempt individually rather than the total, and is exposed by 'attempt_timeout' for runtimes to enforce.
+#[derive(Debug, Clone)]
+pub struct RetryDelays<'a, R: RandomSource = DefaultRandomSource>{
+    policy: &'a RetryPolicyDefinition,
+    random: R,
+    attempt: u32,
+    elapsed: Duration
+}
+impl<'a> RetryDelays<'a>{
+
+    /// Initializes a new RetryDelays for the specified policy, using the default source of randomness
+    pub fn new(policy: &'a RetryPolicyDefinition) -> Self{
+        Self::with_random(policy, DefaultRandomSource::new())
+    }
+
+}
+impl<'a, R: RandomSource> RetryDelays<'a, R>{
+
+    /// Initializes a new RetryDelays for the specified policy, using the specified source of randomness to compute jitter
+    pub fn with_random(policy: &'a RetryPolicyDefinition, random: R) -> Self{
+        Self {
+            policy,
+            random,
+            attempt: 0,
+            elapsed: Duration::ZERO
+        }
+    }
+
+    /// Gets the number of retry attempts scheduled so far
+    pub fn attempt(&self) -> u32{
+        self.attempt
+    }
+
+    /// Gets the time elapsed so far, that is the sum of the delays yielded and of the recorded running time
+    pub fn elapsed(&self) -> Duration{
+        self.elapsed
+    }
+
+    /// Records the specified time spent running the task, so that it counts towards the policy's duration limits
+    pub fn record_elapsed(&mut self, duration: Duration){
+        self.elapsed = self.elapsed.saturating_add(duration);
+    }
+
+    /// Gets the delay, without jitter, to wait before the specified retry attempt, starting at 1
+    pub fn base_delay(&self, attempt: u32) -> Duration{
+        let delay = self.policy.delay.as_ref().map(|delay| delay.to_std()).unwrap_or_default();
+        let steps = attempt.saturating_sub(1);
+        let Some(backoff) = &self.policy.backoff else { return delay };
+        if let Some(linear) = &backoff.linear{
+            let increment = linear.increment.as_ref().map(|increment| increment.to_std()).unwrap_or(delay);
+            return delay.saturating_add(increment.saturating_mul(steps));
+        }
+        if backoff.exponential.is_some(){
+            let factor = EXPONENTIAL_BACKOFF_FACTOR.powi(steps.min(i32::MAX as u32) as i32);
+            return Duration::try_from_secs_f64(delay.as_secs_f64() * factor).unwrap_or(Duration::MAX);
+        }
+        delay
+    }
+
+    /// Gets the maximum duration, if any, of each individual retry attempt, as defined by 'limit.attempt.duration'
+    pub fn attempt_timeout(&self) -> Option<Duration>{
+        self.policy.limit.as_ref()?.attempt.as_ref()?.duration.as_ref().map(|duration| duration.to_std())
+    }
+
+    // Computes a random jitter, in the range defined by the policy, if any
+    fn jitter(&mut self) -> Duration{
+        let Some(jitter) = &self.policy.jitter else { return Duration::ZERO };
+        let from = jitter.from.to_std();
+        let to = jitter.to.to_std();
+        if to <= from{
+            return from;
+        }
+        let random = self.random.next_f64();
+        let random = if random.is_finite() { random.clamp(0.0, 1.0) } else { 0.0 };
+        from + (to - from).mul_f64(random)
+    }
+
+    // Gets the maximum duration, if any, during which to retry, as defined by 'limit.duration'
+    fn duration_limit(&self) -> Option<Duration>{
+        self.policy.limit.as_ref()?.duration.as_ref().map(|duration| duration.to_std())
+    }
+
+}
+impl<R: RandomSource> Iterator for RetryDelays<'_, R>{
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration>{
+        let count = self.policy.limit.as_ref().and_then(|limit| limit.attempt.as_ref()).and_then(|attempt| attempt.count);
+        if count.is_some_and(|count| self.attempt >= count as u32){
+            return None;
+        }
+        let delay = self.base_delay(self.attempt + 1).saturating_add(self.jitter());
+        let elapsed = self.elapsed.saturating_add(delay);
+        if self.duration_limit().is_some_and(|limit| elapsed > limit){
+            return None;
+        }
+        self.attempt += 1;
+        self.elapsed = elapsed;
+        Some(delay)
+    }
+
+}
@@ -0,0 +1,127 @@
+use serde_json::{json, Value};
+use std::fmt;
+use crate::models::error::*;
+use crate::models::input::*;
+use crate::models::output::*;
+use crate::models::schema::*;
+
+/// Gets the type of the errors raised when data does not match the schema it must conform to
+pub const VALIDATION_ERROR_TYPE: &str = "https://serverlessworkflow.io/spec/1.0.0/errors/validation";
+
+/// Represents a violation of a schema by the validated data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaViolation{
+
+    /// Gets/sets the path to the node of the validated data that violates the schema, as a JSON pointer or, for XML documents, an XPath. Empty if the violation concerns the whole data
+    pub instance_path: String,
+
+    /// Gets/sets the path to the keyword of the schema that is violated, as a JSON pointer or, for XML schemas, an XPath
+    pub schema_path: String,
+
+    /// Gets/sets a human-readable message that describes the violation
+    pub message: String
+
+}
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.instance_path.is_empty(){
+            write!(f, "{}", self.message)
+        }
+        else{
+            write!(f, "{}: {}", self.instance_path, self.message)
+        }
+    }
+}
+
+/// Represents the error returned when data cannot be validated against a schema, or does not conform to it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaValidationError{
+
+    /// Gets/sets a human-readable message that describes the error
+    pub message: String,
+
+    /// Gets/sets the violations of the schema by the validated data. Empty if the data could not be validated, for instance because the schema is invalid
+    pub violations: Vec<SchemaViolation>
+
+}
+impl SchemaValidationError{
+
+    /// Initializes a new SchemaValidationError
+    pub fn new(message: &str, violations: Vec<SchemaViolation>) -> Self{
+        Self {
+            message: message.to_string(),
+            violations
+        }
+    }
+
+    /// Converts the error into an error of the specification's 'validation' type, which occurred at the specified instance, if any
+    pub fn to_error_definition(&self, instance: Option<&str>) -> ErrorDefinition{
+        ErrorDefinition::new(VALIDATION_ERROR_TYPE, "Validation Error", json!(400), Some(self.to_string()), instance.map(str::to_string))
+    }
+
+}
+impl fmt::Display for SchemaValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for violation in &self.violations{
+            write!(f, "; {}", violation)?;
+        }
+        Ok(())
+    }
+}
+impl std::error::Error for SchemaValidationError {}
+
+/// Validates the specified value against the specified schema
+///
+/// Only inline schemas are supported: external resources must be resolved, and their content set as the schema's 'document', beforehand.
+/// JSON schemas are always supported, Avro schemas require the 'avro' feature, and XML schemas (XSD) require the 'xml' feature.
+pub fn validate_schema(schema: &SchemaDefinition, value: &Value) -> Result<(), SchemaValidationError>{
+    let document = match &schema.document{
+        Some(document) => document,
+        None if schema.resource.is_some() => return Err(SchemaValidationError::new("external schema resources must be resolved before validation", Vec::new())),
+        None => return Err(SchemaValidationError::new("the schema does not define a document", Vec::new()))
+    };
+    match &schema.format.format{
+        SchemaFormat::Json => validate_json_schema(document, value),
+        #[cfg(feature = "avro")]
+        SchemaFormat::Avro => crate::services::avro::validate_avro_schema(document, value),
+        #[cfg(feature = "xml")]
+        SchemaFormat::Xml => crate::services::xml::validate_xml_schema(document, value),
+        format => Err(SchemaValidationError::new(&format!("unsupported schema format '{}'", format.as_str()), Vec::new()))
+    }
+}
+
+/// Validates the specified input data against the schema, if any, of the specified input definition
+pub fn validate_input(definition: &InputDataModelDefinition, value: &Value) -> Result<(), SchemaValidationError>{
+    match &definition.schema{
+        Some(schema) => validate_schema(schema, value),
+        None => Ok(())
+    }
+}
+
+/// Validates the specified output data against the schema, if any, of the specified output definition
+pub fn validate_output(definition: &OutputDataModelDefinition, value: &Value) -> Result<(), SchemaValidationError>{
+    match &definition.schema{
+        Some(schema) => validate_schema(schema, value),
+        None => Ok(())
+    }
+}
+
+// Validates the specified value against the specified JSON schema document
+fn validate_json_schema(document: &Value, value: &Value) -> Result<(), SchemaValidationError>{
+    let validator = jsonschema::validator_for(document)
+        .map_err(|error| SchemaValidationError::new(&format!("invalid JSON schema: {}", error), Vec::new()))?;
+    let violations: Vec<SchemaViolation> = validator.iter_errors(value)
+        .map(|error| SchemaViolation {
+            instance_path: error.instance_path().as_str().to_string(),
+            schema_path: error.schema_path().as_str().to_string(),
+            message: error.to_string()
+        })
+        .collect();
+    if violations.is_empty(){
+        Ok(())
+    }
+    else{
+        Err(SchemaValidationError::new("the data does not conform to its JSON schema", violations))
+    }
+}
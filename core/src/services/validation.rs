@@ -0,0 +1,426 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fmt;
+use crate::models::authentication::*;
+use crate::models::call::*;
+use crate::models::duration::*;
+use crate::models::enumeration::*;
+use crate::models::error::*;
+use crate::models::input::*;
+use crate::models::map::*;
+use crate::models::output::*;
+use crate::models::resource::*;
+use crate::models::retry::*;
+use crate::models::task::*;
+use crate::models::timeout::*;
+use crate::models::workflow::*;
+use crate::services::pointer::escape_json_pointer_segment;
+use crate::services::visitor::{self, *};
+
+string_enum! {
+    /// Enumerates all the codes of the diagnostics produced by the workflow validator
+    pub enum ValidationCode {
+        /// Indicates that a task list contains two or more tasks with the same name
+        DuplicateTaskName => "duplicate-task-name",
+        /// Indicates that a task list does not contain any task
+        EmptyTaskList => "empty-task-list",
+        /// Indicates that an ISO 8601 duration expression is malformed
+        InvalidDuration => "invalid-duration",
+        /// Indicates that the arguments of a call to a natively supported function are invalid
+        InvalidCallArguments => "invalid-call-arguments",
+        /// Indicates that a runtime expression is syntactically invalid
+        InvalidExpression => "invalid-expression",
+        /// Indicates that a flow directive references a task that does not exist
+        UndefinedTask => "undefined-task",
+        /// Indicates that a reference points to an authentication policy that is not defined in `use.authentications`
+        UndefinedAuthentication => "undefined-authentication",
+        /// Indicates that a reference points to an error that is not defined in `use.errors`
+        UndefinedError => "undefined-error",
+        /// Indicates that a reference points to a retry policy that is not defined in `use.retries`
+        UndefinedRetryPolicy => "undefined-retry-policy",
+        /// Indicates that a reference points to a timeout that is not defined in `use.timeouts`
+        UndefinedTimeout => "undefined-timeout",
+        /// Indicates that runtime expressions cannot be checked because they are written in a language the evaluator does not support
+        UnsupportedExpressionLanguage => "unsupported-expression-language",
+        /// Indicates that a reusable component is defined in `use` but never referenced
+        UnusedComponent => "unused-component"
+    }
+}
+
+/// Enumerates all supported validation severities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ValidationSeverity{
+    /// Indicates a problem that makes the workflow definition invalid
+    #[serde(rename = "error")]
+    Error,
+    /// Indicates a potential problem that does not make the workflow definition invalid
+    #[serde(rename = "warning")]
+    Warning
+}
+impl fmt::Display for ValidationSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationSeverity::Error => write!(f, "error"),
+            ValidationSeverity::Warning => write!(f, "warning")
+        }
+    }
+}
+
+/// Represents a diagnostic produced while validating a workflow definition
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationDiagnostic{
+
+    /// Gets/sets the JSON pointer to the node of the workflow definition the diagnostic concerns
+    #[serde(rename = "path")]
+    pub path: String,
+
+    /// Gets/sets the code of the diagnostic
+    #[serde(rename = "code")]
+    pub code: ValidationCode,
+
+    /// Gets/sets the diagnostic's severity
+    #[serde(rename = "severity")]
+    pub severity: ValidationSeverity,
+
+    /// Gets/sets a human-readable message that describes the diagnostic
+    #[serde(rename = "message")]
+    pub message: String
+
+}
+impl ValidationDiagnostic{
+
+    /// Initializes a new error ValidationDiagnostic
+    pub fn error(path: &str, code: ValidationCode, message: String) -> Self{
+        Self {
+            path: path.to_string(),
+            code,
+            severity: ValidationSeverity::Error,
+            message
+        }
+    }
+
+    /// Initializes a new warning ValidationDiagnostic
+    pub fn warning(path: &str, code: ValidationCode, message: String) -> Self{
+        Self {
+            path: path.to_string(),
+            code,
+            severity: ValidationSeverity::Warning,
+            message
+        }
+    }
+
+    /// Determines whether or not the diagnostic is an error
+    pub fn is_error(&self) -> bool{
+        self.severity == ValidationSeverity::Error
+    }
+
+}
+impl fmt::Display for ValidationDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} [{}] {}: {}", self.severity, self.code, self.path, self.message)
+    }
+}
+
+/// Validates the semantics of the specified workflow definition
+///
+/// Returns all the diagnostics produced during validation. The workflow is valid if none of them is an error.
+pub fn validate(workflow: &WorkflowDefinition) -> Vec<ValidationDiagnostic>{
+    let mut validator = WorkflowValidator::new(workflow);
+    validator.validate();
+    validator.diagnostics
+}
+
+// Represents the service used to walk a workflow definition and to collect validation diagnostics
+struct WorkflowValidator<'a>{
+    workflow: &'a WorkflowDefinition,
+    diagnostics: Vec<ValidationDiagnostic>,
+    referenced_authentications: HashSet<String>,
+    referenced_errors: HashSet<String>,
+    referenced_retries: HashSet<String>,
+    referenced_timeouts: HashSet<String>
+}
+impl<'a> WorkflowValidator<'a>{
+
+    // Initializes a new WorkflowValidator
+    fn new(workflow: &'a WorkflowDefinition) -> Self{
+        Self {
+            workflow,
+            diagnostics: Vec::new(),
+            referenced_authentications: HashSet::new(),
+            referenced_errors: HashSet::new(),
+            referenced_retries: HashSet::new(),
+            referenced_timeouts: HashSet::new()
+        }
+    }
+
+    // Validates the workflow definition
+    fn validate(&mut self){
+        let workflow = self.workflow;
+        if let Some(timeout) = &workflow.timeout{
+            self.validate_timeout("/timeout", timeout);
+        }
+        self.validate_input("/input", workflow.input.as_ref());
+        self.validate_output("/output", workflow.output.as_ref());
+        if let Some(components) = &workflow.use_{
+            if let Some(timeouts) = &components.timeouts{
+                let mut names: Vec<&String> = timeouts.keys().collect();
+                names.sort();
+                for name in names{
+                    let path = format!("/use/timeouts/{}/after", escape_json_pointer_segment(name));
+                    self.validate_duration(&path, &timeouts[name].after);
+                }
+            }
+            if let Some(catalogs) = &components.catalogs{
+                let mut names: Vec<&String> = catalogs.keys().collect();
+                names.sort();
+                for name in names{
+                    let path = format!("/use/catalogs/{}/endpoint", escape_json_pointer_segment(name));
+                    self.validate_endpoint(&path, &catalogs[name].endpoint);
+                }
+            }
+        }
+        visitor::walk(workflow, self);
+        self.validate_unused_components();
+    }
+
+    // Validates the specified task list
+    fn validate_task_list(&mut self, path: &str, tasks: &Map<String, TaskDefinition>){
+        if tasks.entries.is_empty(){
+            self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::EmptyTaskList, "The task list must contain at least one task".to_string()));
+            return;
+        }
+        let mut names = HashSet::new();
+        for (index, entry) in tasks.entries.iter().enumerate(){
+            for name in entry.keys(){
+                if !names.insert(name.as_str()){
+                    let task_path = format!("{}/{}/{}", path, index, escape_json_pointer_segment(name));
+                    self.diagnostics.push(ValidationDiagnostic::error(&task_path, ValidationCode::DuplicateTaskName, format!("A task named '{}' is already defined in the same task list", name)));
+                }
+            }
+        }
+        for (index, entry) in tasks.entries.iter().enumerate(){
+            for (name, task) in entry{
+                let task_path = format!("{}/{}/{}", path, index, escape_json_pointer_segment(name));
+                if let Some(then) = &task.common().then{
+                    self.validate_flow_directive(&format!("{}/then", task_path), then, &names);
+                }
+                if let TaskDefinition::Switch(switch) = task{
+                    for (case_index, case_entry) in switch.switch.entries.iter().enumerate(){
+                        for (case_name, case) in case_entry{
+                            if let Some(then) = &case.then{
+                                let case_path = format!("{}/switch/{}/{}/then", task_path, case_index, escape_json_pointer_segment(case_name));
+                                self.validate_flow_directive(&case_path, then, &names);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Validates the specified flow directive against the names of the tasks it can transition to
+    fn validate_flow_directive(&mut self, path: &str, directive: &FlowDirective, siblings: &HashSet<&str>){
+        let Some(directive) = directive.task_name() else {
+            return;
+        };
+        if siblings.contains(directive){
+            return;
+        }
+        self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedTask, format!("The flow directive references an undefined task '{}'", directive)));
+    }
+
+    // Validates the specified task, excluding its subtasks
+    fn validate_task(&mut self, path: &str, task: &TaskDefinition){
+        let common = task.common();
+        if let Some(timeout) = &common.timeout{
+            self.validate_timeout(&format!("{}/timeout", path), timeout);
+        }
+        self.validate_input(&format!("{}/input", path), common.input.as_ref());
+        self.validate_output(&format!("{}/output", path), common.output.as_ref());
+        self.validate_output(&format!("{}/export", path), common.export.as_ref());
+        match task{
+            TaskDefinition::Call(call) => {
+                self.validate_call_arguments(&format!("{}/with", path), call);
+                for (authentication_path, authentication) in call.authentications(){
+                    self.validate_authentication_value(&format!("{}/with{}", path, authentication_path), authentication);
+                }
+            },
+            TaskDefinition::For(for_) => self.validate_input(&format!("{}/for/input", path), for_.for_.input.as_ref()),
+            TaskDefinition::Listen(listen) => {
+                if let Some(foreach) = &listen.foreach{
+                    self.validate_output(&format!("{}/foreach/output", path), foreach.output.as_ref());
+                    self.validate_output(&format!("{}/foreach/export", path), foreach.export.as_ref());
+                }
+            },
+            TaskDefinition::Raise(raise) => {
+                if let OneOfErrorDefinitionOrReference::Reference(reference) = &raise.raise.error{
+                    self.validate_error_reference(&format!("{}/raise/error", path), reference);
+                }
+            },
+            TaskDefinition::Run(run) => {
+                if let Some(source) = run.run.script.as_ref().and_then(|script| script.source.as_ref()){
+                    self.validate_endpoint(&format!("{}/run/script/source/endpoint", path), &source.endpoint);
+                }
+            },
+            TaskDefinition::Try(try_) => {
+                if let Some(OneOfRetryPolicyDefinitionOrReference::Reference(reference)) = &try_.catch.retry{
+                    self.validate_retry_reference(&format!("{}/catch/retry", path), reference);
+                }
+            },
+            TaskDefinition::Wait(wait) => self.validate_duration(&format!("{}/wait", path), &wait.wait),
+            TaskDefinition::Do(_) | TaskDefinition::Emit(_) | TaskDefinition::Fork(_) | TaskDefinition::Set(_) | TaskDefinition::Switch(_) => {}
+        }
+    }
+
+    // Validates the specified input data model definition
+    fn validate_input(&mut self, path: &str, input: Option<&InputDataModelDefinition>){
+        if let Some(resource) = input.and_then(|input| input.schema.as_ref()).and_then(|schema| schema.resource.as_ref()){
+            self.validate_endpoint(&format!("{}/schema/resource/endpoint", path), &resource.endpoint);
+        }
+    }
+
+    // Validates the specified output data model definition
+    fn validate_output(&mut self, path: &str, output: Option<&OutputDataModelDefinition>){
+        if let Some(resource) = output.and_then(|output| output.schema.as_ref()).and_then(|schema| schema.resource.as_ref()){
+            self.validate_endpoint(&format!("{}/schema/resource/endpoint", path), &resource.endpoint);
+        }
+    }
+
+    // Validates the specified endpoint
+    fn validate_endpoint(&mut self, path: &str, endpoint: &OneOfEndpointDefinitionOrUri){
+        if let OneOfEndpointDefinitionOrUri::Endpoint(endpoint) = endpoint{
+            if let Some(authentication) = &endpoint.authentication{
+                self.validate_authentication(&format!("{}/authentication", path), authentication);
+            }
+        }
+    }
+
+    // Validates the specified authentication policy
+    fn validate_authentication(&mut self, path: &str, authentication: &AuthenticationPolicyDefinition){
+        if let Some(reference) = &authentication.use_{
+            self.validate_authentication_reference(&format!("{}/use", path), reference);
+        }
+    }
+
+    // Validates the specified untyped authentication policy, as found in call arguments
+    fn validate_authentication_value(&mut self, path: &str, authentication: &Value){
+        if let Some(reference) = authentication.get("use").and_then(|reference| reference.as_str()){
+            self.validate_authentication_reference(&format!("{}/use", path), reference);
+        }
+    }
+
+    // Validates the arguments of the specified call, if it targets a natively supported function
+    fn validate_call_arguments(&mut self, path: &str, call: &CallTaskDefinition){
+        let result = match CallFunction::from(call.call.clone()) {
+            CallFunction::Http => call.as_http().err(),
+            CallFunction::Grpc => call.as_grpc().err(),
+            CallFunction::OpenApi => call.as_openapi().err(),
+            CallFunction::AsyncApi => call.as_asyncapi().err(),
+            #[cfg(feature = "agents")]
+            CallFunction::A2a => call.as_a2a().err(),
+            #[cfg(feature = "agents")]
+            CallFunction::Mcp => call.as_mcp().err(),
+            _ => None
+        };
+        if let Some(error) = result{
+            self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::InvalidCallArguments, error.to_string()));
+        }
+    }
+
+    // Validates the specified timeout
+    fn validate_timeout(&mut self, path: &str, timeout: &OneOfTimeoutDefinitionOrReference){
+        if let OneOfTimeoutDefinitionOrReference::Timeout(timeout) = timeout{
+            self.validate_duration(&format!("{}/after", path), &timeout.after);
+        }
+        if let OneOfTimeoutDefinitionOrReference::Reference(reference) = timeout{
+            self.referenced_timeouts.insert(reference.clone());
+            let defined = self.workflow.use_.as_ref()
+                .and_then(|components| components.timeouts.as_ref())
+                .is_some_and(|timeouts| timeouts.contains_key(reference));
+            if !defined{
+                self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedTimeout, format!("The timeout '{}' is not defined in 'use.timeouts'", reference)));
+            }
+        }
+    }
+
+    // Validates the specified duration, if it is defined using an ISO 8601 expression
+    fn validate_duration(&mut self, path: &str, duration: &OneOfDurationOrIso8601Expression){
+        if let OneOfDurationOrIso8601Expression::Iso8601Expression(expression) = duration{
+            if let Err(error) = Duration::parse_iso8601(expression){
+                self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::InvalidDuration, error.to_string()));
+            }
+        }
+    }
+
+    // Validates the specified reference to a reusable authentication policy
+    fn validate_authentication_reference(&mut self, path: &str, reference: &str){
+        self.referenced_authentications.insert(reference.to_string());
+        let defined = self.workflow.use_.as_ref()
+            .and_then(|components| components.authentications.as_ref())
+            .is_some_and(|authentications| authentications.contains_key(reference));
+        if !defined{
+            self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedAuthentication, format!("The authentication policy '{}' is not defined in 'use.authentications'", reference)));
+        }
+    }
+
+    // Validates the specified reference to a reusable error
+    fn validate_error_reference(&mut self, path: &str, reference: &str){
+        self.referenced_errors.insert(reference.to_string());
+        let defined = self.workflow.use_.as_ref()
+            .and_then(|components| components.errors.as_ref())
+            .is_some_and(|errors| errors.contains_key(reference));
+        if !defined{
+            self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedError, format!("The error '{}' is not defined in 'use.errors'", reference)));
+        }
+    }
+
+    // Validates the specified reference to a reusable retry policy
+    fn validate_retry_reference(&mut self, path: &str, reference: &str){
+        self.referenced_retries.insert(reference.to_string());
+        let defined = self.workflow.use_.as_ref()
+            .and_then(|components| components.retries.as_ref())
+            .is_some_and(|retries| retries.contains_key(reference));
+        if !defined{
+            self.diagnostics.push(ValidationDiagnostic::error(path, ValidationCode::UndefinedRetryPolicy, format!("The retry policy '{}' is not defined in 'use.retries'", reference)));
+        }
+    }
+
+    // Reports the reusable components that are defined but never referenced
+    fn validate_unused_components(&mut self){
+        let Some(components) = &self.workflow.use_ else {
+            return;
+        };
+        let mut unused = Vec::new();
+        if let Some(authentications) = &components.authentications{
+            unused.extend(authentications.keys().filter(|name| !self.referenced_authentications.contains(*name)).map(|name| ("authentications", name)));
+        }
+        if let Some(errors) = &components.errors{
+            unused.extend(errors.keys().filter(|name| !self.referenced_errors.contains(*name)).map(|name| ("errors", name)));
+        }
+        if let Some(retries) = &components.retries{
+            unused.extend(retries.keys().filter(|name| !self.referenced_retries.contains(*name)).map(|name| ("retries", name)));
+        }
+        if let Some(timeouts) = &components.timeouts{
+            unused.extend(timeouts.keys().filter(|name| !self.referenced_timeouts.contains(*name)).map(|name| ("timeouts", name)));
+        }
+        unused.sort();
+        for (collection, name) in unused{
+            let path = format!("/use/{}/{}", collection, escape_json_pointer_segment(name));
+            self.diagnostics.push(ValidationDiagnostic::warning(&path, ValidationCode::UnusedComponent, format!("The component '{}' is defined in 'use.{}' but is never referenced", name, collection)));
+        }
+    }
+
+}
+impl Visitor for WorkflowValidator<'_>{
+
+    fn visit_task_list(&mut self, context: &TaskListContext, tasks: &Map<String, TaskDefinition>){
+        self.validate_task_list(&context.path, tasks);
+    }
+
+    fn visit_task(&mut self, context: &TaskContext, task: &TaskDefinition) -> bool{
+        self.validate_task(&context.path, task);
+        true
+    }
+
+}
@@ -0,0 +1,337 @@
+use crate::models::call::*;
+use crate::models::map::*;
+use crate::models::task::*;
+use crate::models::workflow::*;
+use crate::services::pointer::escape_json_pointer_segment;
+
+/// Enumerates the locations a task can be defined at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskContainer{
+    /// Indicates a task defined in the workflow's top-level 'do' list
+    Workflow,
+    /// Indicates a task defined in the 'do' list of a 'do' task
+    Do,
+    /// Indicates a task defined in the 'do' list of a 'for' task
+    For,
+    /// Indicates a task defined in the branches of a 'fork' task
+    Fork,
+    /// Indicates a task defined in the 'try' list of a 'try' task
+    Try,
+    /// Indicates a task defined in the 'catch.do' list of a 'try' task
+    Catch,
+    /// Indicates a task defined in the 'foreach.do' list of a 'listen' task, or in the 'with.subscription.foreach.do' list of an AsyncAPI call
+    Foreach,
+    /// Indicates a task defined in the 'before' list of an extension
+    ExtensionBefore,
+    /// Indicates a task defined in the 'after' list of an extension
+    ExtensionAfter,
+    /// Indicates a reusable function defined in the workflow's 'use.functions'
+    Function
+}
+
+/// Represents the task that defines the task or task list being visited
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskParent{
+
+    /// Gets/sets the name of the parent task
+    pub name: String,
+
+    /// Gets/sets the JSON pointer to the parent task
+    pub path: String,
+
+    /// Gets/sets the type of the parent task
+    pub task_type: TaskType
+
+}
+
+/// Represents the context of a task list being visited
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskListContext{
+
+    /// Gets/sets the JSON pointer to the task list
+    pub path: String,
+
+    /// Gets/sets the location the task list is defined at
+    pub container: TaskContainer,
+
+    /// Gets/sets the task, if any, that defines the task list
+    pub parent: Option<TaskParent>,
+
+    /// Gets/sets the number of tasks the task list is nested in
+    pub depth: usize
+
+}
+
+/// Represents the context of a task being visited
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskContext{
+
+    /// Gets/sets the JSON pointer to the task
+    pub path: String,
+
+    /// Gets/sets the name of the task
+    pub name: String,
+
+    /// Gets/sets the index, if any, of the task in the list that defines it. Not set for functions
+    pub index: Option<usize>,
+
+    /// Gets/sets the location the task is defined at
+    pub container: TaskContainer,
+
+    /// Gets/sets the task, if any, that defines the task
+    pub parent: Option<TaskParent>,
+
+    /// Gets/sets the number of tasks the task is nested in
+    pub depth: usize
+
+}
+impl TaskContext{
+
+    // Gets the parent of the subtasks of the task
+    fn as_parent(&self, task: &TaskDefinition) -> TaskParent{
+        TaskParent {
+            name: self.name.clone(),
+            path: self.path.clone(),
+            task_type: task.task_type()
+        }
+    }
+
+}
+
+/// Defines the fundamentals of a service used to visit the tasks of a workflow definition
+pub trait Visitor{
+
+    /// Visits the specified task list, before any of its tasks
+    fn visit_task_list(&mut self, _context: &TaskListContext, _tasks: &Map<String, TaskDefinition>){}
+
+    /// Visits the specified task, before any of its subtasks. Returns false to skip its subtasks
+    fn visit_task(&mut self, _context: &TaskContext, _task: &TaskDefinition) -> bool{
+        true
+    }
+
+    /// Leaves the specified task, after all of its subtasks have been visited
+    fn leave_task(&mut self, _context: &TaskContext, _task: &TaskDefinition){}
+
+}
+
+/// Defines the fundamentals of a service used to visit, and possibly rewrite, the tasks of a workflow definition
+pub trait VisitorMut{
+
+    /// Visits the specified task list, before any of its tasks
+    fn visit_task_list(&mut self, _context: &TaskListContext, _tasks: &mut Map<String, TaskDefinition>){}
+
+    /// Visits the specified task, before any of its subtasks. Returns false to skip its subtasks
+    fn visit_task(&mut self, _context: &TaskContext, _task: &mut TaskDefinition) -> bool{
+        true
+    }
+
+    /// Leaves the specified task, after all of its subtasks have been visited
+    fn leave_task(&mut self, _context: &TaskContext, _task: &mut TaskDefinition){}
+
+}
+
+/// Walks all the tasks of the specified workflow definition, in document order
+///
+/// Reusable functions are visited first, sorted by name, followed by the tasks of the extensions and then by the workflow's 'do' list.
+pub fn walk<V: Visitor + ?Sized>(workflow: &WorkflowDefinition, visitor: &mut V){
+    if let Some(components) = &workflow.use_{
+        if let Some(functions) = &components.functions{
+            let mut names: Vec<&String> = functions.keys().collect();
+            names.sort();
+            for name in names{
+                let context = function_context(name);
+                walk_task(&context, &functions[name], visitor);
+            }
+        }
+        if let Some(extensions) = &components.extensions{
+            for (index, entry) in extensions.entries.iter().enumerate(){
+                for (name, extension) in entry{
+                    let path = format!("/use/extensions/{}/{}", index, escape_json_pointer_segment(name));
+                    if let Some(before) = &extension.before{
+                        walk_task_list(&list_context(format!("{}/before", path), TaskContainer::ExtensionBefore, None, 0), before, visitor);
+                    }
+                    if let Some(after) = &extension.after{
+                        walk_task_list(&list_context(format!("{}/after", path), TaskContainer::ExtensionAfter, None, 0), after, visitor);
+                    }
+                }
+            }
+        }
+    }
+    walk_task_list(&list_context("/do".to_string(), TaskContainer::Workflow, None, 0), &workflow.do_, visitor);
+}
+
+/// Walks the specified task list and all of its subtasks
+pub fn walk_task_list<V: Visitor + ?Sized>(context: &TaskListContext, tasks: &Map<String, TaskDefinition>, visitor: &mut V){
+    visitor.visit_task_list(context, tasks);
+    for (index, entry) in tasks.entries.iter().enumerate(){
+        for (name, task) in entry{
+            let task_context = task_context(context, index, name);
+            walk_task(&task_context, task, visitor);
+        }
+    }
+}
+
+/// Walks the specified task and all of its subtasks
+pub fn walk_task<V: Visitor + ?Sized>(context: &TaskContext, task: &TaskDefinition, visitor: &mut V){
+    if visitor.visit_task(context, task){
+        let parent = context.as_parent(task);
+        let depth = context.depth + 1;
+        let path = &context.path;
+        match task{
+            TaskDefinition::Do(do_) => walk_task_list(&list_context(format!("{}/do", path), TaskContainer::Do, Some(parent), depth), &do_.do_, visitor),
+            TaskDefinition::For(for_) => walk_task_list(&list_context(format!("{}/do", path), TaskContainer::For, Some(parent), depth), &for_.do_, visitor),
+            TaskDefinition::Fork(fork) => walk_task_list(&list_context(format!("{}/fork/branches", path), TaskContainer::Fork, Some(parent), depth), &fork.fork.branches, visitor),
+            TaskDefinition::Listen(listen) => {
+                if let Some(do_) = listen.foreach.as_ref().and_then(|foreach| foreach.do_.as_ref()){
+                    walk_task_list(&list_context(format!("{}/foreach/do", path), TaskContainer::Foreach, Some(parent), depth), do_, visitor);
+                }
+            },
+            TaskDefinition::Call(call) => {
+                if let Some(do_) = subscription_tasks(call){
+                    walk_task_list(&list_context(format!("{}/with/subscription/foreach/do", path), TaskContainer::Foreach, Some(parent), depth), &do_, visitor);
+                }
+            },
+            TaskDefinition::Try(try_) => {
+                walk_task_list(&list_context(format!("{}/try", path), TaskContainer::Try, Some(parent.clone()), depth), &try_.try_, visitor);
+                if let Some(do_) = &try_.catch.do_{
+                    walk_task_list(&list_context(format!("{}/catch/do", path), TaskContainer::Catch, Some(parent), depth), do_, visitor);
+                }
+            },
+            _ => {}
+        }
+    }
+    visitor.leave_task(context, task);
+}
+
+/// Walks all the tasks of the specified workflow definition, in document order, allowing the visitor to rewrite them
+///
+/// Reusable functions are visited first, sorted by name, followed by the tasks of the extensions and then by the workflow's 'do' list.
+pub fn walk_mut<V: VisitorMut + ?Sized>(workflow: &mut WorkflowDefinition, visitor: &mut V){
+    if let Some(components) = &mut workflow.use_{
+        if let Some(functions) = &mut components.functions{
+            let mut names: Vec<String> = functions.keys().cloned().collect();
+            names.sort();
+            for name in names{
+                let context = function_context(&name);
+                if let Some(function) = functions.get_mut(&name){
+                    walk_task_mut(&context, function, visitor);
+                }
+            }
+        }
+        if let Some(extensions) = &mut components.extensions{
+            for (index, entry) in extensions.entries.iter_mut().enumerate(){
+                for (name, extension) in entry{
+                    let path = format!("/use/extensions/{}/{}", index, escape_json_pointer_segment(name));
+                    if let Some(before) = &mut extension.before{
+                        walk_task_list_mut(&list_context(format!("{}/before", path), TaskContainer::ExtensionBefore, None, 0), before, visitor);
+                    }
+                    if let Some(after) = &mut extension.after{
+                        walk_task_list_mut(&list_context(format!("{}/after", path), TaskContainer::ExtensionAfter, None, 0), after, visitor);
+                    }
+                }
+            }
+        }
+    }
+    walk_task_list_mut(&list_context("/do".to_string(), TaskContainer::Workflow, None, 0), &mut workflow.do_, visitor);
+}
+
+/// Walks the specified task list and all of its subtasks, allowing the visitor to rewrite them
+pub fn walk_task_list_mut<V: VisitorMut + ?Sized>(context: &TaskListContext, tasks: &mut Map<String, TaskDefinition>, visitor: &mut V){
+    visitor.visit_task_list(context, tasks);
+    for (index, entry) in tasks.entries.iter_mut().enumerate(){
+        for (name, task) in entry{
+            let task_context = task_context(context, index, name);
+            walk_task_mut(&task_context, task, visitor);
+        }
+    }
+}
+
+/// Walks the specified task and all of its subtasks, allowing the visitor to rewrite them
+pub fn walk_task_mut<V: VisitorMut + ?Sized>(context: &TaskContext, task: &mut TaskDefinition, visitor: &mut V){
+    if visitor.visit_task(context, task){
+        let parent = context.as_parent(task);
+        let depth = context.depth + 1;
+        let path = &context.path;
+        match task{
+            TaskDefinition::Do(do_) => walk_task_list_mut(&list_context(format!("{}/do", path), TaskContainer::Do, Some(parent), depth), &mut do_.do_, visitor),
+            TaskDefinition::For(for_) => walk_task_list_mut(&list_context(format!("{}/do", path), TaskContainer::For, Some(parent), depth), &mut for_.do_, visitor),
+            TaskDefinition::Fork(fork) => walk_task_list_mut(&list_context(format!("{}/fork/branches", path), TaskContainer::Fork, Some(parent), depth), &mut fork.fork.branches, visitor),
+            TaskDefinition::Listen(listen) => {
+                if let Some(do_) = listen.foreach.as_mut().and_then(|foreach| foreach.do_.as_mut()){
+                    walk_task_list_mut(&list_context(format!("{}/foreach/do", path), TaskContainer::Foreach, Some(parent), depth), do_, visitor);
+                }
+            },
+            TaskDefinition::Call(call) => {
+                if let Some(original) = subscription_tasks(call){
+                    let mut do_ = original.clone();
+                    walk_task_list_mut(&list_context(format!("{}/with/subscription/foreach/do", path), TaskContainer::Foreach, Some(parent), depth), &mut do_, visitor);
+                    if do_ != original{
+                        set_subscription_tasks(call, &do_);
+                    }
+                }
+            },
+            TaskDefinition::Try(try_) => {
+                walk_task_list_mut(&list_context(format!("{}/try", path), TaskContainer::Try, Some(parent.clone()), depth), &mut try_.try_, visitor);
+                if let Some(do_) = &mut try_.catch.do_{
+                    walk_task_list_mut(&list_context(format!("{}/catch/do", path), TaskContainer::Catch, Some(parent), depth), do_, visitor);
+                }
+            },
+            _ => {}
+        }
+    }
+    visitor.leave_task(context, task);
+}
+
+// Gets the tasks an AsyncAPI call performs for each consumed message, if any. Calls whose arguments are malformed are reported by the validator, and are not walked
+pub(crate) fn subscription_tasks(call: &CallTaskDefinition) -> Option<Map<String, TaskDefinition>>{
+    if CallFunction::AsyncApi != *call.call{
+        return None;
+    }
+    let do_ = call.with.as_ref()?.get("subscription")?.get("foreach")?.get("do")?;
+    serde_json::from_value(do_.clone()).ok()
+}
+
+// Writes the specified tasks back into the 'subscription.foreach.do' argument of the specified AsyncAPI call
+fn set_subscription_tasks(call: &mut CallTaskDefinition, tasks: &Map<String, TaskDefinition>){
+    let do_ = call.with.as_mut()
+        .and_then(|with| with.get_mut("subscription"))
+        .and_then(|subscription| subscription.get_mut("foreach"))
+        .and_then(|foreach| foreach.get_mut("do"));
+    if let (Some(do_), Ok(value)) = (do_, serde_json::to_value(tasks)){
+        *do_ = value;
+    }
+}
+
+// Creates the context of the specified task list
+fn list_context(path: String, container: TaskContainer, parent: Option<TaskParent>, depth: usize) -> TaskListContext{
+    TaskListContext {
+        path,
+        container,
+        parent,
+        depth
+    }
+}
+
+// Creates the context of the task with the specified name and index in the specified task list
+fn task_context(list: &TaskListContext, index: usize, name: &str) -> TaskContext{
+    TaskContext {
+        path: format!("{}/{}/{}", list.path, index, escape_json_pointer_segment(name)),
+        name: name.to_string(),
+        index: Some(index),
+        container: list.container,
+        parent: list.parent.clone(),
+        depth: list.depth
+    }
+}
+
+// Creates the context of the function with the specified name
+fn function_context(name: &str) -> TaskContext{
+    TaskContext {
+        path: format!("/use/functions/{}", escape_json_pointer_segment(name)),
+        name: name.to_string(),
+        index: None,
+        container: TaskContainer::Function,
+        parent: None,
+        depth: 0
+    }
+}
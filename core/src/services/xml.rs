@@ -0,0 +1,687 @@
+use roxmltree::{Document, Node};
+use serde_json::Value;
+use std::collections::HashMap;
+use crate::services::schema::*;
+
+/// Gets the namespace of the XML Schema definition language
+pub const XML_SCHEMA_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+
+// Gets the namespace of the XML Schema instance attributes, such as 'xsi:schemaLocation'
+const XML_SCHEMA_INSTANCE_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Validates the specified XML document against the specified XML schema (XSD)
+///
+/// Both the schema and the data must be strings containing XML documents. The following subset of XSD is supported:
+/// - top-level and local element declarations, element references, 'minOccurs' and 'maxOccurs'
+/// - named and anonymous complex types, with 'sequence', 'choice' and 'all' compositors, 'any' wildcards, 'mixed' content, simple content and complex content extensions
+/// - attribute declarations, 'use="required"' and 'anyAttribute' wildcards
+/// - named and anonymous simple types, restrictions with the 'enumeration', 'length', 'minLength', 'maxLength', 'minInclusive', 'maxInclusive', 'minExclusive' and 'maxExclusive' facets, lists and unions
+/// - the built-in string, boolean, numeric, date and time types
+///
+/// Schemas that define a target namespace, reference components of another namespace or use any other construct, such as patterns, groups, identity constraints, imports or other built-in types, are rejected with an 'unsupported XSD construct' error rather than partially enforced.
+/// Accordingly, the elements and attributes of the validated document must not be namespace qualified, except for the XML Schema instance attributes, such as 'xsi:schemaLocation'.
+pub fn validate_xml_schema(document: &Value, value: &Value) -> Result<(), SchemaValidationError>{
+    let schema_text = document.as_str().ok_or_else(|| SchemaValidationError::new("XML schemas must be set as a string", Vec::new()))?;
+    let schema = Document::parse(schema_text).map_err(|error| SchemaValidationError::new(&format!("invalid XML schema: {}", error), Vec::new()))?;
+    let text = value.as_str().ok_or_else(|| SchemaValidationError::new("the data must be a string containing an XML document", Vec::new()))?;
+    let instance = match Document::parse(text){
+        Ok(instance) => instance,
+        Err(error) => return Err(SchemaValidationError::new("the data does not conform to its XML schema", vec![SchemaViolation {
+            instance_path: String::new(),
+            schema_path: String::new(),
+            message: format!("the data is not a well-formed XML document: {}", error)
+        }]))
+    };
+    let validator = XmlSchemaValidator::new(&schema)?;
+    let mut violations = Vec::new();
+    validator.validate_root(instance.root_element(), &mut violations)
+        .map_err(|message| SchemaValidationError::new(&format!("invalid XML schema: {}", message), Vec::new()))?;
+    if violations.is_empty(){
+        Ok(())
+    }
+    else{
+        Err(SchemaValidationError::new("the data does not conform to its XML schema", violations))
+    }
+}
+
+// Represents a reference to a simple type
+#[derive(Clone, Copy)]
+enum SimpleType<'a, 'input>{
+    BuiltIn(&'a str),
+    Defined(Node<'a, 'input>)
+}
+
+// Represents a reference to the type of an element
+#[derive(Clone, Copy)]
+enum ElementType<'a, 'input>{
+    Simple(SimpleType<'a, 'input>),
+    Complex(Node<'a, 'input>)
+}
+
+// Represents the result of matching a particle: the position following the matched elements, along with the declarations to validate them against, or a message describing why the particle did not match
+type ParticleMatch<'a, 'input, 'b, 'c> = Result<(usize, Vec<(Option<Node<'a, 'input>>, Node<'b, 'c>)>), String>;
+
+// Represents the attributes, content particles and text type collected from a complex type and the types it derives from
+struct ComplexContent<'a, 'input>{
+    attributes: Vec<Node<'a, 'input>>,
+    any_attribute: bool,
+    particles: Vec<Node<'a, 'input>>,
+    text_type: Option<SimpleType<'a, 'input>>,
+    mixed: bool
+}
+
+// Represents the service used to validate XML documents against an XML schema
+struct XmlSchemaValidator<'a, 'input>{
+    elements: HashMap<&'a str, Node<'a, 'input>>,
+    complex_types: HashMap<&'a str, Node<'a, 'input>>,
+    simple_types: HashMap<&'a str, Node<'a, 'input>>
+}
+impl<'a, 'input> XmlSchemaValidator<'a, 'input>{
+
+    // Initializes a new XmlSchemaValidator for the specified schema document
+    fn new(schema: &'a Document<'input>) -> Result<Self, SchemaValidationError>{
+        let root = schema.root_element();
+        if !is_xs(root, "schema"){
+            return Err(SchemaValidationError::new("invalid XML schema: the root element must be 'schema', in the XML Schema namespace", Vec::new()));
+        }
+        if let Some(construct) = find_unsupported_construct(root){
+            return Err(SchemaValidationError::new(&format!("unsupported XSD construct {}", construct), Vec::new()));
+        }
+        let mut validator = Self {
+            elements: HashMap::new(),
+            complex_types: HashMap::new(),
+            simple_types: HashMap::new()
+        };
+        for child in root.children().filter(Node::is_element){
+            let Some(name) = child.attribute("name") else { continue };
+            match child.tag_name().name(){
+                "element" => validator.elements.insert(name, child),
+                "complexType" => validator.complex_types.insert(name, child),
+                "simpleType" => validator.simple_types.insert(name, child),
+                _ => None
+            };
+        }
+        Ok(validator)
+    }
+
+    // Validates the specified root element of an XML document
+    fn validate_root(&self, element: Node, violations: &mut Vec<SchemaViolation>) -> Result<(), String>{
+        match self.elements.get(element.tag_name().name()).filter(|_| element.tag_name().namespace().is_none()){
+            Some(declaration) => self.validate_element(*declaration, element, violations),
+            None => {
+                violations.push(violation(element, None, format!("the element '{}' is not declared", display_name(element))));
+                Ok(())
+            }
+        }
+    }
+
+    // Validates the specified element against the specified element declaration
+    fn validate_element(&self, declaration: Node<'a, 'input>, element: Node, violations: &mut Vec<SchemaViolation>) -> Result<(), String>{
+        let declaration = self.resolve_element(declaration)?;
+        match self.element_type(declaration)?{
+            None => Ok(()),
+            Some(ElementType::Complex(type_)) => self.validate_complex(type_, element, violations),
+            Some(ElementType::Simple(type_)) => {
+                if let Some(child) = element.children().find(Node::is_element){
+                    violations.push(violation(child, Some(declaration), format!("the element '{}' must only contain text", element.tag_name().name())));
+                    return Ok(());
+                }
+                self.validate_attributes(&[], false, element, declaration, violations)?;
+                if let Err(message) = self.validate_simple(type_, &element_text(element), &mut Vec::new())?{
+                    violations.push(violation(element, Some(declaration), message));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    // Validates the specified element against the specified complex type
+    fn validate_complex(&self, type_: Node<'a, 'input>, element: Node, violations: &mut Vec<SchemaViolation>) -> Result<(), String>{
+        let mut content = ComplexContent {
+            attributes: Vec::new(),
+            any_attribute: false,
+            particles: Vec::new(),
+            text_type: None,
+            mixed: type_.attribute("mixed") == Some("true")
+        };
+        self.collect_content(type_, &mut content, &mut vec![type_])?;
+        let ComplexContent { attributes, any_attribute, particles, text_type, mixed } = content;
+        self.validate_attributes(&attributes, any_attribute, element, type_, violations)?;
+        let children: Vec<Node> = element.children().filter(Node::is_element).collect();
+        if let Some(text_type) = text_type{
+            if let Some(child) = children.first(){
+                violations.push(violation(*child, Some(type_), format!("the element '{}' must only contain text", element.tag_name().name())));
+            }
+            else if let Err(message) = self.validate_simple(text_type, &element_text(element), &mut Vec::new())?{
+                violations.push(violation(element, Some(type_), message));
+            }
+            return Ok(());
+        }
+        if !mixed && !element_text(element).trim().is_empty(){
+            violations.push(violation(element, Some(type_), format!("the element '{}' must not contain text", element.tag_name().name())));
+        }
+        let mut position = 0;
+        let mut matches = Vec::new();
+        for particle in particles{
+            match self.match_particle(particle, &children, position)?{
+                Ok((next, particle_matches)) => {
+                    position = next;
+                    matches.extend(particle_matches);
+                },
+                Err(message) => {
+                    violations.push(violation(children.get(position).copied().unwrap_or(element), Some(particle), message));
+                    return Ok(());
+                }
+            }
+        }
+        if let Some(child) = children.get(position){
+            violations.push(violation(*child, Some(type_), format!("the element '{}' is not expected here", display_name(*child))));
+            return Ok(());
+        }
+        for (declaration, child) in matches{
+            if let Some(declaration) = declaration{
+                self.validate_element(declaration, child, violations)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Collects the attributes, content particles and text type defined by the specified complex type, including those of the types it extends
+    // The specified derivations are the complex types being collected, and are used to reject types that derive from themselves
+    fn collect_content(&self, type_: Node<'a, 'input>, content: &mut ComplexContent<'a, 'input>, derivations: &mut Vec<Node<'a, 'input>>) -> Result<(), String>{
+        for child in type_.children().filter(|child| child.is_element() && child.tag_name().namespace() == Some(XML_SCHEMA_NAMESPACE)){
+            match child.tag_name().name(){
+                "sequence" | "choice" | "all" => content.particles.push(child),
+                "attribute" => content.attributes.push(child),
+                "anyAttribute" => content.any_attribute = true,
+                "simpleContent" | "complexContent" => {
+                    if child.attribute("mixed") == Some("true"){
+                        content.mixed = true;
+                    }
+                    let Some(derivation) = child.children().find(|node| is_xs(*node, "extension") || is_xs(*node, "restriction")) else { continue };
+                    let base = derivation.attribute("base").ok_or("derivations must define a 'base'")?;
+                    match self.resolve_type(derivation, base)?{
+                        ElementType::Simple(simple) => content.text_type = Some(simple),
+                        ElementType::Complex(base_type) => {
+                            if derivations.contains(&base_type){
+                                return Err(format!("the type '{}' derives from itself", base));
+                            }
+                            let particles = content.particles.len();
+                            derivations.push(base_type);
+                            self.collect_content(base_type, content, derivations)?;
+                            derivations.pop();
+                            if is_xs(derivation, "restriction"){
+                                content.particles.truncate(particles);
+                            }
+                        }
+                    }
+                    self.collect_content(derivation, content, derivations)?;
+                },
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    // Validates the attributes of the specified element against the specified attribute declarations
+    fn validate_attributes(&self, declarations: &[Node<'a, 'input>], any_attribute: bool, element: Node, type_: Node, violations: &mut Vec<SchemaViolation>) -> Result<(), String>{
+        let mut declared = Vec::new();
+        for declaration in declarations{
+            let name = declaration.attribute("name").or(declaration.attribute("ref")).map(local_name).ok_or("attributes must define a 'name'")?;
+            declared.push(name);
+            match element.attributes().find(|attribute| attribute.name() == name && attribute.namespace().is_none()){
+                Some(attribute) => {
+                    let simple = match declaration.attribute("type"){
+                        Some(reference) => match self.resolve_type(*declaration, reference)?{
+                            ElementType::Simple(simple) => simple,
+                            ElementType::Complex(_) => return Err(format!("the type of the attribute '{}' must be a simple type", name))
+                        },
+                        None => match declaration.children().find(|child| is_xs(*child, "simpleType")){
+                            Some(simple) => SimpleType::Defined(simple),
+                            None => SimpleType::BuiltIn("anySimpleType")
+                        }
+                    };
+                    if let Err(message) = self.validate_simple(simple, attribute.value(), &mut Vec::new())?{
+                        violations.push(violation(element, Some(*declaration), format!("invalid value for the attribute '{}': {}", name, message)));
+                    }
+                },
+                None if declaration.attribute("use") == Some("required") => violations.push(violation(element, Some(*declaration), format!("the required attribute '{}' is missing", name))),
+                None => {}
+            }
+        }
+        if !any_attribute{
+            for attribute in element.attributes().filter(|attribute| attribute.namespace() != Some(XML_SCHEMA_INSTANCE_NAMESPACE) && (attribute.namespace().is_some() || !declared.contains(&attribute.name()))){
+                violations.push(violation(element, Some(type_), format!("the attribute '{}' is not declared", attribute.name())));
+            }
+        }
+        Ok(())
+    }
+
+    // Matches the specified particle, as many times as it allows, against the specified elements, starting at the specified position
+    fn match_particle<'b, 'c>(&self, particle: Node<'a, 'input>, elements: &[Node<'b, 'c>], position: usize) -> Result<ParticleMatch<'a, 'input, 'b, 'c>, String>{
+        let (min, max) = occurrences(particle)?;
+        let mut position = position;
+        let mut matches = Vec::new();
+        let mut count = 0;
+        while max.is_none_or(|max| count < max){
+            match self.match_once(particle, elements, position)?{
+                Ok((next, particle_matches)) if next > position || count < min => {
+                    let progressed = next > position;
+                    position = next;
+                    matches.extend(particle_matches);
+                    count += 1;
+                    if !progressed && count >= min{
+                        break;
+                    }
+                },
+                Ok(_) => break,
+                Err(message) if count < min => return Ok(Err(message)),
+                Err(_) => break
+            }
+        }
+        Ok(Ok((position, matches)))
+    }
+
+    // Matches a single occurrence of the specified particle against the specified elements, starting at the specified position
+    fn match_once<'b, 'c>(&self, particle: Node<'a, 'input>, elements: &[Node<'b, 'c>], position: usize) -> Result<ParticleMatch<'a, 'input, 'b, 'c>, String>{
+        match particle.tag_name().name(){
+            "element" => {
+                let declaration = self.resolve_element(particle)?;
+                let name = declaration.attribute("name").ok_or("elements must define a 'name'")?;
+                match elements.get(position){
+                    Some(element) if element.tag_name().name() == name && element.tag_name().namespace().is_none() => Ok(Ok((position + 1, vec![(Some(declaration), *element)]))),
+                    Some(element) => Ok(Err(format!("expected the element '{}' but found '{}'", name, display_name(*element)))),
+                    None => Ok(Err(format!("the element '{}' is missing", name)))
+                }
+            },
+            "any" => match elements.get(position){
+                Some(element) => Ok(Ok((position + 1, vec![(None, *element)]))),
+                None => Ok(Err("an element is missing".to_string()))
+            },
+            "sequence" => {
+                let mut position = position;
+                let mut matches = Vec::new();
+                for child in particles(particle){
+                    match self.match_particle(child, elements, position)?{
+                        Ok((next, child_matches)) => {
+                            position = next;
+                            matches.extend(child_matches);
+                        },
+                        Err(message) => return Ok(Err(message))
+                    }
+                }
+                Ok(Ok((position, matches)))
+            },
+            "choice" => {
+                let mut messages = Vec::new();
+                for child in particles(particle){
+                    match self.match_particle(child, elements, position)?{
+                        Ok((next, child_matches)) if next > position || elements.get(position).is_none() => return Ok(Ok((next, child_matches))),
+                        Ok(_) => messages.push(format!("'{}' did not match", particle_name(child))),
+                        Err(message) => messages.push(message)
+                    }
+                }
+                if particles(particle).any(|child| occurrences(child).is_ok_and(|(min, _)| min == 0)){
+                    return Ok(Ok((position, Vec::new())));
+                }
+                Ok(Err(format!("none of the alternatives of the choice matched: {}", messages.join("; "))))
+            },
+            "all" => {
+                let declarations: Vec<Node> = particles(particle).filter(|child| is_xs(*child, "element")).map(|child| self.resolve_element(child)).collect::<Result<_, _>>()?;
+                let mut position = position;
+                let mut matches = Vec::new();
+                let mut matched = vec![false; declarations.len()];
+                while let Some(element) = elements.get(position){
+                    let Some(index) = declarations.iter().enumerate().position(|(index, declaration)| !matched[index] && element.tag_name().namespace().is_none() && declaration.attribute("name") == Some(element.tag_name().name())) else { break };
+                    matched[index] = true;
+                    matches.push((Some(declarations[index]), *element));
+                    position += 1;
+                }
+                for (index, child) in particles(particle).filter(|child| is_xs(*child, "element")).enumerate(){
+                    if !matched[index] && occurrences(child)?.0 > 0{
+                        return Ok(Err(format!("the element '{}' is missing", particle_name(declarations[index]))));
+                    }
+                }
+                Ok(Ok((position, matches)))
+            },
+            _ => Ok(Ok((position, Vec::new())))
+        }
+    }
+
+    // Resolves the specified element declaration, following its reference, if any
+    fn resolve_element(&self, declaration: Node<'a, 'input>) -> Result<Node<'a, 'input>, String>{
+        match declaration.attribute("ref"){
+            Some(reference) => self.elements.get(local_name(reference)).copied().ok_or_else(|| format!("the element '{}' is not defined", reference)),
+            None => Ok(declaration)
+        }
+    }
+
+    // Gets the type, if any, of the specified element declaration. Returns None if the element accepts any content
+    fn element_type(&self, declaration: Node<'a, 'input>) -> Result<Option<ElementType<'a, 'input>>, String>{
+        if let Some(reference) = declaration.attribute("type"){
+            if local_name(reference) == "anyType" && declaration.lookup_namespace_uri(reference.split_once(':').map(|(prefix, _)| prefix)) == Some(XML_SCHEMA_NAMESPACE){
+                return Ok(None);
+            }
+            return self.resolve_type(declaration, reference).map(Some);
+        }
+        for child in declaration.children(){
+            if is_xs(child, "complexType"){
+                return Ok(Some(ElementType::Complex(child)));
+            }
+            if is_xs(child, "simpleType"){
+                return Ok(Some(ElementType::Simple(SimpleType::Defined(child))));
+            }
+        }
+        Ok(None)
+    }
+
+    // Resolves the type with the specified qualified name, as referenced by the specified node
+    fn resolve_type(&self, node: Node<'a, 'input>, reference: &'a str) -> Result<ElementType<'a, 'input>, String>{
+        let (prefix, name) = match reference.split_once(':'){
+            Some((prefix, name)) => (Some(prefix), name),
+            None => (None, reference)
+        };
+        if node.lookup_namespace_uri(prefix) == Some(XML_SCHEMA_NAMESPACE){
+            return Ok(ElementType::Simple(SimpleType::BuiltIn(name)));
+        }
+        if let Some(type_) = self.complex_types.get(name){
+            return Ok(ElementType::Complex(*type_));
+        }
+        if let Some(type_) = self.simple_types.get(name){
+            return Ok(ElementType::Simple(SimpleType::Defined(*type_)));
+        }
+        Err(format!("the type '{}' is not defined", reference))
+    }
+
+    // Validates the specified text against the specified simple type, returning an error if the schema is invalid, or a message describing why the text does not conform to the type
+    // The specified derivations are the simple types being validated, and are used to reject types that derive from themselves
+    fn validate_simple(&self, type_: SimpleType<'a, 'input>, text: &str, derivations: &mut Vec<Node<'a, 'input>>) -> Result<Result<(), String>, String>{
+        let definition = match type_{
+            SimpleType::BuiltIn(name) => return Ok(validate_built_in(name, text)),
+            SimpleType::Defined(definition) => definition
+        };
+        if derivations.contains(&definition){
+            return Err(format!("the type '{}' derives from itself", definition.attribute("name").unwrap_or(definition.tag_name().name())));
+        }
+        derivations.push(definition);
+        let result = self.validate_derived_simple(definition, text, derivations);
+        derivations.pop();
+        result
+    }
+
+    // Validates the specified text against the derivation of the specified simple type definition
+    fn validate_derived_simple(&self, definition: Node<'a, 'input>, text: &str, derivations: &mut Vec<Node<'a, 'input>>) -> Result<Result<(), String>, String>{
+        for child in definition.children().filter(Node::is_element){
+            match child.tag_name().name(){
+                "restriction" => {
+                    let base = match child.attribute("base"){
+                        Some(base) => match self.resolve_type(child, base)?{
+                            ElementType::Simple(base) => Some(base),
+                            ElementType::Complex(_) => None
+                        },
+                        None => child.children().find(|node| is_xs(*node, "simpleType")).map(SimpleType::Defined)
+                    };
+                    if let Some(base) = base{
+                        if let Err(message) = self.validate_simple(base, text, derivations)?{
+                            return Ok(Err(message));
+                        }
+                    }
+                    return Ok(validate_facets(child, text));
+                },
+                "list" => {
+                    let item_type = match child.attribute("itemType"){
+                        Some(reference) => match self.resolve_type(child, reference)?{
+                            ElementType::Simple(item_type) => item_type,
+                            ElementType::Complex(_) => return Ok(Ok(()))
+                        },
+                        None => match child.children().find(|node| is_xs(*node, "simpleType")){
+                            Some(item_type) => SimpleType::Defined(item_type),
+                            None => return Ok(Ok(()))
+                        }
+                    };
+                    for item in text.split_whitespace(){
+                        if let Err(message) = self.validate_simple(item_type, item, derivations)?{
+                            return Ok(Err(message));
+                        }
+                    }
+                    return Ok(Ok(()));
+                },
+                "union" => {
+                    let mut members = Vec::new();
+                    for reference in child.attribute("memberTypes").unwrap_or_default().split_whitespace(){
+                        if let ElementType::Simple(member) = self.resolve_type(child, reference)?{
+                            members.push(member);
+                        }
+                    }
+                    members.extend(child.children().filter(|node| is_xs(*node, "simpleType")).map(SimpleType::Defined));
+                    if members.is_empty(){
+                        return Ok(Ok(()));
+                    }
+                    for member in members{
+                        if self.validate_simple(member, text, derivations)?.is_ok(){
+                            return Ok(Ok(()));
+                        }
+                    }
+                    return Ok(Err(format!("'{}' does not match any of the member types of the union", text)));
+                },
+                _ => {}
+            }
+        }
+        Ok(Ok(()))
+    }
+
+}
+
+// Gets the local names of the XML Schema elements the validator supports
+const SUPPORTED_ELEMENTS: &[&str] = &[
+    "schema", "annotation", "element", "attribute", "anyAttribute", "complexType", "simpleType", "sequence", "choice", "all", "any",
+    "simpleContent", "complexContent", "extension", "restriction", "list", "union",
+    "enumeration", "length", "minLength", "maxLength", "minInclusive", "maxInclusive", "minExclusive", "maxExclusive"
+];
+
+// Gets the local names of the built-in types the validator supports
+const SUPPORTED_BUILT_IN_TYPES: &[&str] = &[
+    "anyType", "anySimpleType", "string", "normalizedString", "token", "boolean", "decimal", "float", "double", "integer", "long", "int", "short", "byte",
+    "nonNegativeInteger", "positiveInteger", "nonPositiveInteger", "negativeInteger", "unsignedLong", "unsignedInt", "unsignedShort", "unsignedByte",
+    "date", "time", "dateTime", "duration"
+];
+
+// Finds the first construct of the specified schema that the validator does not support, if any, and describes it
+fn find_unsupported_construct(schema: Node) -> Option<String>{
+    if schema.attribute("targetNamespace").is_some(){
+        return Some("'targetNamespace' at /schema".to_string());
+    }
+    let nodes = schema.descendants().filter(|node| is_xs(*node, node.tag_name().name()) && !node.ancestors().skip(1).any(|ancestor| is_xs(ancestor, "annotation")));
+    for node in nodes{
+        let name = node.tag_name().name();
+        if !SUPPORTED_ELEMENTS.contains(&name){
+            return Some(format!("'xs:{}' at {}", name, node_path(node)));
+        }
+        let references = ["ref", "type", "base", "itemType", "memberTypes"].into_iter()
+            .filter_map(|attribute| node.attribute(attribute).map(|value| (attribute, value)))
+            .flat_map(|(attribute, value)| value.split_whitespace().map(move |reference| (attribute, reference)));
+        for (attribute, reference) in references{
+            let prefix = reference.split_once(':').map(|(prefix, _)| prefix);
+            match node.lookup_namespace_uri(prefix){
+                Some(XML_SCHEMA_NAMESPACE) if attribute != "ref" && SUPPORTED_BUILT_IN_TYPES.contains(&local_name(reference)) => {},
+                Some(XML_SCHEMA_NAMESPACE) if attribute != "ref" => return Some(format!("built-in type '{}' at {}", reference, node_path(node))),
+                Some(namespace) => return Some(format!("reference to the namespace '{}' at {}", namespace, node_path(node))),
+                None => {}
+            }
+        }
+    }
+    None
+}
+
+// Gets the name of the specified element, for use in messages, prefixed with its namespace between braces if it is namespace qualified
+fn display_name(element: Node) -> String{
+    match element.tag_name().namespace(){
+        Some(namespace) => format!("{{{}}}{}", namespace, element.tag_name().name()),
+        None => element.tag_name().name().to_string()
+    }
+}
+
+// Determines whether or not the specified node is an XML Schema element with the specified local name
+fn is_xs(node: Node, name: &str) -> bool{
+    node.is_element() && node.tag_name().namespace() == Some(XML_SCHEMA_NAMESPACE) && node.tag_name().name() == name
+}
+
+// Gets the local part of the specified qualified name
+fn local_name(name: &str) -> &str{
+    name.rsplit_once(':').map(|(_, name)| name).unwrap_or(name)
+}
+
+// Gets the name of the specified particle, for use in messages
+fn particle_name<'a>(particle: Node<'a, '_>) -> &'a str{
+    particle.attribute("name").or(particle.attribute("ref")).unwrap_or(particle.tag_name().name())
+}
+
+// Gets the particles nested in the specified compositor
+fn particles<'a, 'input>(compositor: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>>{
+    compositor.children().filter(|child| child.is_element() && child.tag_name().namespace() == Some(XML_SCHEMA_NAMESPACE) && matches!(child.tag_name().name(), "element" | "sequence" | "choice" | "all" | "any"))
+}
+
+// Gets the minimum and maximum, if bounded, occurrences of the specified particle
+fn occurrences(particle: Node) -> Result<(usize, Option<usize>), String>{
+    let min = match particle.attribute("minOccurs"){
+        Some(min) => min.trim().parse::<usize>().map_err(|_| format!("invalid 'minOccurs' value '{}'", min))?,
+        None => 1
+    };
+    let max = match particle.attribute("maxOccurs"){
+        Some("unbounded") => None,
+        Some(max) => Some(max.trim().parse::<usize>().map_err(|_| format!("invalid 'maxOccurs' value '{}'", max))?),
+        None => Some(1)
+    };
+    Ok((min, max))
+}
+
+// Gets the concatenated text content of the specified element
+fn element_text(element: Node) -> String{
+    element.children().filter(Node::is_text).filter_map(|node| node.text()).collect()
+}
+
+// Builds the path, in XPath notation, to the specified node
+fn node_path(node: Node) -> String{
+    let mut segments = Vec::new();
+    let mut current = Some(node);
+    while let Some(node) = current.filter(Node::is_element){
+        let name = node.tag_name().name();
+        let index = node.prev_siblings().skip(1).filter(|sibling| sibling.is_element() && sibling.tag_name().name() == name).count() + 1;
+        let count = node.parent().map(|parent| parent.children().filter(|sibling| sibling.is_element() && sibling.tag_name().name() == name).count()).unwrap_or(1);
+        segments.push(if count > 1 { format!("{}[{}]", name, index) } else { name.to_string() });
+        current = node.parent();
+    }
+    segments.reverse();
+    format!("/{}", segments.join("/"))
+}
+
+// Creates a new violation concerning the specified node of the validated document and the specified node, if any, of the schema
+fn violation(node: Node, schema: Option<Node>, message: String) -> SchemaViolation{
+    SchemaViolation {
+        instance_path: node_path(node),
+        schema_path: schema.map(node_path).unwrap_or_default(),
+        message
+    }
+}
+
+// Validates the specified text against the facets of the specified restriction
+fn validate_facets(restriction: Node, text: &str) -> Result<(), String>{
+    let enumeration: Vec<&str> = restriction.children().filter(|child| is_xs(*child, "enumeration")).filter_map(|child| child.attribute("value")).collect();
+    if !enumeration.is_empty() && !enumeration.contains(&text.trim()){
+        return Err(format!("'{}' is not one of the allowed values: {}", text.trim(), enumeration.join(", ")));
+    }
+    let length = text.chars().count();
+    let number = text.trim().parse::<f64>().ok();
+    for facet in restriction.children().filter(Node::is_element){
+        let Some(value) = facet.attribute("value") else { continue };
+        let bound = value.trim().parse::<f64>().ok();
+        let valid = match facet.tag_name().name(){
+            "length" => bound.is_none_or(|bound| length as f64 == bound),
+            "minLength" => bound.is_none_or(|bound| length as f64 >= bound),
+            "maxLength" => bound.is_none_or(|bound| length as f64 <= bound),
+            "minInclusive" => number.zip(bound).is_none_or(|(number, bound)| number >= bound),
+            "maxInclusive" => number.zip(bound).is_none_or(|(number, bound)| number <= bound),
+            "minExclusive" => number.zip(bound).is_none_or(|(number, bound)| number > bound),
+            "maxExclusive" => number.zip(bound).is_none_or(|(number, bound)| number < bound),
+            _ => true
+        };
+        if !valid{
+            return Err(format!("'{}' does not satisfy the '{}' facet of value '{}'", text, facet.tag_name().name(), value));
+        }
+    }
+    Ok(())
+}
+
+// Validates the specified text against the built-in simple type with the specified name
+fn validate_built_in(name: &str, text: &str) -> Result<(), String>{
+    let value = text.trim();
+    let integer = || value.parse::<i128>().ok();
+    let valid = match name{
+        "boolean" => matches!(value, "true" | "false" | "1" | "0"),
+        "decimal" => !value.contains(['e', 'E']) && value.parse::<f64>().is_ok(),
+        "float" | "double" => matches!(value, "INF" | "-INF" | "NaN") || value.parse::<f64>().is_ok(),
+        "integer" => integer().is_some(),
+        "long" => integer().is_some_and(|number| i64::try_from(number).is_ok()),
+        "int" => integer().is_some_and(|number| i32::try_from(number).is_ok()),
+        "short" => integer().is_some_and(|number| i16::try_from(number).is_ok()),
+        "byte" => integer().is_some_and(|number| i8::try_from(number).is_ok()),
+        "nonNegativeInteger" => integer().is_some_and(|number| number >= 0),
+        "positiveInteger" => integer().is_some_and(|number| number > 0),
+        "nonPositiveInteger" => integer().is_some_and(|number| number <= 0),
+        "negativeInteger" => integer().is_some_and(|number| number < 0),
+        "unsignedLong" => integer().is_some_and(|number| u64::try_from(number).is_ok()),
+        "unsignedInt" => integer().is_some_and(|number| u32::try_from(number).is_ok()),
+        "unsignedShort" => integer().is_some_and(|number| u16::try_from(number).is_ok()),
+        "unsignedByte" => integer().is_some_and(|number| u8::try_from(number).is_ok()),
+        "date" => is_date(value),
+        "time" => is_time(value),
+        "dateTime" => value.split_once('T').is_some_and(|(date, time)| is_date(date) && is_time(time)),
+        "duration" => value.trim_start_matches('-').starts_with('P') && value.len() > 1,
+        _ => true
+    };
+    if valid{
+        Ok(())
+    }
+    else{
+        Err(format!("'{}' is not a valid '{}'", value, name))
+    }
+}
+
+// Determines whether or not the specified value is a date, in the 'YYYY-MM-DD' format followed by an optional timezone
+fn is_date(value: &str) -> bool{
+    let bytes = value.as_bytes();
+    bytes.len() >= 10
+        && bytes[..10].iter().enumerate().all(|(index, byte)| if index == 4 || index == 7 { *byte == b'-' } else { byte.is_ascii_digit() })
+        && value[5..7].parse::<u8>().is_ok_and(|month| (1..=12).contains(&month))
+        && value[8..10].parse::<u8>().is_ok_and(|day| (1..=31).contains(&day))
+        && is_timezone(&value[10..])
+}
+
+// Determines whether or not the specified value is a time, in the 'hh:mm:ss' format followed by optional fractional seconds and timezone
+fn is_time(value: &str) -> bool{
+    let bytes = value.as_bytes();
+    if bytes.len() < 8 || !bytes[..8].iter().enumerate().all(|(index, byte)| if index == 2 || index == 5 { *byte == b':' } else { byte.is_ascii_digit() }){
+        return false;
+    }
+    let mut rest = &value[8..];
+    if let Some(fraction) = rest.strip_prefix('.'){
+        let digits = fraction.chars().take_while(char::is_ascii_digit).count();
+        if digits == 0{
+            return false;
+        }
+        rest = &fraction[digits..];
+    }
+    value[0..2].parse::<u8>().is_ok_and(|hours| hours <= 24)
+        && value[3..5].parse::<u8>().is_ok_and(|minutes| minutes < 60)
+        && value[6..8].parse::<u8>().is_ok_and(|seconds| seconds < 60)
+        && is_timezone(rest)
+}
+
+// Determines whether or not the specified value is empty or a timezone, in the 'Z' or '(+|-)hh:mm' format
+fn is_timezone(value: &str) -> bool{
+    match value.as_bytes(){
+        [] | [b'Z'] => true,
+        [b'+' | b'-', h1, h2, b':', m1, m2] => [h1, h2, m1, m2].iter().all(|byte| byte.is_ascii_digit()),
+        _ => false
+    }
+}